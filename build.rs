@@ -1,16 +1,17 @@
-use std::fs;
-use std::io::Write;
-use std::path::Path;
-
 fn main() {
     // Generate protobuf crate proto rust code
-    let out_dir = std::env::var("OUT_DIR").expect("Unable to get OUT_DIR");
     #[cfg(feature = "protobuf")]
     {
+        use std::fs;
+        use std::io::Write;
+        use std::path::Path;
+
+        let out_dir = std::env::var("OUT_DIR").expect("Unable to get OUT_DIR");
         protobuf_codegen::Codegen::new()
             .pure()
             .out_dir(&out_dir)
             .input("tests/proto/entities.proto")
+            .input("tests/proto/legacy.proto")
             .include("tests/proto")
             .customize(
                 protobuf_codegen::Customize::default().generate_accessors(true), // .gen_mod_rs(true),
@@ -20,6 +21,7 @@ fn main() {
         // Override the mod file to include all the generated protos
         let mod_file_content = r#"//@generated
     pub mod entities;
+    pub mod legacy;
     "#;
         let mod_file_path = Path::new(&out_dir).join("mod.rs");
 
@@ -28,6 +30,13 @@ fn main() {
             .expect("Unable to write mod.rs file");
     }
     // Generate prost crate proto rust code
+    // (type names implement `prost::Name` used in `google.protobuf.Any` type urls)
     #[cfg(feature = "prost")]
-    prost_build::compile_protos(&["tests/proto/entities.proto"], &["/tests"]).unwrap();
+    prost_build::Config::new()
+        .enable_type_names()
+        .compile_protos(
+            &["tests/proto/entities.proto", "tests/proto/legacy.proto"],
+            &["tests/proto"],
+        )
+        .unwrap();
 }
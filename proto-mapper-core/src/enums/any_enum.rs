@@ -0,0 +1,63 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Path;
+
+/// Implementation of proto_map for `any` attributed `enum` items (same for both proto frameworks).
+///
+/// Every variant packs its inner `ProtoMapAny` type, the variant is selected by the type url of the
+/// `google.protobuf.Any` message.
+pub(crate) fn implement_any_proto_map<'a>(
+    name: &Ident,
+    proto_struct: &Path,
+    variants: impl Iterator<Item = (&'a Ident, &'a Path)> + Clone,
+) -> TokenStream {
+    let implement_to_proto = |method: TokenStream| {
+        let match_arms = variants.clone().map(|(variant_name, _)| {
+            quote! {
+                Self::#variant_name(value) => ProtoMapAny::#method(value),
+            }
+        });
+
+        quote! {
+            match self {
+                #( #match_arms )*
+            }
+        }
+    };
+    let to_proto_impl = implement_to_proto(quote! { to_any });
+    let into_proto_impl = implement_to_proto(quote! { into_any });
+
+    let from_proto_impl = {
+        let checks = variants.map(|(variant_name, field_name)| {
+            quote! {
+                if type_name == <#field_name as ProtoMapAny>::type_name() {
+                    return <#field_name as ProtoMapAny>::from_any(proto).map(Self::#variant_name);
+                }
+            }
+        });
+
+        quote! {
+            let type_name = proto_mapper::any::type_name_of_url(&proto.type_url).to_owned();
+            #( #checks )*
+            Err(ProtoMapError::unknown_type_url(proto.type_url))
+        }
+    };
+
+    quote! {
+        impl ProtoMap for #name {
+            type ProtoStruct = #proto_struct;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                #to_proto_impl
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                #into_proto_impl
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                #from_proto_impl
+            }
+        }
+    }
+}
@@ -1,12 +1,79 @@
 use darling::FromMeta;
-use proc_macro2::Ident;
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
 use syn::{Attribute, Path};
-use crate::find_proto_map_meta;
+use crate::{find_proto_map_meta, rename_item, AUTO_ATTRIBUTE_VALUE};
 
 /// Meta attribute used in `enum` items to mark one_of field name
 #[derive(Debug, FromMeta, PartialEq)]
 pub(crate) struct OneOf {
     pub field: Ident,
+    /// Optional path of the enum generated for the `oneof` field (e.g. of nested or package qualified messages),
+    /// defaults to the `snake_case` module of the source message e.g. `proto::hierarchy_entity::Data`.
+    pub path: Option<Path>,
+    /// Optional policy of a `oneof` field without a set member (defaults to `error`).
+    #[darling(default)]
+    pub not_set: NotSet,
+}
+
+/// Policy of `oneof` fields without a set member
+#[derive(Debug, Clone, Copy, Default, PartialEq, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum NotSet {
+    /// Fails with `ProtoMapError::OneOfNotSet`.
+    #[default]
+    Error,
+    /// Maps to the variant marked with `#[proto_map(default)]` (payloads use their default value).
+    Default,
+    /// Maps to the unit variant marked with `#[proto_map(not_set)]` (that leaves the `oneof` field unset).
+    Variant,
+}
+
+impl OneOf {
+    /// Returns the path of the enum generated for the `oneof` field of the `source` message.
+    pub(crate) fn proto_enum_path(&self, source: &Path) -> TokenStream {
+        if let Some(path) = &self.path {
+            return quote! { #path };
+        }
+
+        let mut source_module_name = source.clone();
+        let message = source_module_name
+            .segments
+            .pop()
+            .unwrap()
+            .value()
+            .ident
+            .clone();
+        let module = format_ident!("{}", message.to_string().to_snake_case());
+        let one_of_enum = format_ident!("{}", self.field.to_string().to_upper_camel_case());
+        quote! { #source_module_name #module::#one_of_enum }
+    }
+
+    /// Checks that the `default` and `not_set` marked variants match the `not_set` policy
+    pub(crate) fn validate_variants(&self, variants: &[&VariantAttrs]) -> darling::Result<()> {
+        let defaults = variants.iter().filter(|attrs| attrs.default).count();
+        let not_sets = variants.iter().filter(|attrs| attrs.not_set).count();
+        match (self.not_set, defaults, not_sets) {
+            (NotSet::Error, 0, 0) | (NotSet::Default, 1, 0) | (NotSet::Variant, 0, 1) => Ok(()),
+            (NotSet::Error, _, _) => Err(darling::Error::unsupported_shape(
+                "Variant attributes `default` and `not_set` require the `not_set` policy e.g. `one_of(field = \"...\", not_set = \"default\")`",
+            )),
+            (NotSet::Default, _, _) => Err(darling::Error::unsupported_shape(
+                "The `not_set = \"default\"` policy requires a single variant marked with `default`",
+            )),
+            (NotSet::Variant, _, _) => Err(darling::Error::unsupported_shape(
+                "The `not_set = \"variant\"` policy requires a single unit variant marked with `not_set`",
+            )),
+        }
+    }
+}
+
+/// Meta attribute used in `enum` items mapped to a message with a discriminator (tag) field instead of a `oneof`
+#[derive(Debug, FromMeta, PartialEq)]
+pub(crate) struct Tagged {
+    /// The proto enumeration field of the message that tags the variant e.g. `kind`
+    pub tag: Ident,
 }
 
 /// Meta attributes for `enum` items.
@@ -23,8 +90,36 @@ pub(crate) struct EnumAttrs {
     /// Mutually exclusive with `one_of`
     pub enumeration: Option<bool>,
 
+    /// Indicates that the proto entity is a message with a discriminator field (tagged union) where every variant
+    /// maps to a value of the discriminator and its payload fields.
+    /// Mutually exclusive with `enumeration`, `one_of` and `any`.
+    pub tagged: Option<Tagged>,
+
+    /// Indicates that the proto entity is a `google.protobuf.Any` message that packs one of the variant types
+    /// (every variant type implements `ProtoMapAny`).
+    /// Mutually exclusive with `enumeration` and `one_of`.
+    pub any: Option<bool>,
+
+    /// Indicates that the enum is merged as a whole by `google.protobuf.FieldMask` paths
+    /// (implements `ProtoMapFieldMask`). Not supported with `enumeration`.
+    pub field_mask: Option<bool>,
+
+    /// Indicates that the enum is merged from proto messages by replacing the current variant
+    /// (implements `ProtoMapMerge`). Not supported with `enumeration`.
+    pub merge: Option<bool>,
+
     /// Optional renaming of the variant fields before mapping to the proto entity.
+    /// The `auto` value of `enumeration` enums names the variants as the `STREAMING_SNAKE_CASE` values of the
+    /// `.proto` file and converts them to the names generated by the enabled proto framework.
     pub rename_variants: Option<String>,
+
+    /// Optional prefix removed from the variant names before renaming (e.g. `Status` of `StatusA`).
+    /// Supported only with `enumeration`.
+    pub strip_prefix: Option<String>,
+
+    /// Optional prefix added to the renamed variant names (e.g. `ENTITY_STATUS_`).
+    /// Supported only with `enumeration`.
+    pub add_prefix: Option<String>,
 }
 
 impl EnumAttrs {
@@ -32,10 +127,68 @@ impl EnumAttrs {
         self.enumeration.is_some_and(|e| e)
     }
 
+    pub(crate) fn is_any(&self) -> bool {
+        self.any.is_some_and(|a| a)
+    }
+
+    pub(crate) fn is_field_mask(&self) -> bool {
+        self.field_mask.is_some_and(|f| f)
+    }
+
+    pub(crate) fn is_merge(&self) -> bool {
+        self.merge.is_some_and(|m| m)
+    }
+
+    /// Returns true if the variants are named as the values of the `.proto` file for every proto framework
+    pub(crate) fn is_auto_naming(&self) -> bool {
+        self.rename_variants.as_deref() == Some(AUTO_ATTRIBUTE_VALUE)
+    }
+
+    /// Returns the proto name of a variant following the variant `rename`, the `strip_prefix`, `rename_variants`
+    /// and `add_prefix` attributes (in this order).
+    pub(crate) fn proto_variant_name(&self, name: &Ident, attrs: &VariantAttrs) -> String {
+        if let Some(rename) = attrs.rename.as_ref() {
+            return rename.clone();
+        }
+
+        let name = name.to_string();
+        let name = match self.strip_prefix.as_ref() {
+            Some(prefix) => name.strip_prefix(prefix.as_str()).unwrap_or(&name),
+            None => &name,
+        };
+        let name = match self.rename_variants.as_ref() {
+            // Note: the case is validated when parsing
+            Some(rename_variants) => rename_item(name, rename_variants).unwrap(),
+            None => name.to_owned(),
+        };
+        match self.add_prefix.as_ref() {
+            Some(prefix) => format!("{}{}", prefix, name),
+            None => name,
+        }
+    }
+
     fn validate(self) -> darling::Result<Self> {
+        if let Some(rename_variants) = self.rename_variants.as_ref() {
+            rename_item("", rename_variants)?;
+        }
+        if !self.is_enumeration() && (self.is_auto_naming() || self.strip_prefix.is_some() || self.add_prefix.is_some()) {
+            return Err(darling::Error::unsupported_shape("Enum attributes `rename_variants = \"auto\"`, `strip_prefix` and `add_prefix` are supported only with `enumeration`"));
+        }
         if self.is_enumeration() && self.one_of.is_some() {
             return Err(darling::Error::unsupported_shape("Enum attributes `enumeration` and `one_of` are mutually excluded (use only one of them)"));
         }
+        if self.tagged.is_some() && (self.is_enumeration() || self.one_of.is_some() || self.is_any()) {
+            return Err(darling::Error::unsupported_shape("Enum attribute `tagged` is mutually excluded with `enumeration`, `one_of` and `any` (use only one of them)"));
+        }
+        if self.is_any() && (self.is_enumeration() || self.one_of.is_some()) {
+            return Err(darling::Error::unsupported_shape("Enum attribute `any` is mutually excluded with `enumeration` and `one_of` (use only one of them)"));
+        }
+        if self.is_field_mask() && self.is_enumeration() {
+            return Err(darling::Error::unsupported_shape("Enum attribute `field_mask` is not supported with `enumeration` (enumeration fields are overwritten as a whole)"));
+        }
+        if self.is_merge() && self.is_enumeration() {
+            return Err(darling::Error::unsupported_shape("Enum attribute `merge` is not supported with `enumeration` (enumeration fields are overwritten when set)"));
+        }
         Ok(self)
     }
 }
@@ -51,3 +204,112 @@ impl TryFrom<&[Attribute]> for EnumAttrs {
         attrs.validate()
     }
 }
+
+/// Error of the variant attributes that are supported only for `enumeration` attributed enums
+pub(crate) const ENUMERATION_ONLY_ATTRIBUTES_ERROR: &str =
+    "Variant attributes `unknown`, `other`, `rename`, `alias` and `value` are supported only for `enumeration` attributed enums";
+
+/// Error of the variant attributes that are supported only for `one_of` attributed enums
+pub(crate) const ONE_OF_ONLY_ATTRIBUTES_ERROR: &str =
+    "Variant attributes `scalar`, `enumeration`, `with`, `default` and `not_set` are supported only for `one_of` attributed enums";
+
+/// Error of the variant attributes that are supported only for `tagged` attributed enums
+pub(crate) const TAGGED_ONLY_ATTRIBUTES_ERROR: &str =
+    "Variant attributes `tag` and `payload` are supported only for `tagged` attributed enums";
+
+/// Meta attributes for `enum` variants
+#[derive(Debug, Default, FromMeta)]
+#[darling(default)]
+pub(crate) struct VariantAttrs {
+    /// Optional mark the variant of an `enumeration` as the catch all of unknown proto values that keeps the raw
+    /// value e.g. `Unrecognized(i32)` (and writes it back to the proto entity).
+    pub unknown: bool,
+    /// Optional mark a unit variant of an `enumeration` as the fallback of every unknown proto value.
+    pub other: bool,
+    /// Optional name of the proto enumeration value (overrides `rename_variants`) e.g. `ENTITY_STATUS_ACTIVE`.
+    pub rename: Option<String>,
+    /// Optional extra names of proto enumeration values that map to the variant (e.g. `allow_alias` duplicates).
+    #[darling(multiple)]
+    pub alias: Vec<String>,
+    /// Optional raw number of the proto enumeration value (used instead of the generated enumeration value).
+    pub value: Option<i32>,
+    /// Optional mark the payload of a `one_of` variant as an scalar type mapping.
+    pub scalar: bool,
+    /// Optional mark the payload of a `one_of` variant as an enumeration mapping.
+    pub enumeration: bool,
+    /// Optional module with implementation of override mappings of the payload of a `one_of` variant
+    /// (implementation depends on scalar, enumeration or other proto destination type).
+    pub with: Option<Path>,
+    /// Optional mark the variant of a `one_of` enum as the value of an unset `oneof` (`not_set = "default"` policy).
+    pub default: bool,
+    /// Optional mark a unit variant of a `one_of` enum as the unset `oneof` (`not_set = "variant"` policy).
+    pub not_set: bool,
+    /// Optional path of the proto enumeration value that tags the variant of a `tagged` enum
+    /// e.g. `proto::EventKind::Created`.
+    pub tag: Option<Path>,
+    /// Optional proto field of the payload of an unnamed `tagged` variant (defaults to the `snake_case` variant name).
+    pub payload: Option<String>,
+}
+
+impl VariantAttrs {
+    /// Returns true if the variant is the `unknown` or the `other` catch all variant
+    pub(crate) fn is_catch_all(&self) -> bool {
+        self.unknown || self.other
+    }
+
+    /// Returns true if any of the attributes supported only for `enumeration` variants is set
+    pub(crate) fn is_enumeration_only(&self) -> bool {
+        self.is_catch_all() || self.rename.is_some() || !self.alias.is_empty() || self.value.is_some()
+    }
+
+    /// Returns true if any of the attributes that override the mapping of `one_of` variant payloads is set
+    pub(crate) fn is_payload_mapping(&self) -> bool {
+        self.scalar || self.enumeration || self.with.is_some()
+    }
+
+    /// Returns true if any of the attributes supported only for `one_of` variants is set
+    pub(crate) fn is_one_of_only(&self) -> bool {
+        self.is_payload_mapping() || self.default || self.not_set
+    }
+
+    /// Returns true if any of the attributes supported only for `tagged` variants is set
+    pub(crate) fn is_tagged_only(&self) -> bool {
+        self.tag.is_some() || self.payload.is_some()
+    }
+
+    fn validate(self) -> darling::Result<Self> {
+        if self.is_enumeration_only() && self.is_one_of_only() {
+            return Err(darling::Error::unsupported_shape("Variant attributes `scalar`, `enumeration`, `with`, `default` and `not_set` are mutually excluded with `unknown`, `other`, `rename`, `alias` and `value`"));
+        }
+        if self.default && self.not_set {
+            return Err(darling::Error::unsupported_shape("Variant attributes `default` and `not_set` are mutually excluded (use only one of them)"));
+        }
+        if self.not_set && self.is_payload_mapping() {
+            return Err(darling::Error::unsupported_shape("Variant attribute `not_set` is supported only for unit variants (without payload mapping attributes)"));
+        }
+        if self.scalar && self.enumeration {
+            return Err(darling::Error::unsupported_shape("Variant attributes `enumeration` and `scalar` are mutually excluded (use only one of them)"));
+        }
+        if self.unknown && self.other {
+            return Err(darling::Error::unsupported_shape("Variant attributes `unknown` and `other` are mutually excluded (use only one of them)"));
+        }
+        if self.rename.is_some() && self.value.is_some() {
+            return Err(darling::Error::unsupported_shape("Variant attributes `rename` and `value` are mutually excluded (use only one of them)"));
+        }
+        if self.unknown && (self.rename.is_some() || !self.alias.is_empty() || self.value.is_some()) {
+            return Err(darling::Error::unsupported_shape("Variant attribute `unknown` is mutually excluded with `rename`, `alias` and `value` (the variant keeps the raw proto value)"));
+        }
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[Attribute]> for VariantAttrs {
+    type Error = darling::Error;
+
+    fn try_from(attrs: &[Attribute]) -> Result<Self, Self::Error> {
+        match find_proto_map_meta(attrs) {
+            Some(meta) => Self::from_meta(meta)?.validate(),
+            None => Ok(Self::default()),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+use crate::enums::EnumAttrs;
+
+/// Implementation of `ProtoMapFieldMask` for `field_mask` attributed `enum` items (same for both proto frameworks).
+///
+/// Enums are merged as a whole (oneofs replace the current variant) even if the paths select nested fields of
+/// the variants.
+pub(crate) fn implement_field_mask_proto_map(name: &Ident, attrs: &EnumAttrs) -> TokenStream {
+    if !attrs.is_field_mask() {
+        return quote! {};
+    }
+
+    quote! {
+        impl ProtoMapFieldMask for #name {
+            fn merge_from_proto_paths(
+                &mut self,
+                proto: Self::ProtoStruct,
+                _paths: &proto_mapper::field_mask::FieldMaskPaths,
+            ) -> std::result::Result<(), ProtoMapError> {
+                *self = ProtoMap::from_proto(proto)?;
+                Ok(())
+            }
+
+            fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+                if a != b {
+                    paths.push(prefix.to_owned());
+                }
+            }
+        }
+    }
+}
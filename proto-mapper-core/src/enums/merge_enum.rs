@@ -0,0 +1,36 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
+use crate::enums::EnumAttrs;
+
+/// Implementation of `ProtoMapMerge` for `merge` attributed `enum` items (same for both proto frameworks).
+///
+/// A set oneof replaces the current variant (as a whole even if the member is the same), an unset oneof leaves it
+/// unchanged (regardless of the `not_set` policy of `one_of` enums). The rest of the enums replace the current value.
+pub(crate) fn implement_merge_proto_map(name: &Ident, attrs: &EnumAttrs) -> TokenStream {
+    if !attrs.is_merge() {
+        return quote! {};
+    }
+
+    let merge_impl = if let Some(one_of) = &attrs.one_of {
+        let one_of_field = &one_of.field;
+        quote! {
+            if proto.#one_of_field.is_some() {
+                *self = ProtoMap::from_proto(proto)?;
+            }
+        }
+    } else {
+        quote! {
+            *self = ProtoMap::from_proto(proto)?;
+        }
+    };
+
+    quote! {
+        impl ProtoMapMerge for #name {
+            fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> std::result::Result<(), ProtoMapError> {
+                #merge_impl
+                Ok(())
+            }
+        }
+    }
+}
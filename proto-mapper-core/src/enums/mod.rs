@@ -14,5 +14,14 @@ pub(crate) use protobuf_enum::Enum;
 #[cfg(feature = "protobuf")]
 pub(crate) use protobuf_enum::EnumVariant;
 
+mod any_enum;
+use any_enum::implement_any_proto_map;
+
+mod field_mask_enum;
+pub(crate) use field_mask_enum::implement_field_mask_proto_map;
+
+mod merge_enum;
+pub(crate) use merge_enum::implement_merge_proto_map;
+
 mod attrs;
 pub(crate) use attrs::*;
\ No newline at end of file
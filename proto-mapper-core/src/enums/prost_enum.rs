@@ -1,9 +1,14 @@
-use crate::rename_item;
+use crate::structs::{implement_from_proto_all, FieldAttrs, StructAttrs, StructField};
+use crate::types::Ty;
+use crate::{implement_field_error_mapping, path_to_string};
 use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
-use syn::{Attribute, DataEnum, Fields, Path, Type, Variant};
-use crate::enums::EnumAttrs;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, DataEnum, Fields, Path, Type, Variant};
+use crate::enums::{
+    implement_any_proto_map, EnumAttrs, VariantAttrs, ENUMERATION_ONLY_ATTRIBUTES_ERROR,
+    ONE_OF_ONLY_ATTRIBUTES_ERROR, TAGGED_ONLY_ATTRIBUTES_ERROR,
+};
 
 #[derive(Debug)]
 pub(crate) struct Enum {
@@ -26,12 +31,27 @@ impl Enum {
             .map(|variant| {
                 if attrs.is_enumeration() {
                     EnumVariant::try_from_enumeration_variant(variant)
+                } else if attrs.one_of.is_some() {
+                    EnumVariant::try_from_one_of_variant(variant)
+                } else if attrs.tagged.is_some() {
+                    EnumVariant::try_from_tagged_variant(variant)
                 } else {
                     EnumVariant::try_from_unnamed_variant(variant)
                 }
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        if variants.iter().filter(|variant| variant.attrs.is_catch_all()).count() > 1 {
+            return Err(darling::Error::unsupported_shape(
+                "Variant attributes `unknown` and `other` are supported only for a single variant",
+            ));
+        }
+
+        if let Some(one_of) = &attrs.one_of {
+            let variant_attrs: Vec<_> = variants.iter().map(|variant| &variant.attrs).collect();
+            one_of.validate_variants(&variant_attrs)?;
+        }
+
         Ok(Self {
             name: name.clone(),
             attrs,
@@ -40,16 +60,26 @@ impl Enum {
     }
 
     /// Implementation of (`to_proto_impl`, `from_proto_impl`) for `enumeration` variant cases.
+    ///
+    /// Unknown proto values are kept by the `unknown` variant, mapped to the `other` variant or fail.
     fn implement_enumeration_proto_methods(&self) -> (TokenStream, TokenStream) {
-
-        // Variant outer name
-        let name = &self.name;
         // Proto struct name
         let proto_struct = &self.attrs.source;
+        let type_name = path_to_string(proto_struct);
 
         let to_proto_impl = {
             let match_arms = self.variants.iter().map(|variant| {
                 let variant_name = &variant.name;
+                if variant.attrs.unknown {
+                    return quote! {
+                        Self::#variant_name(value) => *value,
+                    };
+                }
+                if let Some(value) = variant.attrs.value {
+                    return quote! {
+                        Self::#variant_name => #value,
+                    };
+                }
                 let proto_variant_name = self.get_proto_variant_name(variant);
                 let proto_variant_name = Ident::new(&proto_variant_name, Span::call_site());
                 quote! {
@@ -64,21 +94,46 @@ impl Enum {
         };
 
         let from_proto_impl = {
-            let match_arms = self.variants.iter().map(|variant| {
+            let match_arms = self.variants.iter().filter(|variant| !variant.attrs.unknown).map(|variant| {
                 let variant_name = &variant.name;
-                let proto_variant_name = self.get_proto_variant_name(variant);
-                let proto_variant_name = Ident::new(&proto_variant_name, Span::call_site());
+                let value = match variant.attrs.value {
+                    Some(value) => quote! { #value },
+                    None => {
+                        let proto_variant_name = self.get_proto_variant_name(variant);
+                        let proto_variant_name = Ident::new(&proto_variant_name, Span::call_site());
+                        quote! { #proto_struct::#proto_variant_name as i32 }
+                    }
+                };
+                let aliases = variant.attrs.alias.iter().map(|alias| {
+                    let alias = Ident::new(&self.get_proto_alias_name(alias), Span::call_site());
+                    quote! {
+                        _ if proto == #proto_struct::#alias as i32 => Ok(Self::#variant_name),
+                    }
+                });
 
                 quote! {
-                    _ if proto == #proto_struct::#proto_variant_name as i32 => Ok(Self::#variant_name),
+                    _ if proto == #value => Ok(Self::#variant_name),
+                    #( #aliases )*
                 }
             });
 
+            let unknown_arm = match self.variants.iter().find(|variant| variant.attrs.is_catch_all()) {
+                Some(variant) if variant.attrs.unknown => {
+                    let variant_name = &variant.name;
+                    quote! { _ => Ok(Self::#variant_name(proto)) }
+                }
+                Some(variant) => {
+                    let variant_name = &variant.name;
+                    quote! { _ => Ok(Self::#variant_name) }
+                }
+                None => quote! { _ => Err(ProtoMapError::unknown_enum_value(#type_name, proto)) },
+            };
+
             // We map to a protobuf entity enumeration
             quote! {
                 match proto {
                      #( #match_arms )*
-                     _ => Err(anyhow::anyhow!(format!(stringify!(Failed to match enum value {} to proto entity #name), proto)))
+                     #unknown_arm
                 }
             }
         };
@@ -86,75 +141,237 @@ impl Enum {
         (to_proto_impl, from_proto_impl)
     }
 
-    /// Implementation of (`to_proto_impl`, `from_proto_impl`) for `one_of` variant cases.
-    fn implement_one_of_proto_methods(&self) -> (TokenStream, TokenStream) {
-        // Variant outer name
-        let name = &self.name;
-
+    /// Implementation of (`to_proto_impl`, `into_proto_impl`, `from_proto_impl`, `try_from_proto_all_impl`)
+    /// for `one_of` variant cases (the `oneof` field is converted by the `ProtoMapOneOf` implementation).
+    fn implement_one_of_proto_methods(&self) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
         // We map to a prost entity with one of field
 
         // One of field as defined in the .proto file
         // Unwrap here never fails
         let one_of_field = &self.attrs.one_of.as_ref().unwrap().field;
 
-        // Enumeration inner type and module generated by 'protobuf'
-        let proto_one_of_enum = {
-            let mut source_module_name = self.attrs.source.clone();
-
-            let one_of = source_module_name
-                .segments
-                .pop()
-                .unwrap()
-                .value()
-                .ident
-                .clone();
-            let one_of_enum =
-                Ident::new(&one_of.to_string().to_snake_case(), Span::call_site());
-            let variant = Ident::new(
-                &one_of_field.to_string().to_upper_camel_case(),
-                Span::call_site(),
-            );
-
-            quote! { #source_module_name #one_of_enum::#variant }
+        // Both `to_proto` and `into_proto` (that moves the variant value) implementations
+        let implement_to_proto = |method: TokenStream| {
+            quote! {
+                let mut inner = Self::ProtoStruct::default();
+                inner.#one_of_field = ProtoMapOneOf::#method(self);
+                inner
+            }
         };
+        let to_proto_impl = implement_to_proto(quote! { to_one_of });
+        let into_proto_impl = implement_to_proto(quote! { into_one_of });
 
-        let to_proto_impl = {
-            let match_arms = self.variants.iter().map(|variant| {
-                let variant_name = &variant.name;
+        // Both `from_proto` and `try_from_proto_all` (that collects all errors of the variant) implementations
+        let implement_from_proto = |method: TokenStream| {
+            quote! {
+                ProtoMapOneOf::#method(proto.#one_of_field)
+            }
+        };
+        let from_proto_impl = implement_from_proto(quote! { from_one_of });
+        let try_from_proto_all_impl = implement_from_proto(quote! { try_from_one_of_all });
+        (to_proto_impl, into_proto_impl, from_proto_impl, try_from_proto_all_impl)
+    }
 
-                quote! {
-                     Self::#variant_name(value) => inner.#one_of_field = Some(#proto_one_of_enum::#variant_name(value.to_proto())),
+    /// Implementation of (`to_proto_impl`, `into_proto_impl`, `from_proto_impl`, `try_from_proto_all_impl`)
+    /// for `tagged` variant cases (the tag field is the `i32` value of the proto enumeration).
+    ///
+    /// Unknown tags and payloads that disagree with the tag (unset required messages or set message and explicit
+    /// presence fields of the other tags) map to the `other` variant or fail.
+    fn implement_tagged_proto_methods(&self) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+        // Unwrap here never fails
+        let tag = &self.attrs.tagged.as_ref().unwrap().tag;
+        let struct_attrs = StructAttrs::from_source(self.attrs.source.clone());
+
+        // Both `to_proto` and `into_proto` (that moves the payloads) implementations
+        let implement_to_proto = |owned: bool| {
+            let match_arms = self
+                .variants
+                .iter()
+                .map(|variant| variant.implement_to_tagged_arm(tag, &struct_attrs, owned));
+
+            quote! {
+                let mut proto = Self::ProtoStruct::default();
+                match self {
+                    #( #match_arms )*
                 }
+                proto
+            }
+        };
+        let to_proto_impl = implement_to_proto(false);
+        let into_proto_impl = implement_to_proto(true);
+
+        // Both `from_proto` and `try_from_proto_all` (that collects all errors of named fields) implementations
+        let other = self.variants.iter().find(|variant| variant.attrs.other);
+        let tagged: Vec<_> = self.variants.iter().filter(|variant| !variant.attrs.other).collect();
+        let implement_from_proto = |accumulate: bool| {
+            let match_arms = tagged.iter().map(|variant| {
+                // The payload fields of the other tags
+                let others: Vec<_> = tagged
+                    .iter()
+                    .filter(|other| other.name != variant.name)
+                    .flat_map(|other| other.tagged_fields())
+                    .collect();
+                variant.implement_from_tagged_arm(&struct_attrs, &others, other.is_some(), accumulate)
             });
+            let unknown_arm = match other {
+                Some(variant) => {
+                    let variant_name = &variant.name;
+                    quote! { _ => Ok(Self::#variant_name), }
+                }
+                None => {
+                    let type_name = self.tag_type_name();
+                    let tag_name = tag.to_string();
+                    let into = if accumulate { quote! { .into() } } else { quote! {} };
+                    quote! { _ => Err(ProtoMapError::unknown_enum_value(#type_name, tag).with_field(#tag_name)#into), }
+                }
+            };
 
             quote! {
-                let mut inner = Self::ProtoStruct::default();
-                match self {
+                let _guard = RecursionGuard::enter()?;
+                let tag = proto.#tag;
+                match tag {
                     #( #match_arms )*
+                    #unknown_arm
                 }
-                inner
             }
         };
+        let from_proto_impl = implement_from_proto(false);
+        let try_from_proto_all_impl = implement_from_proto(true);
+        (to_proto_impl, into_proto_impl, from_proto_impl, try_from_proto_all_impl)
+    }
 
-        let from_proto_impl = {
-            let match_arms = self.variants.iter().map(|variant| {
-                let variant_name = &variant.name;
-                let field_name = &variant.field_name;
-                quote! {
-                    Some(#proto_one_of_enum::#variant_name(value)) => {
-                        #field_name::from_proto(value).map(Self::#variant_name)
-                    }
+    /// Returns the name of the proto enumeration of the tags (the parent path of the variant tags) used in
+    /// conversion errors.
+    fn tag_type_name(&self) -> String {
+        self.variants
+            .iter()
+            .find_map(|variant| variant.attrs.tag.as_ref())
+            .map(|tag| {
+                let mut path = tag.clone();
+                path.segments.pop();
+                path.segments.pop_punct();
+                path_to_string(&path)
+            })
+            .unwrap_or_else(|| path_to_string(&self.attrs.source))
+    }
+
+    /// Implementation of `ProtoMapOneOf` for `one_of` enums that converts the variants to the members of the enum
+    /// generated for the `oneof` field.
+    fn implement_proto_map_one_of(&self) -> TokenStream {
+        let name = &self.name;
+
+        // Unwrap here never fails
+        let one_of = self.attrs.one_of.as_ref().unwrap();
+        // Enumeration inner type and module generated by 'prost'
+        let proto_one_of_enum = one_of.proto_enum_path(&self.attrs.source);
+
+        // Both `to_one_of` and `into_one_of` (that moves the variant value) implementations
+        let implement_to_one_of = |owned: bool| {
+            let match_arms = self
+                .variants
+                .iter()
+                .map(|variant| variant.implement_to_one_of_arm(&proto_one_of_enum, owned));
+
+            quote! {
+                match self {
+                    #( #match_arms )*
                 }
-            });
+            }
+        };
+        let to_one_of_impl = implement_to_one_of(false);
+        let into_one_of_impl = implement_to_one_of(true);
+
+        // Both `from_one_of` and `try_from_one_of_all` (that collects all errors of the variant) implementations
+        let implement_from_one_of = |accumulate: bool| {
+            let match_arms = self
+                .variants
+                .iter()
+                .filter(|variant| !variant.attrs.not_set)
+                .map(|variant| variant.implement_from_one_of_arm(&proto_one_of_enum, accumulate));
+            let not_set = self.implement_one_of_not_set(accumulate);
 
             quote! {
-                match proto.#one_of_field {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
                      #( #match_arms )*
-                     _ => Err(anyhow::anyhow!(stringify!(Failed to decode enum #name from proto entity)))
+                     None => #not_set,
                 }
             }
         };
-        (to_proto_impl, from_proto_impl)
+        let from_one_of_impl = implement_from_one_of(false);
+        let try_from_one_of_all_impl = implement_from_one_of(true);
+
+        // Proto field names of the members (the `not_set` variant leaves the `oneof` unset)
+        let members = self.variants.iter().filter_map(EnumVariant::one_of_member);
+        let member_arms = self.variants.iter().map(EnumVariant::implement_one_of_member_arm);
+        // Note: `Ok(value?)` of the collectors of named fields converts `ProtoMapError` to `ProtoMapErrors`
+        let allow_needless_question_mark = if self.variants.iter().any(|variant| variant.fields.is_some()) {
+            quote! { #[allow(clippy::needless_question_mark)] }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            impl ProtoMapOneOf for #name {
+                type ProtoOneOf = #proto_one_of_enum;
+
+                const MEMBERS: &'static [&'static str] = &[#(#members),*];
+
+                fn one_of_member(&self) -> Option<&'static str> {
+                    match self {
+                        #( #member_arms )*
+                    }
+                }
+
+                fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                    #to_one_of_impl
+                }
+
+                fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                    #into_one_of_impl
+                }
+
+                fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                    #from_one_of_impl
+                }
+
+                #allow_needless_question_mark
+                fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                    #try_from_one_of_all_impl
+                }
+            }
+        }
+    }
+
+    /// Implementation of the conversion of an unset `oneof` field (depends on the `not_set` policy of the enum)
+    fn implement_one_of_not_set(&self, accumulate: bool) -> TokenStream {
+        // Unwrap here never fails
+        let one_of = self.attrs.one_of.as_ref().unwrap();
+        let variant = self
+            .variants
+            .iter()
+            .find(|variant| variant.attrs.default || variant.attrs.not_set);
+
+        match variant {
+            Some(EnumVariant {
+                name,
+                fields: Some(fields),
+                ..
+            }) => {
+                let fields = fields.iter().map(|f| &f.name);
+                quote! { Ok(Self::#name { #(#fields: Default::default(),)* }) }
+            }
+            Some(EnumVariant {
+                name, field_name: None, ..
+            }) => quote! { Ok(Self::#name) },
+            Some(EnumVariant { name, .. }) => quote! { Ok(Self::#name(Default::default())) },
+            None => {
+                let type_name = path_to_string(&self.attrs.source);
+                let one_of_field_name = one_of.field.to_string();
+                let into = if accumulate { quote! { .into() } } else { quote! {} };
+                quote! { Err(ProtoMapError::one_of_not_set(#type_name).with_field(#one_of_field_name)#into) }
+            }
+        }
     }
 
     /// Implementation of proto_map for `enum` items
@@ -164,6 +381,13 @@ impl Enum {
         // Proto struct name
         let proto_struct = &self.attrs.source;
 
+        if self.attrs.is_any() {
+            let variants = self.variants.iter().filter_map(|variant| {
+                variant.field_name.as_ref().map(|field_name| (&variant.name, field_name))
+            });
+            return implement_any_proto_map(name, proto_struct, variants);
+        }
+
         if self.attrs.is_enumeration() {
            let (to_proto_impl, from_proto_impl) =  self.implement_enumeration_proto_methods();
             quote! {
@@ -173,13 +397,30 @@ impl Enum {
                         #to_proto_impl
                     }
 
-                    fn from_scalar(proto: i32) -> std::result::Result<Self, anyhow::Error> {
+                    fn from_scalar(proto: i32) -> std::result::Result<Self, ProtoMapError> {
                         #from_proto_impl
                     }
                 }
             }
         } else {
-            let (to_proto_impl, from_proto_impl) =  self.implement_one_of_proto_methods();
+            let (to_proto_impl, into_proto_impl, from_proto_impl, try_from_proto_all_impl) =
+                if self.attrs.tagged.is_some() {
+                    self.implement_tagged_proto_methods()
+                } else {
+                    self.implement_one_of_proto_methods()
+                };
+            // Note: `Ok(value?)` of the collectors of named fields converts `ProtoMapError` to `ProtoMapErrors`
+            let allow_needless_question_mark =
+                if self.attrs.tagged.is_some() && self.variants.iter().any(|variant| variant.fields.is_some()) {
+                    quote! { #[allow(clippy::needless_question_mark)] }
+                } else {
+                    quote! {}
+                };
+            let proto_map_one_of = if self.attrs.one_of.is_some() {
+                self.implement_proto_map_one_of()
+            } else {
+                quote! {}
+            };
 
             quote! {
                 impl ProtoMap for #name {
@@ -189,18 +430,55 @@ impl Enum {
                         #to_proto_impl
                     }
 
-                    fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+                    fn into_proto(self) -> Self::ProtoStruct {
+                        #into_proto_impl
+                    }
+
+                    fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
                         #from_proto_impl
                     }
+
+                    #allow_needless_question_mark
+                    fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                        #try_from_proto_all_impl
+                    }
                 }
+
+                #proto_map_one_of
             }
         }
     }
     fn get_proto_variant_name(&self, variant: &EnumVariant) -> String {
-        if let Some(rename_variants) = self.attrs.rename_variants.as_ref() {
-            rename_item(&variant.name.to_string(), rename_variants).unwrap()
+        let name = self.attrs.proto_variant_name(&variant.name, &variant.attrs);
+        if self.attrs.is_auto_naming() {
+            self.get_prost_variant_name(&name)
         } else {
-            variant.name.to_string()
+            name
+        }
+    }
+
+    fn get_proto_alias_name(&self, alias: &str) -> String {
+        if self.attrs.is_auto_naming() {
+            self.get_prost_variant_name(alias)
+        } else {
+            alias.to_owned()
+        }
+    }
+
+    /// Returns the variant name that `prost` generates for a value name of the `.proto` file (the upper camel case
+    /// name without the upper camel case prefix of the enumeration name).
+    fn get_prost_variant_name(&self, proto_name: &str) -> String {
+        let enum_name = self.attrs.source.segments.last().unwrap().ident.to_string();
+        let name = proto_name.to_upper_camel_case();
+
+        // The prefix is removed only if a new word follows e.g. `Foo` is not removed from `Foobar`
+        let stripped = match name.strip_prefix(enum_name.as_str()) {
+            Some(stripped) if stripped.starts_with(char::is_uppercase) => stripped,
+            _ => name.as_str(),
+        };
+        match stripped {
+            "Self" => String::from("Self_"),
+            stripped => stripped.to_owned(),
         }
     }
 }
@@ -209,11 +487,35 @@ impl Enum {
 pub(crate) struct EnumVariant {
     pub name: Ident,
     pub field_name: Option<Path>,
+    /// The payload type of `one_of` variants (`None` for unit variants)
+    pub ty: Option<Ty>,
+    /// The named fields of `one_of` variants mapped to the fields of the member message (`None` for other variants)
+    pub fields: Option<Vec<StructField>>,
+    /// The payload field of unnamed `tagged` variants (mapped like a struct field named as the proto field)
+    pub payload: Option<StructField>,
+    pub attrs: VariantAttrs,
 }
 
 impl EnumVariant {
     pub(crate) fn try_from_enumeration_variant(variant: &Variant) -> darling::Result<Self> {
-        if let Fields::Unnamed(fields) = &variant.fields {
+        let attrs = VariantAttrs::try_from(variant.attrs.as_slice())?;
+        if attrs.is_one_of_only() {
+            return Err(darling::Error::unsupported_shape(ONE_OF_ONLY_ATTRIBUTES_ERROR));
+        }
+        if attrs.is_tagged_only() {
+            return Err(darling::Error::unsupported_shape(TAGGED_ONLY_ATTRIBUTES_ERROR));
+        }
+
+        if attrs.unknown {
+            // The unknown variant keeps the raw proto value
+            let raw_value = matches!(&variant.fields, Fields::Unnamed(fields)
+                if fields.unnamed.len() == 1 && matches!(&fields.unnamed[0].ty, Type::Path(ty) if ty.path.is_ident("i32")));
+            if !raw_value {
+                return Err(darling::Error::unsupported_shape(
+                    "Variant attribute `unknown` is supported only for variants with a single `i32` field e.g. `Unrecognized(i32)`",
+                ));
+            }
+        } else if let Fields::Unnamed(fields) = &variant.fields {
             if !fields.unnamed.is_empty() {
                 return Err(darling::Error::unsupported_shape(
                     "Only unnamed variants with no inner field e.g. `Foo, Bar` are supported for `enumeration` attributed enums.",
@@ -226,9 +528,24 @@ impl EnumVariant {
         Ok(Self {
             name,
             field_name: None,
+            ty: None,
+            fields: None,
+            payload: None,
+            attrs,
         })
     }
     pub(crate) fn try_from_unnamed_variant(variant: &Variant) -> darling::Result<Self> {
+        let attrs = VariantAttrs::try_from(variant.attrs.as_slice())?;
+        if attrs.is_enumeration_only() {
+            return Err(darling::Error::unsupported_shape(ENUMERATION_ONLY_ATTRIBUTES_ERROR));
+        }
+        if attrs.is_one_of_only() {
+            return Err(darling::Error::unsupported_shape(ONE_OF_ONLY_ATTRIBUTES_ERROR));
+        }
+        if attrs.is_tagged_only() {
+            return Err(darling::Error::unsupported_shape(TAGGED_ONLY_ATTRIBUTES_ERROR));
+        }
+
         let field_name = if let Fields::Unnamed(fields) = &variant.fields {
             if fields.unnamed.len() != 1 {
                 return Err(darling::Error::unsupported_shape(
@@ -254,6 +571,481 @@ impl EnumVariant {
         Ok(Self {
             name,
             field_name: Some(field_name),
+            ty: None,
+            fields: None,
+            payload: None,
+            attrs,
+        })
+    }
+
+    /// Parses the variants of `one_of` attributed enums. Unit variants map to empty message members
+    /// (e.g. `google.protobuf.Empty`), unnamed variants to message, scalar or enumeration members and variants with
+    /// named fields to the fields of message members (like the fields of structs).
+    pub(crate) fn try_from_one_of_variant(variant: &Variant) -> darling::Result<Self> {
+        let attrs = VariantAttrs::try_from(variant.attrs.as_slice())?;
+        if attrs.is_enumeration_only() {
+            return Err(darling::Error::unsupported_shape(ENUMERATION_ONLY_ATTRIBUTES_ERROR));
+        }
+        if attrs.is_tagged_only() {
+            return Err(darling::Error::unsupported_shape(TAGGED_ONLY_ATTRIBUTES_ERROR));
+        }
+
+        let name = variant.ident.clone();
+        match &variant.fields {
+            Fields::Unit => {
+                if attrs.is_payload_mapping() {
+                    return Err(darling::Error::unsupported_shape(
+                        "Variant attributes `scalar`, `enumeration` and `with` are not supported for unit variants (mapped to empty message members)",
+                    ));
+                }
+                Ok(Self {
+                    name,
+                    field_name: None,
+                    ty: None,
+                    fields: None,
+                    payload: None,
+                    attrs,
+                })
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                // Note: .first() here never fails
+                let field = fields.unnamed.first().unwrap();
+                if attrs.not_set {
+                    return Err(darling::Error::unsupported_shape(
+                        "Variant attribute `not_set` is supported only for unit variants (that leave the `oneof` unset)",
+                    ));
+                }
+                let ty = Ty::try_from_field(field)?;
+                if ty.is_optional() || ty.is_repeated() || ty.is_map() {
+                    return Err(darling::Error::unsupported_shape(
+                        "Only non optional and non repeated payloads e.g. `Foo(Bar)` are supported for `one_of` attributed enums.",
+                    ));
+                }
+                if attrs.enumeration && attrs.with.is_some() {
+                    return Err(darling::Error::unsupported_shape(
+                        "Variant attribute `with` is not supported for `enumeration` payloads",
+                    ));
+                }
+                match &field.ty {
+                    Type::Path(type_path) => Ok(Self {
+                        name,
+                        field_name: Some(type_path.path.clone()),
+                        ty: Some(ty),
+                        fields: None,
+                        payload: None,
+                        attrs,
+                    }),
+                    _ => Err(darling::Error::unsupported_shape(
+                        "Only unnamed variants with only one inner field e.g. `Foo(Bar)` are supported for `one_of` attributed enums.",
+                    )),
+                }
+            }
+            Fields::Named(fields) => {
+                if attrs.is_payload_mapping() {
+                    return Err(darling::Error::unsupported_shape(
+                        "Variant attributes `scalar`, `enumeration` and `with` are not supported for variants with named fields (use the field attributes instead)",
+                    ));
+                }
+                if attrs.not_set {
+                    return Err(darling::Error::unsupported_shape(
+                        "Variant attribute `not_set` is supported only for unit variants (that leave the `oneof` unset)",
+                    ));
+                }
+                let fields = fields
+                    .named
+                    .iter()
+                    .map(StructField::try_from_field)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Self {
+                    name,
+                    field_name: None,
+                    ty: None,
+                    fields: Some(fields),
+                    payload: None,
+                    attrs,
+                })
+            }
+            _ => Err(darling::Error::unsupported_shape(
+                "Only unit variants, variants with named fields and unnamed variants with only one inner field e.g. `Foo(Bar)` are supported for `one_of` attributed enums.",
+            )),
+        }
+    }
+
+    /// Checks if the payload of a `one_of` variant maps to a scalar member (taking into account the variant attributes)
+    fn is_scalar_like(&self) -> bool {
+        self.attrs.scalar || self.attrs.enumeration || self.ty.as_ref().is_some_and(Ty::is_scalar)
+    }
+
+    /// Returns the proto field name of the `oneof` member of the variant (`None` for the `not_set` variant)
+    pub(crate) fn one_of_member(&self) -> Option<String> {
+        (!self.attrs.not_set).then(|| self.name.to_string().to_snake_case())
+    }
+
+    /// Implementation of the match arm that returns the `oneof` member of the variant
+    pub(crate) fn implement_one_of_member_arm(&self) -> TokenStream {
+        let variant_name = &self.name;
+        let pattern = if self.fields.is_some() {
+            quote! { Self::#variant_name { .. } }
+        } else if self.field_name.is_some() {
+            quote! { Self::#variant_name(_) }
+        } else {
+            quote! { Self::#variant_name }
+        };
+        match self.one_of_member() {
+            Some(member) => quote! { #pattern => Some(#member), },
+            None => quote! { #pattern => None, },
+        }
+    }
+
+    /// Specific `prost` feature implementation of the match arm that converts the variant to the member of the
+    /// `oneof` enum (`owned` consumes the payload).
+    pub(crate) fn implement_to_one_of_arm(&self, proto_one_of_enum: &TokenStream, owned: bool) -> TokenStream {
+        let variant_name = &self.name;
+
+        // The `not_set` variant leaves the `oneof` unset
+        if self.attrs.not_set {
+            return quote! {
+                Self::#variant_name => None,
+            };
+        }
+
+        // Variants with named fields set the fields of the member message
+        if let Some(fields) = &self.fields {
+            let struct_attrs = self.member_struct_attrs(proto_one_of_enum);
+            let fields: Vec<_> = fields.iter().filter(|f| !f.is_skipped()).collect();
+            let bindings = fields.iter().map(|f| &f.name);
+            let set = if fields.is_empty() {
+                quote! { |_| {} }
+            } else {
+                let getters = fields.iter().map(|f| f.implement_binding_getter(&struct_attrs, !owned));
+                quote! { |proto| { #(#getters)* } }
+            };
+            return quote! {
+                Self::#variant_name { #(#bindings,)* .. } => Some(proto_mapper::to_one_of_member(#proto_one_of_enum::#variant_name, #set)),
+            };
+        }
+
+        // Unit variants map to empty messages
+        if self.field_name.is_none() {
+            return quote! {
+                Self::#variant_name => Some(#proto_one_of_enum::#variant_name(Default::default())),
+            };
+        }
+
+        // Note: `prost` generates the enumeration members as `i32` values
+        let value = match &self.attrs.with {
+            // Note: `with` modules map only by reference
+            Some(with) => {
+                let to = if self.is_scalar_like() { quote! { to_scalar } } else { quote! { to_proto } };
+                let value = if owned { quote! { &value } } else { quote! { value } };
+                quote! { #with::#to(#value) }
+            }
+            None if self.is_scalar_like() && owned => quote! { ProtoMapScalar::into_scalar(value) },
+            None if self.is_scalar_like() => quote! { ProtoMapScalar::to_scalar(value) },
+            None if owned => quote! { value.into_proto() },
+            None => quote! { value.to_proto() },
+        };
+        quote! {
+            Self::#variant_name(value) => Some(#proto_one_of_enum::#variant_name(#value)),
+        }
+    }
+
+    /// Specific `prost` feature implementation of the match arm that converts the member of the `oneof` enum to
+    /// the variant (`accumulate` collects all the errors of message payloads).
+    pub(crate) fn implement_from_one_of_arm(&self, proto_one_of_enum: &TokenStream, accumulate: bool) -> TokenStream {
+        let variant_name = &self.name;
+
+        if self.fields.is_some() {
+            return self.implement_from_one_of_named_arm(proto_one_of_enum, accumulate);
+        }
+
+        // Unit variants ignore the empty message
+        let Some(field_name) = &self.field_name else {
+            return quote! {
+                Some(#proto_one_of_enum::#variant_name(_)) => Ok(Self::#variant_name),
+            };
+        };
+
+        let member = self.name.to_string().to_snake_case();
+        let map_err = implement_field_error_mapping(&member, self.attrs.with.is_some());
+        let value = match &self.attrs.with {
+            Some(with) if self.is_scalar_like() => quote! { #with::from_scalar(value) },
+            Some(with) => quote! { #with::from_proto(value) },
+            None if self.is_scalar_like() => quote! { ProtoMapScalar::from_scalar(value) },
+            None if accumulate => quote! { #field_name::try_from_proto_all(value) },
+            None => quote! { #field_name::from_proto(value) },
+        };
+        // Note: only message payloads collect all of their errors
+        let into = if accumulate && (self.is_scalar_like() || self.attrs.with.is_some()) {
+            quote! { .map_err(ProtoMapErrors::from) }
+        } else {
+            quote! {}
+        };
+        quote! {
+            Some(#proto_one_of_enum::#variant_name(value)) => {
+                #value
+                    .map(Self::#variant_name)
+                    #map_err
+                    #into
+            }
+        }
+    }
+
+    /// Specific `prost` feature implementation of the match arm that converts the member message of a variant with
+    /// named fields (the errors of the fields are tagged with the member name).
+    fn implement_from_one_of_named_arm(&self, proto_one_of_enum: &TokenStream, accumulate: bool) -> TokenStream {
+        let variant_name = &self.name;
+        // Unwrap here never fails
+        let fields = self.fields.as_ref().unwrap();
+        let struct_attrs = self.member_struct_attrs(proto_one_of_enum);
+        let member = self.name.to_string().to_snake_case();
+
+        let constructor = quote! { Self::#variant_name };
+        let from_proto_all = implement_from_proto_all(&constructor, fields, &struct_attrs).filter(|_| accumulate);
+        let (from, into) = match from_proto_all {
+            Some(from_proto_all) => (
+                quote! { |proto| -> std::result::Result<Self, ProtoMapErrors> { #from_proto_all } },
+                quote! {},
+            ),
+            None => {
+                let proto = if fields.iter().all(|f| f.is_skipped()) {
+                    quote! { _ }
+                } else {
+                    quote! { proto }
+                };
+                let setters = fields.iter().map(|f| f.implement_setter(&struct_attrs));
+                let into = if accumulate {
+                    quote! { .map_err(ProtoMapErrors::from) }
+                } else {
+                    quote! {}
+                };
+                (
+                    quote! { |#proto| -> std::result::Result<Self, ProtoMapError> { Ok(#constructor { #(#setters)* }) } },
+                    into,
+                )
+            }
+        };
+        quote! {
+            Some(#proto_one_of_enum::#variant_name(proto)) => {
+                proto_mapper::from_one_of_member(proto, #from)
+                    .map_err(|e| e.with_field(#member))
+                    #into
+            }
+        }
+    }
+
+    /// Returns the struct attributes of the named fields of the variant (the member of the `oneof` enum names the
+    /// member message in conversion errors).
+    fn member_struct_attrs(&self, proto_one_of_enum: &TokenStream) -> StructAttrs {
+        let variant_name = &self.name;
+        StructAttrs::from_source(parse_quote! { #proto_one_of_enum::#variant_name })
+    }
+    /// Parses the variants of `tagged` attributed enums. Unit variants map only to the tag, unnamed variants to the
+    /// tag and a single payload field and variants with named fields to the tag and the fields of the message
+    /// (like the fields of structs).
+    pub(crate) fn try_from_tagged_variant(variant: &Variant) -> darling::Result<Self> {
+        let attrs = VariantAttrs::try_from(variant.attrs.as_slice())?;
+        if attrs.unknown || attrs.rename.is_some() || !attrs.alias.is_empty() || attrs.value.is_some() {
+            return Err(darling::Error::unsupported_shape(
+                "Variant attributes `unknown`, `rename`, `alias` and `value` are not supported for `tagged` attributed enums",
+            ));
+        }
+        if attrs.default || attrs.not_set {
+            return Err(darling::Error::unsupported_shape(
+                "Variant attributes `default` and `not_set` are supported only for `one_of` attributed enums",
+            ));
+        }
+        if attrs.other {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(darling::Error::unsupported_shape(
+                    "Variant attribute `other` is supported only for unit variants of `tagged` attributed enums",
+                ));
+            }
+        } else if attrs.tag.is_none() {
+            return Err(darling::Error::unsupported_shape(
+                "Variant attribute `tag` is required for every variant of `tagged` attributed enums (except the `other` variant)",
+            ));
+        }
+
+        let name = variant.ident.clone();
+        let (payload, fields) = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                // Note: .first() here never fails
+                let field = fields.unnamed.first().unwrap();
+                let ty = Ty::try_from_field(field)?;
+                if attrs.enumeration && attrs.with.is_some() {
+                    return Err(darling::Error::unsupported_shape(
+                        "Variant attribute `with` is not supported for `enumeration` payloads",
+                    ));
+                }
+                let payload_name = match &attrs.payload {
+                    Some(payload) => format_ident!("{}", payload),
+                    None => format_ident!("{}", name.to_string().to_snake_case()),
+                };
+                // Message payloads are required (an unset message disagrees with the tag)
+                let required =
+                    !(attrs.is_payload_mapping() || ty.is_scalar() || ty.is_optional() || ty.is_repeated() || ty.is_map());
+                let payload_attrs = FieldAttrs {
+                    scalar: attrs.scalar,
+                    enumeration: attrs.enumeration,
+                    with: attrs.with.clone(),
+                    required,
+                    ..Default::default()
+                };
+                let payload = StructField {
+                    name: payload_name,
+                    ty,
+                    attrs: Some(payload_attrs),
+                };
+                (Some(payload), None)
+            }
+            _ if attrs.is_payload_mapping() || attrs.payload.is_some() => {
+                return Err(darling::Error::unsupported_shape(
+                    "Variant attributes `payload`, `scalar`, `enumeration` and `with` are supported only for unnamed variants with a single field",
+                ));
+            }
+            Fields::Unit => (None, None),
+            Fields::Named(fields) => {
+                let fields = fields
+                    .named
+                    .iter()
+                    .map(StructField::try_from_field)
+                    .collect::<Result<Vec<_>, _>>()?;
+                (None, Some(fields))
+            }
+            _ => {
+                return Err(darling::Error::unsupported_shape(
+                    "Only unit variants, variants with named fields and unnamed variants with only one inner field e.g. `Foo(Bar)` are supported for `tagged` attributed enums.",
+                ))
+            }
+        };
+
+        Ok(Self {
+            name,
+            field_name: None,
+            ty: None,
+            fields,
+            payload,
+            attrs,
         })
     }
-}
\ No newline at end of file
+
+    /// Specific `prost` feature implementation of the match arm that sets the tag and the payload fields of a
+    /// `tagged` variant (`owned` consumes the payload).
+    pub(crate) fn implement_to_tagged_arm(&self, tag: &Ident, struct_attrs: &StructAttrs, owned: bool) -> TokenStream {
+        let variant_name = &self.name;
+
+        // The `other` variant without a tag leaves the default tag
+        let set_tag = match &self.attrs.tag {
+            Some(value) => quote! { proto.#tag = #value as i32; },
+            None => quote! {},
+        };
+
+        if let Some(payload) = &self.payload {
+            let binding = &payload.name;
+            let getter = payload.implement_binding_getter(struct_attrs, !owned);
+            return quote! {
+                Self::#variant_name(#binding) => {
+                    #set_tag
+                    #getter
+                }
+            };
+        }
+
+        if let Some(fields) = &self.fields {
+            let fields: Vec<_> = fields.iter().filter(|f| !f.is_skipped()).collect();
+            let bindings = fields.iter().map(|f| &f.name);
+            let getters = fields.iter().map(|f| f.implement_binding_getter(struct_attrs, !owned));
+            return quote! {
+                Self::#variant_name { #(#bindings,)* .. } => {
+                    #set_tag
+                    #(#getters)*
+                }
+            };
+        }
+
+        quote! {
+            Self::#variant_name => {
+                #set_tag
+            }
+        }
+    }
+
+    /// Specific `prost` feature implementation of the match arm that converts the payload fields of the variant
+    /// of a `tag` (`accumulate` collects all the errors of named fields).
+    ///
+    /// The set message and explicit presence fields of the `others` payload fields (of the other tags) that are not
+    /// fields of the variant disagree with the tag. With a `fallback` (the `other` variant) the arm matches only if
+    /// the required messages of the payload are set and the fields of the other tags are not, otherwise the set fields
+    /// of the other tags fail with `TaggedFieldMismatch`.
+    pub(crate) fn implement_from_tagged_arm(
+        &self,
+        struct_attrs: &StructAttrs,
+        others: &[&StructField],
+        fallback: bool,
+        accumulate: bool,
+    ) -> TokenStream {
+        let variant_name = &self.name;
+        // Unwrap here never fails (only the `other` variant has no tag)
+        let value = self.attrs.tag.as_ref().unwrap();
+        let tag_matches = quote! { tag == #value as i32 };
+
+        let own: Vec<_> = self.tagged_fields().map(|f| f.proto_field_path_name()).collect();
+        let mut conflicting: Vec<&StructField> = Vec::new();
+        for f in others.iter().copied().filter(|f| f.has_presence(struct_attrs)) {
+            let field = f.proto_field_path_name();
+            if !own.contains(&field) && !conflicting.iter().any(|c| c.proto_field_path_name() == field) {
+                conflicting.push(f);
+            }
+        }
+
+        let required = self.tagged_fields().filter(|f| fallback && f.is_required()).map(|f| {
+            let is_set = f.implement_message_is_set();
+            quote! { && #is_set }
+        });
+        let (checks, mismatch_arms) = if fallback {
+            let checks = conflicting.iter().map(|f| {
+                let is_set = f.implement_message_is_set();
+                quote! { && !#is_set }
+            });
+            (quote! { #(#required)* #(#checks)* }, quote! {})
+        } else {
+            let type_name = path_to_string(&struct_attrs.source);
+            let into = if accumulate { quote! { .into() } } else { quote! {} };
+            let mismatch_arms = conflicting.iter().map(|f| {
+                let is_set = f.implement_message_is_set();
+                let field = f.proto_field_path_name();
+                quote! {
+                    _ if #tag_matches && #is_set => Err(ProtoMapError::tagged_field_mismatch(#type_name, tag).with_field(#field)#into),
+                }
+            });
+            (quote! {}, quote! { #(#mismatch_arms)* })
+        };
+
+        let conversion = if let Some(payload) = &self.payload {
+            let value = payload.implement_setter_value(struct_attrs, accumulate);
+            quote! { Ok(Self::#variant_name(#value)) }
+        } else if let Some(fields) = &self.fields {
+            let constructor = quote! { Self::#variant_name };
+            match implement_from_proto_all(&constructor, fields, struct_attrs).filter(|_| accumulate) {
+                Some(from_proto_all) => quote! { { #from_proto_all } },
+                None => {
+                    let setters = fields.iter().map(|f| f.implement_setter(struct_attrs));
+                    quote! { Ok(#constructor { #(#setters)* }) }
+                }
+            }
+        } else {
+            quote! { Ok(Self::#variant_name) }
+        };
+
+        quote! {
+            #mismatch_arms
+            _ if #tag_matches #checks => #conversion,
+        }
+    }
+
+    /// Returns the payload field or the named fields of a `tagged` variant
+    fn tagged_fields(&self) -> impl Iterator<Item = &StructField> {
+        self.payload.iter().chain(self.fields.iter().flatten())
+    }
+}
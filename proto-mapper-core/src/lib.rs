@@ -1,9 +1,10 @@
 use crate::proto_map::ProtoMap;
+use crate::types::MapType;
 use darling::FromDeriveInput;
 use heck::{ToShoutySnakeCase, ToSnakeCase};
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Attribute, DeriveInput, Meta};
+use syn::{Attribute, DeriveInput, Meta, Path};
 
 mod proto_map;
 mod enums;
@@ -16,6 +17,7 @@ mod tests;
 const PROTO_MAP_ATTRIBUTE: &str = "proto_map";
 const SNAKE_CASE_ATTRIBUTE_VALUE: &str = "snake_case";
 const SCREAMING_SNAKE_CASE_ATTRIBUTE_VALUE: &str = "STREAMING_SNAKE_CASE";
+const AUTO_ATTRIBUTE_VALUE: &str = "auto";
 
 pub fn implement_proto_map(input: TokenStream) -> TokenStream {
     let input: DeriveInput = syn::parse(input.into()).unwrap();
@@ -35,6 +37,8 @@ pub(crate) fn rename_item(item: &str, to_case: &str) -> darling::Result<String>
     match to_case {
         SNAKE_CASE_ATTRIBUTE_VALUE => Ok(item.to_string().to_snake_case()),
         SCREAMING_SNAKE_CASE_ATTRIBUTE_VALUE => Ok(item.to_string().to_shouty_snake_case()),
+        // The names of the `.proto` file (converted to the names of the enabled proto framework later)
+        AUTO_ATTRIBUTE_VALUE => Ok(item.to_string().to_shouty_snake_case()),
 
         _ => Err(darling::Error::unknown_value(&format!(
             "Unknown rename case attribute = `{}` ",
@@ -54,3 +58,160 @@ pub(crate) fn get_proto_field_name(name: &str, remove_last_char_if: Option<char>
     }
     name.to_string()
 }
+
+/// Returns the proto field name reported in conversion error paths.
+/// Raw identifier prefixes and trailing '_' characters (e.g. `r#type`, `type_`) are removed.
+pub(crate) fn get_proto_field_path_name(name: &str) -> String {
+    let name = name.strip_prefix("r#").unwrap_or(name);
+    get_proto_field_name(name, Some('_'))
+}
+
+/// Returns the textual representation of a path e.g. `proto::Entity`, used as type name in conversion errors.
+pub(crate) fn path_to_string(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Implements the error mapping that adds `field` to the path of a conversion error.
+///
+/// Custom (`with` module) conversions may return any error convertible to `ProtoMapError`.
+pub(crate) fn implement_field_error_mapping(field: &str, custom: bool) -> TokenStream {
+    if custom {
+        quote! { .map_err(|e| ProtoMapError::from(e).with_field(#field)) }
+    } else {
+        quote! { .map_err(|e| e.with_field(#field)) }
+    }
+}
+
+/// Implements the conversion of the elements of a repeated field.
+///
+/// `values` is an iterator of the proto values and `convert` the conversion expression of a single `value`.
+/// The resulting expression is a `Result` of all the elements, the errors are tagged with the element index
+/// (with `accumulate` the errors of all the elements are collected).
+pub(crate) fn implement_repeated_from_proto(
+    values: TokenStream,
+    convert: TokenStream,
+    custom: bool,
+    accumulate: bool,
+) -> TokenStream {
+    let error = if custom {
+        quote! { ProtoMapError::from(e) }
+    } else {
+        quote! { e }
+    };
+
+    if accumulate {
+        quote! {
+            {
+                let mut element_errors = ProtoMapErrors::default();
+                let elements: Vec<_> = #values
+                    .enumerate()
+                    .filter_map(|(index, value)| {
+                        element_errors.collect(|| Ok(#convert.map_err(|e| #error.with_index(index))?))
+                    })
+                    .collect();
+                if element_errors.is_empty() {
+                    Ok(elements)
+                } else {
+                    Err(element_errors)
+                }
+            }
+        }
+    } else {
+        quote! {
+            #values
+                .enumerate()
+                .map(|(index, value)| #convert.map_err(|e| #error.with_index(index)))
+                .collect::<std::result::Result<Vec<_>, _>>()
+        }
+    }
+}
+
+/// Implements the conversion of the entries of a map field.
+///
+/// `entries` is an iterator of the proto `(key, value)` entries, `convert_key` and `convert_value` the conversion
+/// expressions of a single `key` and `value` (`custom` if they come from `with` modules).
+/// The resulting expression is a `Result` of the `map` collection, the errors of the values are tagged with the entry
+/// key (with `accumulate` the errors of all the entries are collected).
+///
+/// Note: the keys are moved to their conversion so the errors of the keys are not tagged with the entry key.
+pub(crate) fn implement_map_from_proto(
+    map: MapType,
+    entries: TokenStream,
+    (convert_key, custom_key): (TokenStream, bool),
+    (convert_value, custom_value): (TokenStream, bool),
+    accumulate: bool,
+) -> TokenStream {
+    let key_error = if custom_key {
+        quote! { .map_err(ProtoMapError::from) }
+    } else {
+        quote! {}
+    };
+    let value_error = if custom_value {
+        quote! { ProtoMapError::from(e) }
+    } else {
+        quote! { e }
+    };
+
+    if accumulate {
+        quote! {
+            {
+                let mut entry_errors = ProtoMapErrors::default();
+                let entries: #map<_, _> = #entries
+                    .filter_map(|(key, value)| {
+                        let entry_value = entry_errors.collect(|| Ok(#convert_value.map_err(|e| #value_error.with_key(&key))?));
+                        let entry_key = entry_errors.collect(|| Ok(#convert_key #key_error?));
+                        Some((entry_key?, entry_value?))
+                    })
+                    .collect();
+                if entry_errors.is_empty() {
+                    Ok(entries)
+                } else {
+                    Err(entry_errors)
+                }
+            }
+        }
+    } else {
+        quote! {
+            #entries
+                .map(|(key, value)| {
+                    let value = #convert_value.map_err(|e| #value_error.with_key(&key))?;
+                    Ok((#convert_key #key_error?, value))
+                })
+                .collect::<std::result::Result<#map<_, _>, ProtoMapError>>()
+        }
+    }
+}
+
+/// Implements the conversion of a `oneof` field (the `Option` of the generated enum `proto_value`) to a struct field
+/// marked with `one_of` (see `ProtoMapOneOf`).
+///
+/// The errors of the members are already tagged with the member name. Optional fields map an unset `oneof` to `None`,
+/// the rest to the `not_set` policy of the enum.
+pub(crate) fn implement_one_of_from_proto(
+    proto_value: TokenStream,
+    optional: bool,
+    accumulate: bool,
+) -> TokenStream {
+    let from_one_of_method = if accumulate {
+        quote! { ProtoMapOneOf::try_from_one_of_all }
+    } else {
+        quote! { ProtoMapOneOf::from_one_of }
+    };
+
+    if optional {
+        quote! {
+            match #proto_value {
+                Some(value) => Some(#from_one_of_method(Some(value))?),
+                None => None,
+            }
+        }
+    } else {
+        quote! {
+            #from_one_of_method(#proto_value)?
+        }
+    }
+}
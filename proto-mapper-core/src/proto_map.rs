@@ -1,4 +1,4 @@
-use crate::enums::Enum;
+use crate::enums::{implement_field_mask_proto_map, implement_merge_proto_map, Enum};
 use crate::structs::Struct;
 use heck::ToSnakeCase;
 use proc_macro2::{Ident, TokenStream};
@@ -20,7 +20,16 @@ impl ProtoMap {
     fn implement_proto_map(&self) -> TokenStream {
         match self {
             Self::Struct(data) => data.implement_proto_map(),
-            Self::Enum(data) => data.implement_proto_map(),
+            Self::Enum(data) => {
+                let proto_map = data.implement_proto_map();
+                let field_mask = implement_field_mask_proto_map(&data.name, &data.attrs);
+                let merge = implement_merge_proto_map(&data.name, &data.attrs);
+                quote! {
+                    #proto_map
+                    #field_mask
+                    #merge
+                }
+            }
         }
     }
 }
@@ -31,9 +40,24 @@ impl ToTokens for ProtoMap {
 
         let proto_convert = self.implement_proto_map();
 
+        // Enumeration fields of `rust-protobuf` map possibly unknown values
+        #[cfg(feature = "protobuf")]
+        let backend_imports = quote! {
+            #[allow(unused_imports)]
+            use proto_mapper::ProtoMapEnum;
+        };
+        #[cfg(not(feature = "protobuf"))]
+        let backend_imports = quote! {};
+
         let expanded = quote! {
             mod #mod_name {
                 use super::*;
+                #[allow(unused_imports)]
+                use proto_mapper::{
+                    ProtoMapAny, ProtoMapError, ProtoMapErrors, ProtoMapFieldMask, ProtoMapMerge,
+                    ProtoMapOneOf, RecursionGuard,
+                };
+                #backend_imports
                 #proto_convert
             }
         };
@@ -6,7 +6,57 @@ use syn::Path;
 pub(crate) struct StructAttrs {
     pub source: Path,
     /// Optional renaming of the struct fields before mapping to the proto entity.
+    #[allow(dead_code)] // TODO use rename_all in field getter/setter implementations
     pub rename_all: Option<String>,
+    /// Optional presence policy of all the optional scalar and enumeration fields (fields override it).
+    pub presence: Option<Presence>,
+    /// Optional mark the struct as packed to `google.protobuf.Any` messages (implements `ProtoMapAny`).
+    #[darling(default)]
+    pub any: bool,
+    /// Optional full proto name of the source message used in `google.protobuf.Any` type urls
+    /// (implies `any`, defaults to the name of the proto framework message).
+    pub type_name: Option<String>,
+    /// Optional mark the struct as merged by `google.protobuf.FieldMask` paths (implements `ProtoMapFieldMask`).
+    #[darling(default)]
+    pub field_mask: bool,
+    /// Optional mark the struct as merged from proto messages with the protobuf merge rules
+    /// (implements `ProtoMapMerge`).
+    #[darling(default)]
+    pub merge: bool,
+}
+
+impl StructAttrs {
+    /// Returns the attributes of fields that are mapped like struct fields of the `source` message without a struct
+    /// item (e.g. the named fields of enum variants).
+    pub(crate) fn from_source(source: Path) -> Self {
+        Self {
+            source,
+            rename_all: None,
+            presence: None,
+            any: false,
+            type_name: None,
+            field_mask: false,
+            merge: false,
+        }
+    }
+
+    pub(crate) fn is_any(&self) -> bool {
+        self.any || self.type_name.is_some()
+    }
+}
+
+/// Presence policy of optional scalar and enumeration fields
+#[derive(Debug, Clone, PartialEq, darling::FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum Presence {
+    /// Default (zero) proto values map to `None` (proto3 fields without `optional` label).
+    Implicit,
+    /// Proto fields that track presence (proto3 `optional` and proto2 `optional`/`required` fields).
+    Explicit,
+    /// Every proto value maps to `Some` (`None` leaves the default proto value).
+    Always,
+    /// The sentinel proto value maps to `None` (and `None` maps to the sentinel value).
+    Sentinel(syn::Lit),
 }
 
 /// Meta attributes for `struct field` items
@@ -21,8 +71,23 @@ pub(crate) struct FieldAttrs {
     pub enumeration: bool,
     /// Optional module with implementation of override mappings (implementation depends on scalar, enumeration or other proto destination type)
     pub with: Option<Path>,
+    /// Optional module with implementation of override mappings for the keys of map fields (keys are always scalars).
+    pub key_with: Option<Path>,
     /// Optional renaming of a single struct field before mapping to the proto entity.
     pub rename: Option<String>,
+    /// Optional mark a non optional message field as required (fails instead of using a default value when missing).
+    pub required: bool,
+    /// Optional mark a message field as boxed in the proto entity (`prost` boxes the fields of recursive messages,
+    /// ignored by `protobuf` where all the message fields are boxed).
+    pub boxed: bool,
+    /// Optional mark an optional scalar field as mapped to a `google.protobuf` wrapper message (e.g. `Int32Value`)
+    /// where presence is explicit (a `Some(0)` value is not lost).
+    pub wrapper: bool,
+    /// Optional presence policy of an optional scalar or enumeration field (defaults to `implicit`).
+    pub presence: Option<Presence>,
+    /// Optional mark the field as mapped to the named `oneof` field of the proto entity (the field type is a
+    /// `one_of` enum that implements `ProtoMapOneOf`).
+    pub one_of: Option<String>,
 }
 
 impl FieldAttrs {
@@ -35,6 +100,22 @@ impl FieldAttrs {
         if self.enumeration && self.scalar {
             return Err(darling::Error::unsupported_shape("Struct attributes `enumeration` and `scalar` are mutually excluded (use only one of them)"));
         }
+        if self.wrapper && self.enumeration {
+            return Err(darling::Error::unsupported_shape("Struct attribute `wrapper` is supported only for scalar fields"));
+        }
+        if self.wrapper && self.presence.is_some() {
+            return Err(darling::Error::unsupported_shape("Struct attributes `wrapper` and `presence` are mutually excluded (wrapper messages have explicit presence)"));
+        }
+        if self.boxed && (self.enumeration || self.scalar) {
+            return Err(darling::Error::unsupported_shape("Struct attribute `boxed` is supported only for message fields"));
+        }
+        if self.required && (self.enumeration || self.scalar) {
+            return Err(darling::Error::unsupported_shape("Struct attribute `required` is supported only for message fields"));
+        }
+        if self.one_of.is_some() && (self.scalar || self.enumeration || self.with.is_some() || self.key_with.is_some() || self.rename.is_some()
+            || self.required || self.boxed || self.wrapper || self.presence.is_some()) {
+            return Err(darling::Error::unsupported_shape("Struct attribute `one_of` is mutually excluded with `scalar`, `enumeration`, `with`, `key_with`, `rename`, `required`, `boxed`, `wrapper` and `presence`"));
+        }
         Ok(self)
     }
 }
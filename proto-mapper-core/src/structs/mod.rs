@@ -10,7 +10,7 @@ pub(crate) use protobuf_field::StructField;
 
 mod r#struct;
 
-pub(crate) use r#struct::Struct;
+pub(crate) use r#struct::{implement_from_proto_all, Struct};
 
 mod attrs;
 
@@ -1,15 +1,20 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
-use super::{FieldAttrs, StructAttrs};
+use super::{FieldAttrs, Presence, StructAttrs};
 use crate::types::Ty;
-use crate::{find_proto_map_meta, get_proto_field_name};
+use crate::{
+    find_proto_map_meta, get_proto_field_name, get_proto_field_path_name,
+    implement_field_error_mapping, implement_map_from_proto, implement_one_of_from_proto,
+    implement_repeated_from_proto, path_to_string,
+};
 
 
 /// [`StructField`] describes a struct field capable of generating `getter` `setter` implementations
 /// for `ProtoMap` and `ProtoMapScalar` traits
 ///
 /// This is the implementation variant for `prost` library support
+#[derive(Debug)]
 pub(crate) struct StructField {
     pub name: Ident,
     pub ty: Ty,
@@ -31,9 +36,11 @@ impl StructField {
     /// attribute overrides.
     ///
     /// For example in `prost` (this) implementation proto `message enum ... ` is treated as scalar.
+    ///
+    /// For repeated and map fields the check refers to the element (map value) type.
     #[inline]
     pub fn is_scalar_like(&self) -> bool {
-        self.ty.is_scalar()
+        self.ty.element().is_scalar()
             || matches!(self.attrs, Some(FieldAttrs{enumeration: true, ..}))
             || matches!(self.attrs, Some(FieldAttrs{scalar: true, ..}))
     }
@@ -51,6 +58,54 @@ impl StructField {
             None
         };
 
+        if let Some(FieldAttrs { required: true, .. }) = &attrs {
+            if ty.is_scalar() || ty.is_optional() || ty.is_repeated() || ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `required` is supported only for non optional message fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { boxed: true, .. }) = &attrs {
+            if ty.is_scalar() || ty.is_repeated() || ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `boxed` is supported only for message fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { wrapper: true, scalar, .. }) = &attrs {
+            if !ty.is_optional() || !(ty.is_scalar() || *scalar) {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `wrapper` is supported only for optional scalar fields e.g. `Option<i32>`",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { presence: Some(_), scalar, enumeration, .. }) = &attrs {
+            if !ty.is_optional() || !(ty.is_scalar() || *scalar || *enumeration) {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `presence` is supported only for optional scalar or enumeration fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { key_with: Some(_), .. }) = &attrs {
+            if !ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `key_with` is supported only for map fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { one_of: Some(_), .. }) = &attrs {
+            if ty.is_scalar() || ty.is_repeated() || ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `one_of` is supported only for (optional) fields of `one_of` enums",
+                ));
+            }
+        }
+
         Ok(Self {
             name: name.clone(),
             ty,
@@ -58,6 +113,30 @@ impl StructField {
         })
     }
 
+    /// Determines the conversion method of the keys of map fields in `to_proto` (or `into_proto` if `owned`)
+    /// implementations.
+    pub fn determine_key_to_proto_method(&self, owned: bool) -> TokenStream {
+        match &self.attrs {
+            Some(FieldAttrs {
+                key_with: Some(key_with),
+                ..
+            }) => quote! { #key_with::to_scalar },
+            _ if owned => quote! { ProtoMapScalar::into_scalar },
+            _ => quote! { ProtoMapScalar::to_scalar },
+        }
+    }
+
+    /// Determines the conversion method of the keys of map fields in `from_proto` implementations.
+    pub fn determine_key_from_proto_method(&self) -> TokenStream {
+        match &self.attrs {
+            Some(FieldAttrs {
+                key_with: Some(key_with),
+                ..
+            }) => quote! { #key_with::from_scalar },
+            _ => quote! { ProtoMapScalar::from_scalar },
+        }
+    }
+
     pub fn determine_to_proto_method(&self) -> TokenStream {
         // First consult field attributes that override struct type
         if let Some(attrs) = &self.attrs {
@@ -67,7 +146,7 @@ impl StructField {
                     return quote! { ProtoMapScalar::to_scalar };
                 }
                 // Override implementation for with module  scalar
-                Some(with) if attrs.scalar || attrs.enumeration || self.ty.is_scalar() => {
+                Some(with) if attrs.scalar || attrs.enumeration || self.ty.element().is_scalar() => {
                     return quote! { #with::to_scalar };
                 }
                 // Override implementation for with module  non scalar
@@ -80,16 +159,58 @@ impl StructField {
         }
 
         // If no related attributes found return defaults
-        if self.ty.is_scalar() {
+        if self.ty.element().is_scalar() {
             quote! { ProtoMapScalar::to_scalar }
         } else {
             quote! { ProtoMap::to_proto }
         }
     }
 
+    /// Determines the conversion method of the field in `into_proto` implementations.
+    ///
+    /// Fields with `with` module overrides are converted by reference (see [`Self::implement_into_getter`]).
+    pub fn determine_into_proto_method(&self) -> TokenStream {
+        if self.is_scalar_like() {
+            quote! { ProtoMapScalar::into_scalar }
+        } else {
+            quote! { ProtoMap::into_proto }
+        }
+    }
+
     // TODO use struct attrs for rename_all
     /// Specific `prost` feature implementation of struct filed getter method.
-    pub(crate) fn implement_getter(&self, _struct_attrs: &StructAttrs) -> TokenStream {
+    pub(crate) fn implement_getter(&self, struct_attrs: &StructAttrs) -> TokenStream {
+        let struct_field = &self.name;
+        self.implement_field_getter(struct_attrs, false, quote! { self.#struct_field })
+    }
+
+    // TODO use struct attrs for rename_all
+    /// Specific `prost` feature implementation of struct field consuming getter method (moves the field value
+    /// to the proto struct).
+    pub(crate) fn implement_into_getter(&self, struct_attrs: &StructAttrs) -> TokenStream {
+        let struct_field = &self.name;
+        self.implement_field_getter(struct_attrs, self.is_owned_getter(), quote! { self.#struct_field })
+    }
+
+    /// Specific `prost` feature implementation of the getter of a named field of an enum variant that is bound
+    /// to a local variable of the same name (`by_ref` bindings are references of the field value).
+    pub(crate) fn implement_binding_getter(&self, struct_attrs: &StructAttrs, by_ref: bool) -> TokenStream {
+        let binding = &self.name;
+        if by_ref {
+            self.implement_field_getter(struct_attrs, false, quote! { (*#binding) })
+        } else {
+            self.implement_field_getter(struct_attrs, self.is_owned_getter(), quote! { #binding })
+        }
+    }
+
+    /// Checks if the consuming getter moves the field value (`with` modules convert only by reference)
+    fn is_owned_getter(&self) -> bool {
+        !matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }))
+            && !matches!(self.attrs, Some(FieldAttrs { key_with: Some(_), .. }))
+    }
+
+    /// Implementation of the getter of the field value `field` (a place expression e.g. `self.field`)
+    fn implement_field_getter(&self, struct_attrs: &StructAttrs, owned: bool, field: TokenStream) -> TokenStream {
         // Fast handle skip attribute
         if let Some(FieldAttrs { skip: true, .. }) = &self.attrs {
             return quote! {};
@@ -107,38 +228,96 @@ impl StructField {
             self.name.clone()
         };
 
-        let struct_field = &self.name;
+        let (to_proto_method, field_value) = if owned {
+            (self.determine_into_proto_method(), quote! { #field })
+        } else {
+            (self.determine_to_proto_method(), quote! { &#field })
+        };
 
-        let to_proto_method = self.determine_to_proto_method();
+        // Oneof fields set the `oneof` field (see `ProtoMapOneOf`), optional fields leave it unset for `None`
+        if let Some(FieldAttrs { one_of: Some(one_of), .. }) = &self.attrs {
+            let proto_field = format_ident!("{}", one_of);
+            let value = match (self.ty.is_optional(), owned) {
+                (true, true) => quote! { #field.and_then(ProtoMapOneOf::into_one_of) },
+                (true, false) => quote! { #field.as_ref().and_then(ProtoMapOneOf::to_one_of) },
+                (false, true) => quote! { ProtoMapOneOf::into_one_of(#field_value) },
+                (false, false) => quote! { ProtoMapOneOf::to_one_of(#field_value) },
+            };
+            return quote! {
+                proto.#proto_field = #value;
+            };
+        }
+
+        // Repeated fields convert every element
+        if self.ty.is_repeated() {
+            let values = if owned {
+                quote! { #field.into_iter() }
+            } else {
+                quote! { #field.iter() }
+            };
+            return quote! {
+                proto.#proto_field_setter = #values.map(#to_proto_method).collect();
+            };
+        }
+
+        // Map fields convert every entry
+        if self.ty.is_map() {
+            let to_proto_key_method = self.determine_key_to_proto_method(owned);
+            let entries = if owned {
+                quote! { #field.into_iter() }
+            } else {
+                quote! { #field.iter() }
+            };
+            return quote! {
+                proto.#proto_field_setter = #entries
+                    .map(|(key, value)| (#to_proto_key_method(key), #to_proto_method(value)))
+                    .collect();
+            };
+        }
 
         match (self.is_scalar_like(), self.ty.is_optional()) {
             // scalar - non optional
             (true, false) => {
                 quote! {
-                    proto.#proto_field_setter = #to_proto_method(&self.#struct_field);
+                    proto.#proto_field_setter = #to_proto_method(#field_value);
                }
             }
 
             // scalar - optional
-            (true, true) => {
-                quote! {
-                    if let Some(value) = &self.#struct_field {
+            (true, true) => match self.presence(struct_attrs) {
+                // Wrapper messages and explicit presence fields are generated as optional scalars
+                Presence::Explicit => quote! {
+                    if let Some(value) = #field_value {
+                        proto.#proto_field_setter = Some(#to_proto_method(value));
+                    }
+                },
+                Presence::Sentinel(sentinel) => quote! {
+                    if let Some(value) = #field_value {
                         proto.#proto_field_setter = #to_proto_method(value);
+                    } else {
+                        proto.#proto_field_setter = Into::into(#sentinel);
                     }
-                }
-            }
+                },
+                Presence::Implicit | Presence::Always => quote! {
+                    if let Some(value) = #field_value {
+                        proto.#proto_field_setter = #to_proto_method(value);
+                    }
+                },
+            },
 
             // non scalar - non optional
             (false, false) => {
+                let value = self.implement_boxed_value(quote! { #to_proto_method(#field_value) });
                 quote! {
-                    proto.#proto_field_setter = Some(#to_proto_method(&self.#struct_field));
+                    proto.#proto_field_setter = Some(#value);
                 }
             }
             // non scalar - optional
             (false, true) => {
+                let value = self.implement_boxed_value(quote! { #to_proto_method(value) });
                 quote! {
-                    if let Some(value) = &self.#struct_field {
-                        proto.#proto_field_setter = Some(#to_proto_method(value));
+                    if let Some(value) = #field_value {
+                        proto.#proto_field_setter = Some(#value);
                     }
                 }
             }
@@ -154,7 +333,7 @@ impl StructField {
                     return quote! { ProtoMapScalar::from_scalar };
                 }
                 // Override implementation for with module  scalar
-                Some(with) if attrs.scalar || attrs.enumeration || self.ty.is_scalar() => {
+                Some(with) if attrs.scalar || attrs.enumeration || self.ty.element().is_scalar() => {
                     return quote! { #with::from_scalar };
                 }
                 // Override implementation for with module  non scalar
@@ -166,24 +345,265 @@ impl StructField {
             };
         }
 
-        if self.ty.is_scalar() {
+        if self.ty.element().is_scalar() {
             quote! { ProtoMapScalar::from_scalar }
         } else {
             quote! { ProtoMap::from_proto }
         }
     }
 
+    /// Returns the proto field name reported in the path of conversion errors.
+    pub(crate) fn proto_field_path_name(&self) -> String {
+        if let Some(FieldAttrs {
+            rename: Some(new_name),
+            ..
+        }) = &self.attrs
+        {
+            get_proto_field_path_name(new_name)
+        } else if let Some(FieldAttrs {
+            one_of: Some(one_of),
+            ..
+        }) = &self.attrs
+        {
+            get_proto_field_path_name(one_of)
+        } else {
+            get_proto_field_path_name(&self.name.to_string())
+        }
+    }
+
+    /// Implements the error mapping of the field conversion that adds the field to the error path.
+    pub(crate) fn implement_error_mapping(&self) -> TokenStream {
+        let custom = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+        implement_field_error_mapping(&self.proto_field_path_name(), custom)
+    }
+
+    /// Determines the conversion method of the field in `try_from_proto_all` implementations.
+    ///
+    /// Nested messages (without `with` module overrides) collect all of their errors as well.
+    pub fn determine_try_from_proto_all_method(&self) -> TokenStream {
+        if self.is_scalar_like() || matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. })) {
+            self.determine_from_proto_method()
+        } else {
+            quote! { ProtoMap::try_from_proto_all }
+        }
+    }
+
+    /// Wraps the converted `value` of `boxed` message fields (prost boxes the fields of recursive messages).
+    fn implement_boxed_value(&self, value: TokenStream) -> TokenStream {
+        if let Some(FieldAttrs { boxed: true, .. }) = &self.attrs {
+            quote! { Box::new(#value) }
+        } else {
+            value
+        }
+    }
+
+    /// Unwraps the proto `value` of `boxed` message fields before the conversion.
+    fn implement_unboxed_value(&self) -> TokenStream {
+        if let Some(FieldAttrs { boxed: true, .. }) = &self.attrs {
+            quote! { *value }
+        } else {
+            quote! { value }
+        }
+    }
+
+    /// Resolves the presence policy of optional scalar and enumeration fields (the field attribute overrides the
+    /// struct one). Wrapper messages are generated as `Option<T>` proto fields, exactly as explicit presence fields.
+    pub(crate) fn presence(&self, struct_attrs: &StructAttrs) -> Presence {
+        match &self.attrs {
+            Some(FieldAttrs { wrapper: true, .. }) => Presence::Explicit,
+            _ if !self.ty.is_optional() || !self.is_scalar_like() => Presence::Implicit,
+            Some(FieldAttrs {
+                presence: Some(presence),
+                ..
+            }) => presence.clone(),
+            _ => struct_attrs.presence.clone().unwrap_or(Presence::Implicit),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_skipped(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { skip: true, .. }))
+    }
+
+    #[inline]
+    pub(crate) fn is_one_of(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { one_of: Some(_), .. }))
+    }
+
+    #[inline]
+    pub(crate) fn is_required(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { required: true, .. }))
+    }
+
+    /// Checks if the proto field tracks its presence (message and explicit presence fields) e.g. to detect the payload
+    /// fields of the other tags of `tagged` enums.
+    pub(crate) fn has_presence(&self, struct_attrs: &StructAttrs) -> bool {
+        !self.is_skipped()
+            && !self.ty.is_repeated()
+            && !self.ty.is_map()
+            && (!self.is_scalar_like() || self.presence(struct_attrs) == Presence::Explicit)
+    }
+
+    /// Specific `prost` feature implementation of the check that a message (or explicit presence) field of the proto
+    /// struct is set
+    pub(crate) fn implement_message_is_set(&self) -> TokenStream {
+        let proto_field = self.proto_field_ident();
+        quote! { proto.#proto_field.is_some() }
+    }
+
+    /// Checks if the field is a nested message that merges the nested paths of field masks (see `ProtoMapFieldMask`).
+    ///
+    /// Repeated, map, `with`, `one_of` and scalar like fields are overwritten only as a whole.
+    pub(crate) fn is_nested_message(&self) -> bool {
+        !self.is_scalar_like()
+            && !self.ty.is_repeated()
+            && !self.ty.is_map()
+            && !matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }))
+            && !matches!(self.attrs, Some(FieldAttrs { one_of: Some(_), .. }))
+    }
+
+    /// Returns the proto field ident of the struct field (following the `rename` attribute).
+    fn proto_field_ident(&self) -> Ident {
+        if let Some(FieldAttrs {
+            rename: Some(new_name),
+            ..
+        }) = &self.attrs
+        {
+            format_ident!("{}", get_proto_field_name(new_name.as_str(), None))
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// Specific `prost` feature implementation of the proto message value of nested message fields
+    /// (the default message if the field is not set) merged by field masks.
+    pub(crate) fn implement_nested_proto_value(&self) -> TokenStream {
+        let proto_field = self.proto_field_ident();
+        let deref = if let Some(FieldAttrs { boxed: true, .. }) = &self.attrs {
+            quote! { * }
+        } else {
+            quote! {}
+        };
+        quote! { #deref proto.#proto_field.unwrap_or_default() }
+    }
+
+    /// Specific `prost` feature implementation of the statement that merges the proto field value to the struct
+    /// field with the protobuf merge rules (see `ProtoMapMerge`).
+    ///
+    /// Set (non default) scalars overwrite, nested messages merge, repeated fields append and maps upsert
+    /// (`with` messages overwrite as a whole).
+    pub(crate) fn implement_merger(&self, struct_attrs: &StructAttrs) -> TokenStream {
+        if self.is_skipped() {
+            return quote! {};
+        }
+
+        let struct_field = &self.name;
+        if self.ty.is_repeated() || self.ty.is_map() {
+            let value = self.implement_setter_value(struct_attrs, false);
+            return quote! {
+                proto_mapper::merge::extend(&mut self.#struct_field, #value);
+            };
+        }
+
+        // Set oneof members replace the current variant
+        if let Some(FieldAttrs { one_of: Some(one_of), .. }) = &self.attrs {
+            let proto_field = format_ident!("{}", one_of);
+            let map_err = self.implement_error_mapping();
+            let value = if self.ty.is_optional() {
+                quote! { Some(ProtoMapOneOf::from_one_of(Some(value))#map_err?) }
+            } else {
+                quote! { ProtoMapOneOf::from_one_of(Some(value))#map_err? }
+            };
+            return quote! {
+                if let Some(value) = proto.#proto_field {
+                    self.#struct_field = #value;
+                }
+            };
+        }
+
+        let proto_field = self.proto_field_ident();
+        let from_proto_method = self.determine_from_proto_method();
+        let map_err = self.implement_error_mapping();
+        let with = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+
+        match (self.is_scalar_like(), self.ty.is_optional()) {
+            // scalar - non optional
+            (true, false) => quote! {
+                if ProtoScalar::has_value(&proto.#proto_field) {
+                    self.#struct_field = #from_proto_method(proto.#proto_field)#map_err?;
+                }
+            },
+            // scalar - optional (the presence policy decides if the field is set)
+            (true, true) => {
+                let value = self.implement_setter_value(struct_attrs, false);
+                quote! {
+                    if let Some(value) = #value {
+                        self.#struct_field = Some(value);
+                    }
+                }
+            }
+            // non scalar - non optional
+            (false, false) => {
+                let value = self.implement_unboxed_value();
+                let merge = if with {
+                    quote! { self.#struct_field = #from_proto_method(#value)#map_err?; }
+                } else {
+                    quote! { ProtoMapMerge::merge_from_proto(&mut self.#struct_field, #value)#map_err?; }
+                };
+                quote! {
+                    if let Some(value) = proto.#proto_field {
+                        #merge
+                    }
+                }
+            }
+            // non scalar - optional
+            (false, true) => {
+                let value = self.implement_unboxed_value();
+                let merge = if with {
+                    quote! { self.#struct_field = Some(#from_proto_method(#value)#map_err?); }
+                } else {
+                    quote! {
+                        match &mut self.#struct_field {
+                            Some(current) => ProtoMapMerge::merge_from_proto(current, #value)#map_err?,
+                            None => self.#struct_field = Some(#from_proto_method(#value)#map_err?),
+                        }
+                    }
+                };
+                quote! {
+                    if let Some(value) = proto.#proto_field {
+                        #merge
+                    }
+                }
+            }
+        }
+    }
+
     // TODO use struct attrs for rename_all
     /// Specific `prost` feature implementation of struct filed setter method.
-    pub(crate) fn implement_setter(&self, _struct_attrs: &StructAttrs) -> TokenStream {
+    pub(crate) fn implement_setter(&self, struct_attrs: &StructAttrs) -> TokenStream {
         let struct_field = &self.name;
 
         // Fast fail skip attribute
-        if let Some(FieldAttrs { skip: true, .. }) = &self.attrs {
+        if self.is_skipped() {
             // Default struct setter for the skipped fields.
             return quote! { #struct_field: Default::default(), };
         }
 
+        let value = self.implement_setter_value(struct_attrs, false);
+        quote! {
+            #struct_field: #value,
+        }
+    }
+
+    /// Specific `prost` feature implementation of the expression that converts the proto field value.
+    ///
+    /// With `accumulate` the expression is evaluated inside a closure that returns `ProtoMapErrors`
+    /// (see `try_from_proto_all`).
+    pub(crate) fn implement_setter_value(
+        &self,
+        struct_attrs: &StructAttrs,
+        accumulate: bool,
+    ) -> TokenStream {
         //Check field rename
         let proto_field = if let Some(FieldAttrs {
                                           rename: Some(new_name),
@@ -193,58 +613,147 @@ impl StructField {
             let field_name = get_proto_field_name(new_name.as_str(), None);
             format_ident!("{}", field_name)
         } else {
-            struct_field.clone() // Here proto and struct field are the same
+            self.name.clone() // Here proto and struct field are the same
         };
 
-        let from_proto_method = self.determine_from_proto_method();
+        // Oneof fields convert the `oneof` field (see `ProtoMapOneOf`)
+        if let Some(FieldAttrs { one_of: Some(one_of), .. }) = &self.attrs {
+            let proto_field = format_ident!("{}", one_of);
+            return implement_one_of_from_proto(quote! { proto.#proto_field }, self.ty.is_optional(), accumulate);
+        }
+
+        let from_proto_method = if accumulate {
+            self.determine_try_from_proto_all_method()
+        } else {
+            self.determine_from_proto_method()
+        };
+        let map_err = self.implement_error_mapping();
 
         let proto_field_getter = &proto_field;
 
+        // Repeated fields convert every element
+        if self.ty.is_repeated() {
+            let custom = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+            let elements = implement_repeated_from_proto(
+                quote! { proto.#proto_field_getter.into_iter() },
+                quote! { #from_proto_method(value) },
+                custom,
+                accumulate,
+            );
+            let map_err = implement_field_error_mapping(&self.proto_field_path_name(), false);
+            return quote! {
+                #elements #map_err?
+            };
+        }
+
+        // Map fields convert every entry
+        if let Ty::Map { map, .. } = &self.ty {
+            let from_proto_key_method = self.determine_key_from_proto_method();
+            let custom_key = matches!(self.attrs, Some(FieldAttrs { key_with: Some(_), .. }));
+            let custom_value = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+            let entries = implement_map_from_proto(
+                *map,
+                quote! { proto.#proto_field_getter.into_iter() },
+                (quote! { #from_proto_key_method(key) }, custom_key),
+                (quote! { #from_proto_method(value) }, custom_value),
+                accumulate,
+            );
+            let map_err = implement_field_error_mapping(&self.proto_field_path_name(), false);
+            return quote! {
+                #entries #map_err?
+            };
+        }
+
         match (self.is_scalar_like(), self.ty.is_optional()) {
             // scalar - non optional
             (true, false) => {
                 quote! {
-                    #struct_field: #from_proto_method(proto.#proto_field_getter)?,
+                    #from_proto_method(proto.#proto_field_getter)#map_err?
                 }
             }
 
             // scalar - optional
-            (true, true) => {
-
-                quote! {
-                    #struct_field: {
+            (true, true) => match self.presence(struct_attrs) {
+                // Wrapper messages and explicit presence fields are generated as optional scalars
+                Presence::Explicit => quote! {
+                    {
+                        if let Some(value) = proto.#proto_field_getter {
+                            Some(#from_proto_method(value)#map_err?)
+                        } else {
+                            None
+                        }
+                    }
+                },
+                Presence::Always => quote! {
+                    Some(#from_proto_method(proto.#proto_field_getter)#map_err?)
+                },
+                Presence::Sentinel(sentinel) => quote! {
+                    {
+                        let value = proto.#proto_field_getter;
+                        if value == #sentinel {
+                            None
+                        } else {
+                            Some(#from_proto_method(value)#map_err?)
+                        }
+                    }
+                },
+                Presence::Implicit => quote! {
+                    {
                         let value = proto.#proto_field_getter;
                         if ProtoScalar::has_value(&value) {
-                            Some(#from_proto_method(value)?)
+                            Some(#from_proto_method(value)#map_err?)
                         } else {
                             None
                         }
-                    },
-                }
-            }
+                    }
+                },
+            },
 
             // non scalar - non optional
             (false, false) => {
+                // `with` modules convert the default proto value (as `protobuf` getters do)
+                // since the mapped types may not implement `Default`
+                if let Some(FieldAttrs { with: Some(_), required: false, boxed, .. }) = &self.attrs {
+                    let deref = if *boxed { quote! { * } } else { quote! {} };
+                    return quote! {
+                        #from_proto_method(#deref proto.#proto_field_getter.unwrap_or_default())#map_err?
+                    };
+                }
+
+                // Missing required messages fail instead of using the default value
+                let missing = if let Some(FieldAttrs { required: true, .. }) = &self.attrs {
+                    let type_name = path_to_string(&struct_attrs.source);
+                    let field = self.proto_field_path_name();
+                    let into = if accumulate { quote! { .into() } } else { quote! {} };
+                    quote! {
+                        return Err(ProtoMapError::missing_message(#type_name).with_field(#field)#into)
+                    }
+                } else {
+                    quote! { Default::default() }
+                };
+
+                let value = self.implement_unboxed_value();
                 quote! {
-                    #struct_field: {
+                    {
                         if let Some(value) = proto.#proto_field_getter {
-                            #from_proto_method(value)?
+                            #from_proto_method(#value)#map_err?
                         } else {
-                            Default::default()
+                            #missing
                         }
-                    },
+                    }
                 }
             }
             // non scalar - optional
             (false, true) => {
+                let value = self.implement_unboxed_value();
                 quote! {
-                    #struct_field: {
+                    {
                         if let Some(value) = proto.#proto_field_getter {
-                            Some(#from_proto_method(value)?)
+                            Some(#from_proto_method(#value)#map_err?)
                         } else {
                             None
                         }
-                    },
+                    }
                 }
             }
         }
@@ -1,14 +1,19 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
-use super::{FieldAttrs, StructAttrs};
+use super::{FieldAttrs, Presence, StructAttrs};
 use crate::types::Ty;
-use crate::{find_proto_map_meta, get_proto_field_name};
+use crate::{
+    find_proto_map_meta, get_proto_field_name, get_proto_field_path_name,
+    implement_field_error_mapping, implement_map_from_proto, implement_one_of_from_proto,
+    implement_repeated_from_proto, path_to_string,
+};
 
 /// [`StructField`] describes a struct field capable of generating `getter` `setter` implementations
 /// for `ProtoMap` and `ProtoMapScalar` traits
 ///
 /// This is the implementation variant for `protobuf` library support
+#[derive(Debug)]
 pub(crate) struct StructField {
     pub name: Ident,
     pub ty: Ty,
@@ -38,6 +43,54 @@ impl StructField {
             None
         };
 
+        if let Some(FieldAttrs { required: true, .. }) = &attrs {
+            if ty.is_scalar() || ty.is_optional() || ty.is_repeated() || ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `required` is supported only for non optional message fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { boxed: true, .. }) = &attrs {
+            if ty.is_scalar() || ty.is_repeated() || ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `boxed` is supported only for message fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { wrapper: true, scalar, .. }) = &attrs {
+            if !ty.is_optional() || !(ty.is_scalar() || *scalar) {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `wrapper` is supported only for optional scalar fields e.g. `Option<i32>`",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { presence: Some(_), scalar, enumeration, .. }) = &attrs {
+            if !ty.is_optional() || !(ty.is_scalar() || *scalar || *enumeration) {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `presence` is supported only for optional scalar or enumeration fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { key_with: Some(_), .. }) = &attrs {
+            if !ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `key_with` is supported only for map fields",
+                ));
+            }
+        }
+
+        if let Some(FieldAttrs { one_of: Some(_), .. }) = &attrs {
+            if ty.is_scalar() || ty.is_repeated() || ty.is_map() {
+                return Err(darling::Error::unsupported_shape(
+                    "Struct attribute `one_of` is supported only for (optional) fields of `one_of` enums",
+                ));
+            }
+        }
+
         Ok(Self {
             name: name.clone(),
             ty,
@@ -45,6 +98,30 @@ impl StructField {
         })
     }
 
+    /// Determines the conversion method of the keys of map fields in `to_proto` (or `into_proto` if `owned`)
+    /// implementations.
+    pub fn determine_key_to_proto_method(&self, owned: bool) -> TokenStream {
+        match &self.attrs {
+            Some(FieldAttrs {
+                key_with: Some(key_with),
+                ..
+            }) => quote! { #key_with::to_scalar },
+            _ if owned => quote! { ProtoMapScalar::into_scalar },
+            _ => quote! { ProtoMapScalar::to_scalar },
+        }
+    }
+
+    /// Determines the conversion method of the keys of map fields in `from_proto` implementations.
+    pub fn determine_key_from_proto_method(&self) -> TokenStream {
+        match &self.attrs {
+            Some(FieldAttrs {
+                key_with: Some(key_with),
+                ..
+            }) => quote! { #key_with::from_scalar },
+            _ => quote! { ProtoMapScalar::from_scalar },
+        }
+    }
+
     pub fn determine_to_proto_method(&self) -> TokenStream {
         // First consult field attributes that override struct type
         if let Some(attrs) = &self.attrs {
@@ -53,13 +130,14 @@ impl StructField {
                 None if attrs.scalar => {
                     return quote! { ProtoMapScalar::to_scalar };
                 }
+                // Enumerations are set as possibly unknown values
                 None if attrs.enumeration => {
-                    return quote! { ProtoMap::to_proto };
+                    return quote! { ProtoMapEnum::to_enum_or_unknown };
                 }
 
                 // TODO protobuf enumeration with override should be done via #with::to_proto interface
                 // Override implementation for with module  scalar
-                Some(with) if attrs.scalar || attrs.enumeration || self.ty.is_scalar() => {
+                Some(with) if attrs.scalar || attrs.enumeration || self.ty.element().is_scalar() => {
                     return quote! { #with::to_scalar };
                 }
                 // Override implementation for with module  non scalar
@@ -72,48 +150,183 @@ impl StructField {
         }
 
         // If no related attributes found return defaults
-        if self.ty.is_scalar() {
+        if self.ty.element().is_scalar() {
             quote! { ProtoMapScalar::to_scalar }
         } else {
             quote! { ProtoMap::to_proto }
         }
     }
 
+    /// Determines the conversion method of the field in `into_proto` implementations.
+    ///
+    /// Fields with `with` module overrides are converted by reference (see [`Self::implement_into_getter`]).
+    pub fn determine_into_proto_method(&self) -> TokenStream {
+        match &self.attrs {
+            Some(FieldAttrs { scalar: true, .. }) => quote! { ProtoMapScalar::into_scalar },
+            Some(FieldAttrs { enumeration: true, with: None, .. }) => quote! { ProtoMapEnum::into_enum_or_unknown },
+            _ if self.ty.element().is_scalar() => quote! { ProtoMapScalar::into_scalar },
+            _ => quote! { ProtoMap::into_proto },
+        }
+    }
+
     // TODO use struct attrs for rename_all
     /// Specific `protobuf` feature implementation of struct filed getter method.
-    pub(crate) fn implement_getter(&self, _struct_attrs: &StructAttrs) -> TokenStream {
+    pub(crate) fn implement_getter(&self, struct_attrs: &StructAttrs) -> TokenStream {
+        let struct_field = &self.name;
+        self.implement_field_getter(struct_attrs, false, quote! { self.#struct_field })
+    }
+
+    // TODO use struct attrs for rename_all
+    /// Specific `protobuf` feature implementation of struct field consuming getter method (moves the field value
+    /// to the proto struct).
+    pub(crate) fn implement_into_getter(&self, struct_attrs: &StructAttrs) -> TokenStream {
+        let struct_field = &self.name;
+        self.implement_field_getter(struct_attrs, self.is_owned_getter(), quote! { self.#struct_field })
+    }
+
+    /// Specific `protobuf` feature implementation of the getter of a named field of an enum variant that is bound
+    /// to a local variable of the same name (`by_ref` bindings are references of the field value).
+    pub(crate) fn implement_binding_getter(&self, struct_attrs: &StructAttrs, by_ref: bool) -> TokenStream {
+        let binding = &self.name;
+        if by_ref {
+            self.implement_field_getter(struct_attrs, false, quote! { (*#binding) })
+        } else {
+            self.implement_field_getter(struct_attrs, self.is_owned_getter(), quote! { #binding })
+        }
+    }
+
+    /// Checks if the consuming getter moves the field value (`with` modules convert only by reference)
+    fn is_owned_getter(&self) -> bool {
+        !matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }))
+            && !matches!(self.attrs, Some(FieldAttrs { key_with: Some(_), .. }))
+    }
+
+    /// Implementation of the getter of the field value `field` (a place expression e.g. `self.field`)
+    fn implement_field_getter(&self, struct_attrs: &StructAttrs, owned: bool, field: TokenStream) -> TokenStream {
         // Fast handle skip attribute
         if let Some(FieldAttrs { skip: true, .. }) = &self.attrs {
             return quote! {};
         }
 
         //Check field rename
-        let proto_field_setter = if let Some(FieldAttrs {
+        let proto_field = if let Some(FieldAttrs {
             rename: Some(new_name),
             ..
         }) = &self.attrs
         {
-            let field_name = get_proto_field_name(new_name.as_str(), Some('_'));
-            format_ident!("set_{}", field_name)
+            get_proto_field_name(new_name.as_str(), Some('_'))
         } else {
-            format_ident!("set_{}", &self.name)
+            self.name.to_string()
         };
+        let proto_field_setter = format_ident!("set_{}", proto_field);
 
-        let struct_field = &self.name;
+        let (to_proto_method, field_value) = if owned {
+            (self.determine_into_proto_method(), quote! { #field })
+        } else {
+            (self.determine_to_proto_method(), quote! { &#field })
+        };
 
-        let to_proto_method = self.determine_to_proto_method();
+        // Oneof fields set the `oneof` field (see `ProtoMapOneOf`), optional fields leave it unset for `None`
+        if let Some(FieldAttrs { one_of: Some(one_of), .. }) = &self.attrs {
+            let proto_field = format_ident!("{}", one_of);
+            let value = match (self.ty.is_optional(), owned) {
+                (true, true) => quote! { #field.and_then(ProtoMapOneOf::into_one_of) },
+                (true, false) => quote! { #field.as_ref().and_then(ProtoMapOneOf::to_one_of) },
+                (false, true) => quote! { ProtoMapOneOf::into_one_of(#field_value) },
+                (false, false) => quote! { ProtoMapOneOf::to_one_of(#field_value) },
+            };
+            return quote! {
+                proto.#proto_field = #value;
+            };
+        }
+
+        // Message `with` modules return the proto message type (possibly a generic one) that needs no conversion
+        let into = if self.is_message_with() {
+            quote! {}
+        } else {
+            quote! { .into() }
+        };
 
-        if self.ty.is_optional() {
-            // Optional field setter
+        if self.ty.is_repeated() {
+            // Repeated field setter converts every element
+            let values = if owned {
+                quote! { #field.into_iter() }
+            } else {
+                quote! { #field.iter() }
+            };
+            quote! {
+                proto.#proto_field_setter(#values.map(|value| #to_proto_method(value)#into).collect());
+            }
+        } else if self.ty.is_map() {
+            // Map field setter converts every entry
+            let to_proto_key_method = self.determine_key_to_proto_method(owned);
+            let entries = if owned {
+                quote! { #field.into_iter() }
+            } else {
+                quote! { #field.iter() }
+            };
             quote! {
-                if let Some(value) = &self.#struct_field {
-                    proto.#proto_field_setter(#to_proto_method(value).into());
+                proto.#proto_field_setter(
+                    #entries
+                        .map(|(key, value)| (#to_proto_key_method(key), #to_proto_method(value)#into))
+                        .collect(),
+                );
+            }
+        } else if self.is_wrapper() {
+            // Wrapper message field sets the inner value (presence is kept even for default values)
+            let proto_field_mut = format_ident!("mut_{}", proto_field);
+            quote! {
+                if let Some(value) = #field_value {
+                    proto.#proto_field_mut().value = #to_proto_method(value);
                 }
             }
+        } else if self.is_raw_enumeration() {
+            // Enumeration fields are set as possibly unknown enumeration values (see `ProtoMapEnum`)
+            let proto_field = self.proto_field_ident();
+            if !self.ty.is_optional() {
+                return quote! {
+                    proto.#proto_field = #to_proto_method(#field_value);
+                };
+            }
+            match self.presence(struct_attrs) {
+                Presence::Explicit => quote! {
+                    if let Some(value) = #field_value {
+                        proto.#proto_field = Some(#to_proto_method(value));
+                    }
+                },
+                // Enumeration sentinels are set as possibly unknown enumeration values
+                Presence::Sentinel(sentinel) => quote! {
+                    if let Some(value) = #field_value {
+                        proto.#proto_field = #to_proto_method(value);
+                    } else {
+                        proto.#proto_field = ::protobuf::EnumOrUnknown::from_i32(#sentinel);
+                    }
+                },
+                Presence::Implicit | Presence::Always => quote! {
+                    if let Some(value) = #field_value {
+                        proto.#proto_field = #to_proto_method(value);
+                    }
+                },
+            }
+        } else if self.ty.is_optional() {
+            // Optional field setter (sentinel presence sets the sentinel value for `None`)
+            let missing = match self.presence(struct_attrs) {
+                Presence::Sentinel(sentinel) => quote! {
+                    else {
+                        proto.#proto_field_setter(Into::into(#sentinel));
+                    }
+                },
+                _ => quote! {},
+            };
+            quote! {
+                if let Some(value) = #field_value {
+                    proto.#proto_field_setter(#to_proto_method(value)#into);
+                } #missing
+            }
         } else {
             // Non optional field just a setter
             quote! {
-                proto.#proto_field_setter(#to_proto_method(&self.#struct_field).into());
+                proto.#proto_field_setter(#to_proto_method(#field_value)#into);
             }
         }
     }
@@ -125,9 +338,9 @@ impl StructField {
                 None if attrs.scalar => {
                     return quote! { ProtoMapScalar::from_scalar };
                 }
-                None if attrs.enumeration => return quote! {ProtoMap::from_proto},
+                None if attrs.enumeration => return quote! { ProtoMapEnum::from_enum_or_unknown },
                 // Override implementation for with module  scalar
-                Some(with) if attrs.scalar || attrs.enumeration || self.ty.is_scalar() => {
+                Some(with) if attrs.scalar || attrs.enumeration || self.ty.element().is_scalar() => {
                     return quote! { #with::from_scalar };
                 }
                 // Override implementation for with module  non scalar
@@ -139,7 +352,7 @@ impl StructField {
             };
         }
 
-        if self.ty.is_scalar() {
+        if self.ty.element().is_scalar() {
             quote! { ProtoMapScalar::from_scalar }
         } else {
             quote! { ProtoMap::from_proto }
@@ -159,7 +372,7 @@ impl StructField {
             }
         }
 
-        if self.ty.is_scalar() {
+        if self.ty.element().is_scalar() {
             quote! { ProtoScalar::has_value(&value) }
         } else {
             let has_field = format_ident!("has_{}", proto_field);
@@ -167,17 +380,265 @@ impl StructField {
         }
     }
 
+    /// Returns the proto field name reported in the path of conversion errors.
+    pub(crate) fn proto_field_path_name(&self) -> String {
+        if let Some(FieldAttrs {
+            rename: Some(new_name),
+            ..
+        }) = &self.attrs
+        {
+            get_proto_field_path_name(new_name)
+        } else if let Some(FieldAttrs {
+            one_of: Some(one_of),
+            ..
+        }) = &self.attrs
+        {
+            get_proto_field_path_name(one_of)
+        } else {
+            get_proto_field_path_name(&self.name.to_string())
+        }
+    }
+
+    /// Implements the error mapping of the field conversion that adds the field to the error path.
+    pub(crate) fn implement_error_mapping(&self) -> TokenStream {
+        let custom = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+        implement_field_error_mapping(&self.proto_field_path_name(), custom)
+    }
+
+    /// Determines the conversion method of the field in `try_from_proto_all` implementations.
+    ///
+    /// Nested messages (without `with` module overrides) collect all of their errors as well.
+    pub fn determine_try_from_proto_all_method(&self) -> TokenStream {
+        match &self.attrs {
+            Some(FieldAttrs { with: Some(_), .. })
+            | Some(FieldAttrs { scalar: true, .. })
+            | Some(FieldAttrs { enumeration: true, .. }) => self.determine_from_proto_method(),
+            _ if self.ty.element().is_scalar() => self.determine_from_proto_method(),
+            _ => quote! { ProtoMap::try_from_proto_all },
+        }
+    }
+
+    /// Resolves the presence policy of optional scalar and enumeration fields (the field attribute overrides the
+    /// struct one).
+    pub(crate) fn presence(&self, struct_attrs: &StructAttrs) -> Presence {
+        match &self.attrs {
+            Some(FieldAttrs { wrapper: true, .. }) => Presence::Explicit,
+            _ if !self.ty.is_optional() || !self.is_scalar_like() => Presence::Implicit,
+            Some(FieldAttrs {
+                presence: Some(presence),
+                ..
+            }) => presence.clone(),
+            _ => struct_attrs.presence.clone().unwrap_or(Presence::Implicit),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_scalar_like(&self) -> bool {
+        self.ty.element().is_scalar()
+            || matches!(self.attrs, Some(FieldAttrs { enumeration: true, .. }))
+            || matches!(self.attrs, Some(FieldAttrs { scalar: true, .. }))
+    }
+
+    #[inline]
+    pub(crate) fn is_message_with(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. })) && !self.is_scalar_like()
+    }
+
+    #[inline]
+    pub(crate) fn is_enumeration(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { enumeration: true, .. }))
+    }
+
+    /// Checks if the field is an enumeration mapped from the raw `EnumOrUnknown` proto field (see `ProtoMapEnum`).
+    #[inline]
+    pub(crate) fn is_raw_enumeration(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { enumeration: true, with: None, .. }))
+    }
+
+    /// Implements the proto value of a non repeated field, the raw `EnumOrUnknown` value of enumerations or an
+    /// owned value of the getter of the rest of the fields.
+    fn implement_proto_value(&self, proto_field: &Ident) -> TokenStream {
+        if self.is_raw_enumeration() {
+            quote! { proto.#proto_field }
+        } else {
+            quote! { proto.#proto_field().to_owned() }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_wrapper(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { wrapper: true, .. }))
+    }
+
+    #[inline]
+    pub(crate) fn is_skipped(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { skip: true, .. }))
+    }
+
+    #[inline]
+    pub(crate) fn is_one_of(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { one_of: Some(_), .. }))
+    }
+
+    #[inline]
+    pub(crate) fn is_required(&self) -> bool {
+        matches!(self.attrs, Some(FieldAttrs { required: true, .. }))
+    }
+
+    /// Checks if the proto field tracks its presence (message and explicit presence fields) e.g. to detect the payload
+    /// fields of the other tags of `tagged` enums.
+    pub(crate) fn has_presence(&self, struct_attrs: &StructAttrs) -> bool {
+        !self.is_skipped()
+            && !self.ty.is_repeated()
+            && !self.ty.is_map()
+            && (!self.is_scalar_like() || self.presence(struct_attrs) == Presence::Explicit)
+    }
+
+    /// Specific `protobuf` feature implementation of the check that a message (or explicit presence) field of the proto
+    /// struct is set
+    pub(crate) fn implement_message_is_set(&self) -> TokenStream {
+        let has_field = format_ident!("has_{}", self.proto_field_ident());
+        quote! { proto.#has_field() }
+    }
+
+    /// Checks if the field is a nested message that merges the nested paths of field masks (see `ProtoMapFieldMask`).
+    ///
+    /// Repeated, map, `with`, `one_of` and scalar like fields are overwritten only as a whole.
+    pub(crate) fn is_nested_message(&self) -> bool {
+        !self.is_scalar_like()
+            && !self.ty.is_repeated()
+            && !self.ty.is_map()
+            && !matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }))
+            && !matches!(self.attrs, Some(FieldAttrs { one_of: Some(_), .. }))
+    }
+
+    /// Returns the proto field ident of the struct field (following the `rename` attribute).
+    fn proto_field_ident(&self) -> Ident {
+        if let Some(FieldAttrs {
+            rename: Some(new_name),
+            ..
+        }) = &self.attrs
+        {
+            format_ident!("{}", get_proto_field_name(new_name.as_str(), None))
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// Specific `protobuf` feature implementation of the proto message value of nested message fields
+    /// (the default message if the field is not set) merged by field masks.
+    pub(crate) fn implement_nested_proto_value(&self) -> TokenStream {
+        let proto_field = self.proto_field_ident();
+        quote! { proto.#proto_field().to_owned() }
+    }
+
+    /// Specific `protobuf` feature implementation of the statement that merges the proto field value to the struct
+    /// field with the protobuf merge rules (see `ProtoMapMerge`).
+    ///
+    /// Set (non default) scalars overwrite, nested messages merge, repeated fields append and maps upsert
+    /// (`with` messages overwrite as a whole).
+    pub(crate) fn implement_merger(&self, struct_attrs: &StructAttrs) -> TokenStream {
+        if self.is_skipped() {
+            return quote! {};
+        }
+
+        let struct_field = &self.name;
+        if self.ty.is_repeated() || self.ty.is_map() {
+            let value = self.implement_setter_value(struct_attrs, false);
+            return quote! {
+                proto_mapper::merge::extend(&mut self.#struct_field, #value);
+            };
+        }
+
+        // Set oneof members replace the current variant
+        if let Some(FieldAttrs { one_of: Some(one_of), .. }) = &self.attrs {
+            let proto_field = format_ident!("{}", one_of);
+            let map_err = self.implement_error_mapping();
+            let value = if self.ty.is_optional() {
+                quote! { Some(ProtoMapOneOf::from_one_of(Some(value))#map_err?) }
+            } else {
+                quote! { ProtoMapOneOf::from_one_of(Some(value))#map_err? }
+            };
+            return quote! {
+                if let Some(value) = proto.#proto_field.clone() {
+                    self.#struct_field = #value;
+                }
+            };
+        }
+
+        // Optional scalars and wrappers (the presence policy decides if the field is set)
+        if self.is_scalar_like() && self.ty.is_optional() {
+            let value = self.implement_setter_value(struct_attrs, false);
+            return quote! {
+                if let Some(value) = #value {
+                    self.#struct_field = Some(value);
+                }
+            };
+        }
+
+        let proto_field = self.proto_field_ident();
+        let from_proto_method = self.determine_from_proto_method();
+        let map_err = self.implement_error_mapping();
+
+        if self.is_scalar_like() {
+            let has_value_method = self.determine_has_value_method(&proto_field);
+            let proto_value = self.implement_proto_value(&proto_field);
+            return quote! {
+                {
+                    let value = #proto_value;
+                    if #has_value_method {
+                        self.#struct_field = #from_proto_method(value)#map_err?;
+                    }
+                }
+            };
+        }
+
+        let has_field = format_ident!("has_{}", proto_field);
+        let merge = match (self.is_message_with(), self.ty.is_optional()) {
+            (true, false) => quote! { self.#struct_field = #from_proto_method(value)#map_err?; },
+            (true, true) => quote! { self.#struct_field = Some(#from_proto_method(value)#map_err?); },
+            (false, false) => quote! { ProtoMapMerge::merge_from_proto(&mut self.#struct_field, value)#map_err?; },
+            (false, true) => quote! {
+                match &mut self.#struct_field {
+                    Some(current) => ProtoMapMerge::merge_from_proto(current, value)#map_err?,
+                    None => self.#struct_field = Some(#from_proto_method(value)#map_err?),
+                }
+            },
+        };
+        quote! {
+            if proto.#has_field() {
+                let value = proto.#proto_field().to_owned();
+                #merge
+            }
+        }
+    }
+
     // TODO use struct attrs for rename_all
     /// Specific `protobuf` feature implementation of struct filed setter method.
-    pub(crate) fn implement_setter(&self, _struct_attrs: &StructAttrs) -> TokenStream {
+    pub(crate) fn implement_setter(&self, struct_attrs: &StructAttrs) -> TokenStream {
         let struct_field = &self.name;
 
         // Fast fail skip attribute
-        if let Some(FieldAttrs { skip: true, .. }) = &self.attrs {
+        if self.is_skipped() {
             // Default struct setter for the skipped fields.
             return quote! { #struct_field: Default::default(), };
         }
 
+        let value = self.implement_setter_value(struct_attrs, false);
+        quote! {
+            #struct_field: #value,
+        }
+    }
+
+    /// Specific `protobuf` feature implementation of the expression that converts the proto field value.
+    ///
+    /// With `accumulate` the expression is evaluated inside a closure that returns `ProtoMapErrors`
+    /// (see `try_from_proto_all`).
+    pub(crate) fn implement_setter_value(
+        &self,
+        struct_attrs: &StructAttrs,
+        accumulate: bool,
+    ) -> TokenStream {
         //Check field rename
         let proto_field = if let Some(FieldAttrs {
             rename: Some(new_name),
@@ -187,32 +648,154 @@ impl StructField {
             let field_name = get_proto_field_name(new_name.as_str(), None);
             format_ident!("{}", field_name)
         } else {
-            struct_field.clone() // Here proto and struct field are the same
+            self.name.clone() // Here proto and struct field are the same
         };
 
-        let from_proto_method = self.determine_from_proto_method();
+        // Oneof fields convert the `oneof` field (see `ProtoMapOneOf`)
+        // Note: the member is cloned since the rest of the fields are read by the getters of the proto entity
+        if let Some(FieldAttrs { one_of: Some(one_of), .. }) = &self.attrs {
+            let proto_field = format_ident!("{}", one_of);
+            return implement_one_of_from_proto(quote! { proto.#proto_field.clone() }, self.ty.is_optional(), accumulate);
+        }
 
-        let proto_field_getter = format_ident!("{}", proto_field);
+        let from_proto_method = if accumulate {
+            self.determine_try_from_proto_all_method()
+        } else {
+            self.determine_from_proto_method()
+        };
+        let map_err = self.implement_error_mapping();
 
-        if self.ty.is_optional() {
-            // Determine the appropriate has_value method
-            let has_value_method = self.determine_has_value_method(&proto_field);
+        let proto_field_getter = format_ident!("{}", proto_field);
+        let proto_value = self.implement_proto_value(&proto_field_getter);
 
-            // In case of optional check value is empty via `has_value_method`
+        if self.ty.is_repeated() {
+            // Repeated field converts every element
+            // Enumeration elements are stored as `EnumOrUnknown` values (see `ProtoMapEnum`)
+            let custom = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+            let convert = quote! { #from_proto_method(value) };
+            let elements = implement_repeated_from_proto(
+                quote! { proto.#proto_field_getter().iter().cloned() },
+                convert,
+                custom,
+                accumulate,
+            );
+            let map_err = implement_field_error_mapping(&self.proto_field_path_name(), false);
             quote! {
-                #struct_field: {
-                    let value = proto.#proto_field_getter().to_owned();
-                    if #has_value_method {
-                        Some(#from_proto_method(value)?)
+                #elements #map_err?
+            }
+        } else if let Ty::Map { map, .. } = &self.ty {
+            // Map field converts every entry
+            let from_proto_key_method = self.determine_key_from_proto_method();
+            let custom_key = matches!(self.attrs, Some(FieldAttrs { key_with: Some(_), .. }));
+            // Enumeration values are stored as `EnumOrUnknown` values (see `ProtoMapEnum`)
+            let custom_value = matches!(self.attrs, Some(FieldAttrs { with: Some(_), .. }));
+            let convert_value = quote! { #from_proto_method(value) };
+            let entries = implement_map_from_proto(
+                *map,
+                quote! { proto.#proto_field_getter().clone().into_iter() },
+                (quote! { #from_proto_key_method(key) }, custom_key),
+                (convert_value, custom_value),
+                accumulate,
+            );
+            let map_err = implement_field_error_mapping(&self.proto_field_path_name(), false);
+            quote! {
+                #entries #map_err?
+            }
+        } else if self.is_wrapper() {
+            // Wrapper message field is present only if the message is set
+            let has_field = format_ident!("has_{}", proto_field);
+            quote! {
+                {
+                    if proto.#has_field() {
+                        Some(#from_proto_method(proto.#proto_field_getter().value.to_owned())#map_err?)
                     } else {
                         None
                     }
+                }
+            }
+        } else if self.ty.is_optional() {
+            match self.presence(struct_attrs) {
+                // Explicit presence field is present only if the field is set
+                // Explicit presence enumerations are stored as optional `EnumOrUnknown` values
+                Presence::Explicit if self.is_raw_enumeration() => quote! {
+                    {
+                        if let Some(value) = proto.#proto_field_getter {
+                            Some(#from_proto_method(value)#map_err?)
+                        } else {
+                            None
+                        }
+                    }
+                },
+                Presence::Explicit => {
+                    let has_field = format_ident!("has_{}", proto_field);
+                    quote! {
+                        {
+                            if proto.#has_field() {
+                                Some(#from_proto_method(proto.#proto_field_getter().to_owned())#map_err?)
+                            } else {
+                                None
+                            }
+                        }
+                    }
+                }
+                Presence::Always => quote! {
+                    Some(#from_proto_method(#proto_value)#map_err?)
                 },
+                Presence::Sentinel(sentinel) => {
+                    // Enumeration sentinels are compared to the enumeration values
+                    let value = if self.is_raw_enumeration() {
+                        quote! { value.value() }
+                    } else if self.is_enumeration() {
+                        quote! { ::protobuf::Enum::value(&value) }
+                    } else {
+                        quote! { value }
+                    };
+                    quote! {
+                        {
+                            let value = #proto_value;
+                            if #value == #sentinel {
+                                None
+                            } else {
+                                Some(#from_proto_method(value)#map_err?)
+                            }
+                        }
+                    }
+                }
+                Presence::Implicit => {
+                    // Determine the appropriate has_value method
+                    let has_value_method = self.determine_has_value_method(&proto_field);
+
+                    // In case of optional check value is empty via `has_value_method`
+                    quote! {
+                        {
+                            let value = #proto_value;
+                            if #has_value_method {
+                                Some(#from_proto_method(value)#map_err?)
+                            } else {
+                                None
+                            }
+                        }
+                    }
+                }
+            }
+        } else if let Some(FieldAttrs { required: true, .. }) = &self.attrs {
+            // Missing required messages fail instead of using the default value
+            let type_name = path_to_string(&struct_attrs.source);
+            let field = self.proto_field_path_name();
+            let has_field = format_ident!("has_{}", field);
+            let into = if accumulate { quote! { .into() } } else { quote! {} };
+            quote! {
+                {
+                    if !proto.#has_field() {
+                        return Err(ProtoMapError::missing_message(#type_name).with_field(#field)#into);
+                    }
+                    #from_proto_method(proto.#proto_field_getter().to_owned())#map_err?
+                }
             }
         } else {
             // Non optional field just a setter
             quote! {
-                #struct_field: #from_proto_method(proto.#proto_field_getter().to_owned())?,
+                #from_proto_method(#proto_value)#map_err?
             }
         }
     }
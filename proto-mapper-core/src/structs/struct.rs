@@ -1,9 +1,10 @@
-use super::attrs::StructAttrs;
-use crate::find_proto_map_meta;
+use super::attrs::{Presence, StructAttrs};
+use crate::{find_proto_map_meta, path_to_string};
 use crate::structs::StructField;
 use darling::FromMeta;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
+use syn::ext::IdentExt;
 use syn::{Attribute, DataStruct};
 
 /// Macro implementor of `struct` items.
@@ -24,6 +25,11 @@ impl Struct {
         })?;
 
         let attrs = StructAttrs::from_meta(meta)?;
+        if let Some(Presence::Sentinel(_)) = &attrs.presence {
+            return Err(darling::Error::unsupported_shape(
+                "Struct attribute `presence` supports a `sentinel` value only for single fields",
+            ));
+        }
 
         let fields = data
             .fields
@@ -52,10 +58,21 @@ impl Struct {
             }
         };
 
+        let into_proto_impl = {
+            let fields = self.fields.iter().map(|f| f.implement_into_getter(&self.attrs));
+
+            quote! {
+                let mut proto = #proto_struct::default();
+                #(#fields)*
+                proto
+            }
+        };
+
         let from_proto_impl = {
             let fields = self.fields.iter().map(|f| f.implement_setter(&self.attrs));
 
             quote! {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
                     #(#fields)*
                 };
@@ -63,6 +80,11 @@ impl Struct {
             }
         };
 
+        let try_from_proto_all = self.implement_try_from_proto_all();
+        let proto_map_any = self.implement_proto_map_any();
+        let proto_map_field_mask = self.implement_proto_map_field_mask();
+        let proto_map_merge = self.implement_proto_map_merge();
+
         quote! {
             impl ProtoMap for #struct_name {
                 type ProtoStruct = #proto_struct;
@@ -70,10 +92,274 @@ impl Struct {
                     #to_proto_impl
                 }
 
-                fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+                fn into_proto(self) -> Self::ProtoStruct {
+                    #into_proto_impl
+                }
+
+                fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
                     #from_proto_impl
                 }
+
+                #try_from_proto_all
+            }
+
+            #proto_map_any
+
+            #proto_map_field_mask
+
+            #proto_map_merge
+        }
+    }
+
+    /// Implementation of `ProtoMapAny` for `any` attributed structs that packs the proto struct
+    /// to `google.protobuf.Any` messages.
+    fn implement_proto_map_any(&self) -> TokenStream {
+        if !self.attrs.is_any() {
+            return quote! {};
+        }
+
+        let struct_name = format_ident!("{}", &self.name);
+        let proto_struct = &self.attrs.source;
+        let type_name = match &self.attrs.type_name {
+            Some(type_name) => quote! { String::from(#type_name) },
+            None => quote! { proto_mapper::any::full_name::<#proto_struct>() },
+        };
+
+        quote! {
+            impl ProtoMapAny for #struct_name {
+                fn type_name() -> String {
+                    #type_name
+                }
+
+                fn to_any(&self) -> proto_mapper::any::Any {
+                    proto_mapper::any::pack(Self::type_url(), &ProtoMap::to_proto(self))
+                }
+
+                fn into_any(self) -> proto_mapper::any::Any {
+                    proto_mapper::any::pack(Self::type_url(), &ProtoMap::into_proto(self))
+                }
+
+                fn from_any(any: proto_mapper::any::Any) -> std::result::Result<Self, ProtoMapError> {
+                    ProtoMap::from_proto(proto_mapper::any::unpack(any, &Self::type_name())?)
+                }
+            }
+        }
+    }
+
+    /// Implementation of `ProtoMapFieldMask` for `field_mask` attributed structs.
+    ///
+    /// The paths of the mask are the proto field names. Nested message fields merge the nested paths (optional
+    /// messages only if they are set), `one_of` fields are selected by the names of their members and every other
+    /// field is overwritten as a whole.
+    pub(crate) fn implement_proto_map_field_mask(&self) -> TokenStream {
+        if !self.attrs.field_mask {
+            return quote! {};
+        }
+
+        let struct_name = format_ident!("{}", &self.name);
+        let type_name = path_to_string(&self.attrs.source);
+        let fields: Vec<_> = self.fields.iter().filter(|f| !f.is_skipped()).collect();
+        let proto_fields = fields.iter().filter(|f| !f.is_one_of()).map(|f| f.proto_field_path_name());
+        let one_of_fields: Vec<_> = fields.iter().filter(|f| f.is_one_of()).map(|f| &f.name).collect();
+        let check_fields = if one_of_fields.is_empty() {
+            quote! { paths.check_fields(&[#(#proto_fields),*], #type_name)?; }
+        } else {
+            quote! {
+                let fields = [
+                    &[#(#proto_fields),*][..],
+                    #(proto_mapper::field_mask::OneOfField::one_of_members(&self.#one_of_fields),)*
+                ];
+                paths.check_fields(&fields.concat(), #type_name)?;
+            }
+        };
+
+        let merges = fields.iter().map(|f| {
+            let struct_field = &f.name;
+            let field = f.proto_field_path_name();
+            let value = f.implement_setter_value(&self.attrs, false);
+
+            if f.is_one_of() {
+                return quote! {
+                    if paths.one_of_selected(proto_mapper::field_mask::OneOfField::one_of_members(&self.#struct_field))? {
+                        self.#struct_field = #value;
+                    }
+                };
+            }
+
+            if !f.is_nested_message() {
+                return quote! {
+                    if let Some(paths) = paths.field(#field) {
+                        paths.check_all().map_err(|e| e.with_field(#field))?;
+                        self.#struct_field = #value;
+                    }
+                };
+            }
+
+            let proto_value = f.implement_nested_proto_value();
+            let merge = |target: TokenStream| {
+                quote! {
+                    ProtoMapFieldMask::merge_from_proto_paths(#target, #proto_value, paths)
+                        .map_err(|e| e.with_field(#field))?
+                }
+            };
+            if f.ty.is_optional() {
+                let merge = merge(quote! { value });
+                quote! {
+                    if let Some(paths) = paths.field(#field) {
+                        match &mut self.#struct_field {
+                            Some(value) if !paths.is_all() => #merge,
+                            _ => self.#struct_field = #value,
+                        }
+                    }
+                }
+            } else {
+                let merge = merge(quote! { &mut self.#struct_field });
+                quote! {
+                    if let Some(paths) = paths.field(#field) {
+                        if paths.is_all() {
+                            self.#struct_field = #value;
+                        } else {
+                            #merge;
+                        }
+                    }
+                }
+            }
+        });
+
+        let diffs = fields.iter().map(|f| {
+            let struct_field = &f.name;
+            let field = f.proto_field_path_name();
+            let path = quote! { proto_mapper::field_mask::join_path(prefix, #field) };
+
+            if f.is_one_of() {
+                quote! {
+                    proto_mapper::field_mask::push_one_of_paths(&a.#struct_field, &b.#struct_field, prefix, paths);
+                }
+            } else if !f.is_nested_message() {
+                quote! {
+                    if a.#struct_field != b.#struct_field {
+                        paths.push(#path);
+                    }
+                }
+            } else if f.ty.is_optional() {
+                quote! {
+                    match (&a.#struct_field, &b.#struct_field) {
+                        (Some(a), Some(b)) => ProtoMapFieldMask::push_field_mask_paths(a, b, &#path, paths),
+                        (None, None) => {}
+                        _ => paths.push(#path),
+                    }
+                }
+            } else {
+                quote! {
+                    ProtoMapFieldMask::push_field_mask_paths(&a.#struct_field, &b.#struct_field, &#path, paths);
+                }
+            }
+        });
+
+        quote! {
+            impl ProtoMapFieldMask for #struct_name {
+                fn merge_from_proto_paths(
+                    &mut self,
+                    proto: Self::ProtoStruct,
+                    paths: &proto_mapper::field_mask::FieldMaskPaths,
+                ) -> std::result::Result<(), ProtoMapError> {
+                    let _guard = RecursionGuard::enter()?;
+                    #check_fields
+                    #(#merges)*
+                    Ok(())
+                }
+
+                fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+                    #(#diffs)*
+                }
+            }
+        }
+    }
+
+    /// Implementation of `ProtoMapMerge` for `merge` attributed structs that merges every field of the proto
+    /// message with the protobuf merge rules.
+    pub(crate) fn implement_proto_map_merge(&self) -> TokenStream {
+        if !self.attrs.merge {
+            return quote! {};
+        }
+
+        let struct_name = format_ident!("{}", &self.name);
+        let mergers = self.fields.iter().map(|f| f.implement_merger(&self.attrs));
+
+        quote! {
+            impl ProtoMapMerge for #struct_name {
+                fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> std::result::Result<(), ProtoMapError> {
+                    let _guard = RecursionGuard::enter()?;
+                    #(#mergers)*
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Implementation of `try_from_proto_all` that converts every field and collects all the errors.
+    ///
+    /// Structs without any converted fields (e.g. all fields skipped) use the default trait implementation.
+    fn implement_try_from_proto_all(&self) -> TokenStream {
+        let Some(from_proto_all) = implement_from_proto_all(&quote! { Self }, &self.fields, &self.attrs) else {
+            return quote! {};
+        };
+
+        // Note: `Ok(value?)` of the collectors converts `ProtoMapError` of the fields to `ProtoMapErrors`
+        quote! {
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                #from_proto_all
             }
         }
     }
 }
+
+/// Implementation of the conversion of the `proto` fields to the `constructor` (e.g. `Self` or a variant with named
+/// fields) that converts every field and collects all the errors.
+///
+/// Returns `None` if there are no converted fields (e.g. all fields skipped).
+pub(crate) fn implement_from_proto_all(
+    constructor: &TokenStream,
+    fields: &[StructField],
+    attrs: &StructAttrs,
+) -> Option<TokenStream> {
+    let converted: Vec<_> = fields.iter().filter(|f| !f.is_skipped()).collect();
+    if converted.is_empty() {
+        return None;
+    }
+
+    let values: Vec<_> = converted
+        .iter()
+        .map(|f| format_ident!("field_{}", f.name.unraw()))
+        .collect();
+
+    let collectors = converted.iter().zip(&values).map(|(f, value)| {
+        let setter_value = f.implement_setter_value(attrs, true);
+        quote! {
+            let #value = errors.collect(|| Ok(#setter_value));
+        }
+    });
+
+    let setters = fields.iter().map(|f| {
+        let struct_field = &f.name;
+        if f.is_skipped() {
+            quote! { #struct_field: Default::default(), }
+        } else {
+            let value = format_ident!("field_{}", f.name.unraw());
+            quote! { #struct_field: #value, }
+        }
+    });
+
+    Some(quote! {
+        let mut errors = ProtoMapErrors::default();
+        #(#collectors)*
+        match (#(#values,)*) {
+            (#(Some(#values),)*) => Ok(#constructor {
+                #(#setters)*
+            }),
+            _ => Err(errors),
+        }
+    })
+}
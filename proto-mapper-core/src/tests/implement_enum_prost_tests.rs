@@ -17,7 +17,7 @@ fn implement_enumeration_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let e = from_derive_input_enum(&input).unwrap();
 
     let expected = quote! {
@@ -31,12 +31,155 @@ fn implement_enumeration_test() {
                 }
             }
 
-            fn from_scalar(proto: i32) -> std::result::Result<Self, anyhow::Error> {
+            fn from_scalar(proto: i32) -> std::result::Result<Self, ProtoMapError> {
                  match proto {
                     _ if proto == proto::prost::EntityStatus::StatusA as i32 => Ok(Self::StatusA),
                     _ if proto == proto::prost::EntityStatus::StatusB as i32 => Ok(Self::StatusB),
                     _ if proto == proto::prost::EntityStatus::StatusC as i32 => Ok(Self::StatusC),
-                    _ => Err(anyhow::anyhow!(format!(stringify!(Failed to match enum value {} to proto entity EntityStatus) ,proto)))
+                    _ => Err(ProtoMapError::unknown_enum_value("proto::prost::EntityStatus", proto))
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_enumeration_unknown_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::prost::EntityStatus",
+            enumeration,
+        )]
+        enum EntityStatus {
+            StatusA,
+            StatusB,
+            #[proto_map(unknown)]
+            Unrecognized(i32),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapScalar<i32> for EntityStatus {
+
+            fn to_scalar(&self) -> i32 {
+                 match self {
+                    Self::StatusA => proto::prost::EntityStatus::StatusA.into(),
+                    Self::StatusB => proto::prost::EntityStatus::StatusB.into(),
+                    Self::Unrecognized(value) => *value,
+                }
+            }
+
+            fn from_scalar(proto: i32) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                    _ if proto == proto::prost::EntityStatus::StatusA as i32 => Ok(Self::StatusA),
+                    _ if proto == proto::prost::EntityStatus::StatusB as i32 => Ok(Self::StatusB),
+                    _ => Ok(Self::Unrecognized(proto))
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_enumeration_variant_attributes_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::prost::EntityState",
+            enumeration,
+        )]
+        enum EntityState {
+            #[proto_map(rename = "Unspecified")]
+            Unknown,
+            #[proto_map(alias = "Enabled", alias = "LegacyActive")]
+            Active,
+            #[proto_map(value = 3)]
+            Archived,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapScalar<i32> for EntityState {
+
+            fn to_scalar(&self) -> i32 {
+                 match self {
+                    Self::Unknown => proto::prost::EntityState::Unspecified.into(),
+                    Self::Active => proto::prost::EntityState::Active.into(),
+                    Self::Archived => 3i32,
+                }
+            }
+
+            fn from_scalar(proto: i32) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                    _ if proto == proto::prost::EntityState::Unspecified as i32 => Ok(Self::Unknown),
+                    _ if proto == proto::prost::EntityState::Active as i32 => Ok(Self::Active),
+                    _ if proto == proto::prost::EntityState::Enabled as i32 => Ok(Self::Active),
+                    _ if proto == proto::prost::EntityState::LegacyActive as i32 => Ok(Self::Active),
+                    _ if proto == 3i32 => Ok(Self::Archived),
+                    _ => Err(ProtoMapError::unknown_enum_value("proto::prost::EntityState", proto))
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_enumeration_auto_naming_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::prost::EntityState",
+            enumeration,
+            rename_variants = "auto",
+            add_prefix = "ENTITY_STATE_"
+        )]
+        enum EntityState {
+            Active,
+            #[proto_map(alias = "ENTITY_STATE_LEGACY_ACTIVE")]
+            Enabled,
+            #[proto_map(rename = "SELF")]
+            Myself,
+            #[proto_map(rename = "ENTITY_STATEFUL")]
+            Stateful,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapScalar<i32> for EntityState {
+
+            fn to_scalar(&self) -> i32 {
+                 match self {
+                    Self::Active => proto::prost::EntityState::Active.into(),
+                    Self::Enabled => proto::prost::EntityState::Enabled.into(),
+                    Self::Myself => proto::prost::EntityState::Self_.into(),
+                    Self::Stateful => proto::prost::EntityState::EntityStateful.into(),
+                }
+            }
+
+            fn from_scalar(proto: i32) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                    _ if proto == proto::prost::EntityState::Active as i32 => Ok(Self::Active),
+                    _ if proto == proto::prost::EntityState::Enabled as i32 => Ok(Self::Enabled),
+                    _ if proto == proto::prost::EntityState::LegacyActive as i32 => Ok(Self::Enabled),
+                    _ if proto == proto::prost::EntityState::Self_ as i32 => Ok(Self::Myself),
+                    _ if proto == proto::prost::EntityState::EntityStateful as i32 => Ok(Self::Stateful),
+                    _ => Err(ProtoMapError::unknown_enum_value("proto::prost::EntityState", proto))
                 }
             }
         }
@@ -60,7 +203,7 @@ fn implement_non_enumeration_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let e = from_derive_input_enum(&input).unwrap();
 
     let expected = quote! {
@@ -69,20 +212,606 @@ fn implement_non_enumeration_test() {
 
             fn to_proto(&self) -> Self::ProtoStruct {
                 let mut inner = Self::ProtoStruct::default();
+                inner.data = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.data = ProtoMapOneOf::into_one_of(self);
+                inner
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.data)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.data)
+            }
+        }
+
+        impl ProtoMapOneOf for HierarchyEntity {
+            type ProtoOneOf = proto::hierarchy_entity::Data;
+
+            const MEMBERS: &'static [&'static str] = &["first_entity", "second_entity"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::FirstEntity(_) => Some("first_entity"),
+                    Self::SecondEntity(_) => Some("second_entity"),
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::FirstEntity(value) => Some(proto::hierarchy_entity::Data::FirstEntity(value.to_proto())),
+                    Self::SecondEntity(value) => Some(proto::hierarchy_entity::Data::SecondEntity(value.to_proto())),
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::FirstEntity(value) => Some(proto::hierarchy_entity::Data::FirstEntity(value.into_proto())),
+                    Self::SecondEntity(value) => Some(proto::hierarchy_entity::Data::SecondEntity(value.into_proto())),
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::hierarchy_entity::Data::FirstEntity(value)) => {
+                        Entity::from_proto(value)
+                            .map(Self::FirstEntity)
+                            .map_err(|e| e.with_field("first_entity"))
+                    }
+                    Some(proto::hierarchy_entity::Data::SecondEntity(value)) => {
+                        NestedEntity::from_proto(value)
+                            .map(Self::SecondEntity)
+                            .map_err(|e| e.with_field("second_entity"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::HierarchyEntity").with_field("data")),
+                }
+            }
+
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::hierarchy_entity::Data::FirstEntity(value)) => {
+                        Entity::try_from_proto_all(value)
+                            .map(Self::FirstEntity)
+                            .map_err(|e| e.with_field("first_entity"))
+                    }
+                    Some(proto::hierarchy_entity::Data::SecondEntity(value)) => {
+                        NestedEntity::try_from_proto_all(value)
+                            .map(Self::SecondEntity)
+                            .map_err(|e| e.with_field("second_entity"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::HierarchyEntity").with_field("data").into()),
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_one_of_payloads_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::IdentityEntity", one_of(field = "id"))]
+        enum Identity {
+            Email(String),
+            Anonymous,
+            #[proto_map(enumeration)]
+            Status(EntityStatus),
+            #[proto_map(scalar, with = "uuid_as_string")]
+            Uuid(Uuid),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Identity {
+            type ProtoStruct = proto::IdentityEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.id = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.id = ProtoMapOneOf::into_one_of(self);
+                inner
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.id)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.id)
+            }
+        }
+
+        impl ProtoMapOneOf for Identity {
+            type ProtoOneOf = proto::identity_entity::Id;
+
+            const MEMBERS: &'static [&'static str] = &["email", "anonymous", "status", "uuid"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::Email(_) => Some("email"),
+                    Self::Anonymous => Some("anonymous"),
+                    Self::Status(_) => Some("status"),
+                    Self::Uuid(_) => Some("uuid"),
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Email(value) => Some(proto::identity_entity::Id::Email(ProtoMapScalar::to_scalar(value))),
+                    Self::Anonymous => Some(proto::identity_entity::Id::Anonymous(Default::default())),
+                    Self::Status(value) => Some(proto::identity_entity::Id::Status(ProtoMapScalar::to_scalar(value))),
+                    Self::Uuid(value) => Some(proto::identity_entity::Id::Uuid(uuid_as_string::to_scalar(value))),
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Email(value) => Some(proto::identity_entity::Id::Email(ProtoMapScalar::into_scalar(value))),
+                    Self::Anonymous => Some(proto::identity_entity::Id::Anonymous(Default::default())),
+                    Self::Status(value) => Some(proto::identity_entity::Id::Status(ProtoMapScalar::into_scalar(value))),
+                    Self::Uuid(value) => Some(proto::identity_entity::Id::Uuid(uuid_as_string::to_scalar(&value))),
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::identity_entity::Id::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                    }
+                    Some(proto::identity_entity::Id::Anonymous(_)) => Ok(Self::Anonymous),
+                    Some(proto::identity_entity::Id::Status(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Status)
+                            .map_err(|e| e.with_field("status"))
+                    }
+                    Some(proto::identity_entity::Id::Uuid(value)) => {
+                        uuid_as_string::from_scalar(value)
+                            .map(Self::Uuid)
+                            .map_err(|e| ProtoMapError::from(e).with_field("uuid"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::IdentityEntity").with_field("id")),
+                }
+            }
+
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::identity_entity::Id::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    Some(proto::identity_entity::Id::Anonymous(_)) => Ok(Self::Anonymous),
+                    Some(proto::identity_entity::Id::Status(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Status)
+                            .map_err(|e| e.with_field("status"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    Some(proto::identity_entity::Id::Uuid(value)) => {
+                        uuid_as_string::from_scalar(value)
+                            .map(Self::Uuid)
+                            .map_err(|e| ProtoMapError::from(e).with_field("uuid"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::IdentityEntity").with_field("id").into()),
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_one_of_not_set_variant_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "variant"))]
+        enum Contact {
+            Email(String),
+            #[proto_map(not_set)]
+            NotSet,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Contact {
+            type ProtoStruct = proto::ContactEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.contact = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.contact = ProtoMapOneOf::into_one_of(self);
+                inner
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.contact)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.contact)
+            }
+        }
+
+        impl ProtoMapOneOf for Contact {
+            type ProtoOneOf = proto::contact_entity::Contact;
+
+            const MEMBERS: &'static [&'static str] = &["email"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::Email(_) => Some("email"),
+                    Self::NotSet => None,
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Email(value) => Some(proto::contact_entity::Contact::Email(ProtoMapScalar::to_scalar(value))),
+                    Self::NotSet => None,
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
                 match self {
-                    Self::FirstEntity(value) => inner.data = Some(proto::hierarchy_entity::Data::FirstEntity(value.to_proto())),
-                    Self::SecondEntity(value) => inner.data = Some(proto::hierarchy_entity::Data::SecondEntity(value.to_proto())),
+                    Self::Email(value) => Some(proto::contact_entity::Contact::Email(ProtoMapScalar::into_scalar(value))),
+                    Self::NotSet => None,
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::contact_entity::Contact::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                    }
+                    None => Ok(Self::NotSet),
+                }
+            }
+
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::contact_entity::Contact::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    None => Ok(Self::NotSet),
                 }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_one_of_named_variants_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::ShapeEntity", one_of(field = "shape"))]
+        enum Shape {
+            Circle { radius: f64 },
+            Labeled {
+                #[proto_map(rename = "name")]
+                label: String,
+                #[proto_map(skip)]
+                cached: bool,
+            },
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Shape {
+            type ProtoStruct = proto::ShapeEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.shape = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::default();
+                inner.shape = ProtoMapOneOf::into_one_of(self);
                 inner
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
-                match proto.data {
-                   Some(proto::hierarchy_entity::Data::FirstEntity(value)) => { Entity::from_proto(value)
-                    .map(Self::FirstEntity) }
-                    Some(proto::hierarchy_entity::Data::SecondEntity(value)) => { NestedEntity::from_proto(value)
-                    .map(Self::SecondEntity) }
-                    _ => Err(anyhow::anyhow!(stringify!(Failed to decode enum HierarchyEntity from proto entity)))
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.shape)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.shape)
+            }
+        }
+
+        impl ProtoMapOneOf for Shape {
+            type ProtoOneOf = proto::shape_entity::Shape;
+
+            const MEMBERS: &'static [&'static str] = &["circle", "labeled"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::Circle { .. } => Some("circle"),
+                    Self::Labeled { .. } => Some("labeled"),
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Circle { radius, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Circle, |proto| {
+                        proto.radius = ProtoMapScalar::to_scalar(&(*radius));
+                    })),
+                    Self::Labeled { label, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Labeled, |proto| {
+                        proto.name = ProtoMapScalar::to_scalar(&(*label));
+                    })),
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Circle { radius, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Circle, |proto| {
+                        proto.radius = ProtoMapScalar::into_scalar(radius);
+                    })),
+                    Self::Labeled { label, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Labeled, |proto| {
+                        proto.name = ProtoMapScalar::into_scalar(label);
+                    })),
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::shape_entity::Shape::Circle(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapError> {
+                            Ok(Self::Circle {
+                                radius: ProtoMapScalar::from_scalar(proto.radius).map_err(|e| e.with_field("radius"))?,
+                            })
+                        })
+                        .map_err(|e| e.with_field("circle"))
+                    }
+                    Some(proto::shape_entity::Shape::Labeled(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapError> {
+                            Ok(Self::Labeled {
+                                label: ProtoMapScalar::from_scalar(proto.name).map_err(|e| e.with_field("name"))?,
+                                cached: Default::default(),
+                            })
+                        })
+                        .map_err(|e| e.with_field("labeled"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::ShapeEntity").with_field("shape")),
+                }
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::shape_entity::Shape::Circle(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapErrors> {
+                            let mut errors = ProtoMapErrors::default();
+                            let field_radius = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.radius).map_err(|e| e.with_field("radius"))?));
+                            match (field_radius,) {
+                                (Some(field_radius),) => Ok(Self::Circle {
+                                    radius: field_radius,
+                                }),
+                                _ => Err(errors),
+                            }
+                        })
+                        .map_err(|e| e.with_field("circle"))
+                    }
+                    Some(proto::shape_entity::Shape::Labeled(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapErrors> {
+                            let mut errors = ProtoMapErrors::default();
+                            let field_label = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.name).map_err(|e| e.with_field("name"))?));
+                            match (field_label,) {
+                                (Some(field_label),) => Ok(Self::Labeled {
+                                    label: field_label,
+                                    cached: Default::default(),
+                                }),
+                                _ => Err(errors),
+                            }
+                        })
+                        .map_err(|e| e.with_field("labeled"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::ShapeEntity").with_field("shape").into()),
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_any_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto_mapper::any::Any", any)]
+        enum Event {
+            Created(EventCreated),
+            Deleted(EventDeleted),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Event {
+            type ProtoStruct = proto_mapper::any::Any;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                match self {
+                    Self::Created(value) => ProtoMapAny::to_any(value),
+                    Self::Deleted(value) => ProtoMapAny::to_any(value),
+                }
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                match self {
+                    Self::Created(value) => ProtoMapAny::into_any(value),
+                    Self::Deleted(value) => ProtoMapAny::into_any(value),
+                }
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let type_name = proto_mapper::any::type_name_of_url(&proto.type_url).to_owned();
+                if type_name == <EventCreated as ProtoMapAny>::type_name() {
+                    return <EventCreated as ProtoMapAny>::from_any(proto).map(Self::Created);
+                }
+                if type_name == <EventDeleted as ProtoMapAny>::type_name() {
+                    return <EventDeleted as ProtoMapAny>::from_any(proto).map(Self::Deleted);
+                }
+                Err(ProtoMapError::unknown_type_url(proto.type_url))
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_tagged_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+        enum Event {
+            #[proto_map(tag = "proto::EventKind::Created")]
+            Created(EventCreated),
+            #[proto_map(tag = "proto::EventKind::Moved")]
+            Moved {
+                x: f64,
+                #[proto_map(skip)]
+                cached: bool,
+            },
+            #[proto_map(other)]
+            Other,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Event {
+            type ProtoStruct = proto::TaggedEventEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = Self::ProtoStruct::default();
+                match self {
+                    Self::Created(created) => {
+                        proto.kind = proto::EventKind::Created as i32;
+                        proto.created = Some(ProtoMap::to_proto(&(*created)));
+                    }
+                    Self::Moved { x, .. } => {
+                        proto.kind = proto::EventKind::Moved as i32;
+                        proto.x = ProtoMapScalar::to_scalar(&(*x));
+                    }
+                    Self::Other => {}
+                }
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = Self::ProtoStruct::default();
+                match self {
+                    Self::Created(created) => {
+                        proto.kind = proto::EventKind::Created as i32;
+                        proto.created = Some(ProtoMap::into_proto(created));
+                    }
+                    Self::Moved { x, .. } => {
+                        proto.kind = proto::EventKind::Moved as i32;
+                        proto.x = ProtoMapScalar::into_scalar(x);
+                    }
+                    Self::Other => {}
+                }
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let tag = proto.kind;
+                match tag {
+                    _ if tag == proto::EventKind::Created as i32 && proto.created.is_some() => Ok(Self::Created({
+                            if let Some(value) = proto.created {
+                                ProtoMap::from_proto(value).map_err(|e| e.with_field("created"))?
+                            } else {
+                                return Err(ProtoMapError::missing_message("proto::TaggedEventEntity").with_field("created"))
+                            }
+                        })),
+                    _ if tag == proto::EventKind::Moved as i32 && !proto.created.is_some() => Ok(Self::Moved {
+                        x: ProtoMapScalar::from_scalar(proto.x).map_err(|e| e.with_field("x"))?,
+                        cached: Default::default(),
+                    }),
+                    _ => Ok(Self::Other),
+                }
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let tag = proto.kind;
+                match tag {
+                    _ if tag == proto::EventKind::Created as i32 && proto.created.is_some() => Ok(Self::Created({
+                            if let Some(value) = proto.created {
+                                ProtoMap::try_from_proto_all(value).map_err(|e| e.with_field("created"))?
+                            } else {
+                                return Err(ProtoMapError::missing_message("proto::TaggedEventEntity").with_field("created").into())
+                            }
+                        })),
+                    _ if tag == proto::EventKind::Moved as i32 && !proto.created.is_some() => {
+                        let mut errors = ProtoMapErrors::default();
+                        let field_x = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.x).map_err(|e| e.with_field("x"))?));
+                        match (field_x,) {
+                            (Some(field_x),) => Ok(Self::Moved {
+                                x: field_x,
+                                cached: Default::default(),
+                            }),
+                            _ => Err(errors),
+                        }
+                    },
+                    _ => Ok(Self::Other),
                 }
             }
         }
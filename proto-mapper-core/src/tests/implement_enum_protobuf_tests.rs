@@ -18,7 +18,7 @@ fn implement_enumeration_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let e = from_derive_input_enum(&input).unwrap();
 
     let expected = quote! {
@@ -34,7 +34,7 @@ fn implement_enumeration_test() {
                 }
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
                  match proto {
                         proto::EntityStatus::STATUS_A => Ok(Self::StatusA),
                         proto::EntityStatus::STATUS_B => Ok(Self::StatusB),
@@ -42,11 +42,234 @@ fn implement_enumeration_test() {
                     }
             }
         }
+
+        impl ProtoMapEnum for EntityStatus {}
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+#[test]
+fn implement_enumeration_unknown_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+            rename_variants = "STREAMING_SNAKE_CASE"
+        )]
+        enum EntityStatus {
+            StatusA,
+            StatusB,
+            #[proto_map(unknown)]
+            Unrecognized(i32),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for EntityStatus {
+            type ProtoStruct = proto::EntityStatus;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                 match self {
+                    Self::StatusA => proto::EntityStatus::STATUS_A,
+                    Self::StatusB => proto::EntityStatus::STATUS_B,
+                    Self::Unrecognized(value) => ::protobuf::EnumOrUnknown::<proto::EntityStatus>::from_i32(*value).enum_value_or_default(),
+                }
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                        proto::EntityStatus::STATUS_A => Ok(Self::StatusA),
+                        proto::EntityStatus::STATUS_B => Ok(Self::StatusB),
+                    }
+            }
+        }
+
+        impl ProtoMapEnum for EntityStatus {
+            fn to_enum_or_unknown(&self) -> ::protobuf::EnumOrUnknown<Self::ProtoStruct> {
+                match self {
+                    Self::Unrecognized(value) => ::protobuf::EnumOrUnknown::from_i32(*value),
+                    _ => ::protobuf::EnumOrUnknown::new(ProtoMap::to_proto(self)),
+                }
+            }
+
+            fn from_enum_or_unknown(proto: ::protobuf::EnumOrUnknown<Self::ProtoStruct>) -> std::result::Result<Self, ProtoMapError> {
+                match proto.enum_value() {
+                    Ok(value) => ProtoMap::from_proto(value),
+                    Err(value) => Ok(Self::Unrecognized(value)),
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_enumeration_other_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+            rename_variants = "STREAMING_SNAKE_CASE"
+        )]
+        enum EntityStatus {
+            StatusA,
+            #[proto_map(other)]
+            StatusB,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for EntityStatus {
+            type ProtoStruct = proto::EntityStatus;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                 match self {
+                    Self::StatusA => proto::EntityStatus::STATUS_A,
+                    Self::StatusB => proto::EntityStatus::STATUS_B,
+                }
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                        proto::EntityStatus::STATUS_A => Ok(Self::StatusA),
+                        proto::EntityStatus::STATUS_B => Ok(Self::StatusB),
+                    }
+            }
+        }
+
+        impl ProtoMapEnum for EntityStatus {
+            fn from_enum_or_unknown(proto: ::protobuf::EnumOrUnknown<Self::ProtoStruct>) -> std::result::Result<Self, ProtoMapError> {
+                match proto.enum_value() {
+                    Ok(value) => ProtoMap::from_proto(value),
+                    Err(_) => Ok(Self::StatusB),
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_enumeration_variant_attributes_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityState",
+            enumeration,
+        )]
+        enum EntityState {
+            #[proto_map(rename = "ENTITY_STATE_UNSPECIFIED")]
+            Unknown,
+            #[proto_map(rename = "ENTITY_STATE_ACTIVE", alias = "ENTITY_STATE_ENABLED")]
+            Active,
+            #[proto_map(value = 3)]
+            Archived,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for EntityState {
+            type ProtoStruct = proto::EntityState;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                 match self {
+                    Self::Unknown => proto::EntityState::ENTITY_STATE_UNSPECIFIED,
+                    Self::Active => proto::EntityState::ENTITY_STATE_ACTIVE,
+                    Self::Archived => <proto::EntityState as ::protobuf::Enum>::from_i32(3i32).unwrap_or_default(),
+                }
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                    _ if proto == proto::EntityState::ENTITY_STATE_UNSPECIFIED => Ok(Self::Unknown),
+                    _ if proto == proto::EntityState::ENTITY_STATE_ACTIVE => Ok(Self::Active),
+                    _ if proto == proto::EntityState::ENTITY_STATE_ENABLED => Ok(Self::Active),
+                    _ if ::protobuf::Enum::value(&proto) == 3i32 => Ok(Self::Archived),
+                    _ => Err(ProtoMapError::unknown_enum_value("proto::EntityState", ::protobuf::Enum::value(&proto)))
+                }
+            }
+        }
+
+        impl ProtoMapEnum for EntityState {
+            fn to_enum_or_unknown(&self) -> ::protobuf::EnumOrUnknown<Self::ProtoStruct> {
+                match self {
+                    Self::Archived => ::protobuf::EnumOrUnknown::from_i32(3i32),
+                    _ => ::protobuf::EnumOrUnknown::new(ProtoMap::to_proto(self)),
+                }
+            }
+
+            fn from_enum_or_unknown(proto: ::protobuf::EnumOrUnknown<Self::ProtoStruct>) -> std::result::Result<Self, ProtoMapError> {
+                match proto.enum_value() {
+                    Ok(value) => ProtoMap::from_proto(value),
+                    Err(3i32) => Ok(Self::Archived),
+                    Err(value) => Err(ProtoMapError::unknown_enum_value(<Self::ProtoStruct as ::protobuf::Enum>::NAME, value)),
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_enumeration_auto_naming_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityType",
+            enumeration,
+            rename_variants = "auto",
+            strip_prefix = "Kind",
+            add_prefix = "TYPE_"
+        )]
+        enum EntityKind {
+            KindA,
+            KindB,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for EntityKind {
+            type ProtoStruct = proto::EntityType;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                 match self {
+                    Self::KindA => proto::EntityType::TYPE_A,
+                    Self::KindB => proto::EntityType::TYPE_B,
+                }
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                 match proto {
+                        proto::EntityType::TYPE_A => Ok(Self::KindA),
+                        proto::EntityType::TYPE_B => Ok(Self::KindB),
+                    }
+            }
+        }
+
+        impl ProtoMapEnum for EntityKind {}
     };
 
     let actual = e.implement_proto_map();
     assert_tokens_eq(&expected, &actual)
 }
+
 #[test]
 fn implement_non_enumeration_test() {
     let fragment = quote! {
@@ -61,7 +284,7 @@ fn implement_non_enumeration_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let e = from_derive_input_enum(&input).unwrap();
 
     let expected = quote! {
@@ -70,20 +293,620 @@ fn implement_non_enumeration_test() {
 
             fn to_proto(&self) -> Self::ProtoStruct {
                 let mut inner = Self::ProtoStruct::new();
+                inner.data = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.data = ProtoMapOneOf::into_one_of(self);
+                inner
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.data)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.data)
+            }
+        }
+
+        impl ProtoMapOneOf for HierarchyEntity {
+            type ProtoOneOf = proto::hierarchy_entity::Data;
+
+            const MEMBERS: &'static [&'static str] = &["first_entity", "second_entity"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::FirstEntity(_) => Some("first_entity"),
+                    Self::SecondEntity(_) => Some("second_entity"),
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::FirstEntity(value) => Some(proto::hierarchy_entity::Data::FirstEntity(value.to_proto())),
+                    Self::SecondEntity(value) => Some(proto::hierarchy_entity::Data::SecondEntity(value.to_proto())),
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::FirstEntity(value) => Some(proto::hierarchy_entity::Data::FirstEntity(value.into_proto())),
+                    Self::SecondEntity(value) => Some(proto::hierarchy_entity::Data::SecondEntity(value.into_proto())),
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::hierarchy_entity::Data::FirstEntity(value)) => {
+                        Entity::from_proto(value)
+                            .map(Self::FirstEntity)
+                            .map_err(|e| e.with_field("first_entity"))
+                    }
+                    Some(proto::hierarchy_entity::Data::SecondEntity(value)) => {
+                        NestedEntity::from_proto(value)
+                            .map(Self::SecondEntity)
+                            .map_err(|e| e.with_field("second_entity"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::HierarchyEntity").with_field("data")),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::HierarchyEntity").with_field("data"))
+                }
+            }
+
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::hierarchy_entity::Data::FirstEntity(value)) => {
+                        Entity::try_from_proto_all(value)
+                            .map(Self::FirstEntity)
+                            .map_err(|e| e.with_field("first_entity"))
+                    }
+                    Some(proto::hierarchy_entity::Data::SecondEntity(value)) => {
+                        NestedEntity::try_from_proto_all(value)
+                            .map(Self::SecondEntity)
+                            .map_err(|e| e.with_field("second_entity"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::HierarchyEntity").with_field("data").into()),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::HierarchyEntity").with_field("data").into())
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_one_of_payloads_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::IdentityEntity", one_of(field = "id"))]
+        enum Identity {
+            Email(String),
+            Anonymous,
+            #[proto_map(enumeration)]
+            Status(EntityStatus),
+            #[proto_map(scalar, with = "uuid_as_string")]
+            Uuid(Uuid),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Identity {
+            type ProtoStruct = proto::IdentityEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.id = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.id = ProtoMapOneOf::into_one_of(self);
+                inner
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.id)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.id)
+            }
+        }
+
+        impl ProtoMapOneOf for Identity {
+            type ProtoOneOf = proto::identity_entity::Id;
+
+            const MEMBERS: &'static [&'static str] = &["email", "anonymous", "status", "uuid"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::Email(_) => Some("email"),
+                    Self::Anonymous => Some("anonymous"),
+                    Self::Status(_) => Some("status"),
+                    Self::Uuid(_) => Some("uuid"),
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Email(value) => Some(proto::identity_entity::Id::Email(ProtoMapScalar::to_scalar(value))),
+                    Self::Anonymous => Some(proto::identity_entity::Id::Anonymous(Default::default())),
+                    Self::Status(value) => Some(proto::identity_entity::Id::Status(ProtoMapEnum::to_enum_or_unknown(value))),
+                    Self::Uuid(value) => Some(proto::identity_entity::Id::Uuid(uuid_as_string::to_scalar(value))),
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Email(value) => Some(proto::identity_entity::Id::Email(ProtoMapScalar::into_scalar(value))),
+                    Self::Anonymous => Some(proto::identity_entity::Id::Anonymous(Default::default())),
+                    Self::Status(value) => Some(proto::identity_entity::Id::Status(ProtoMapEnum::into_enum_or_unknown(value))),
+                    Self::Uuid(value) => Some(proto::identity_entity::Id::Uuid(uuid_as_string::to_scalar(&value))),
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::identity_entity::Id::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                    }
+                    Some(proto::identity_entity::Id::Anonymous(_)) => Ok(Self::Anonymous),
+                    Some(proto::identity_entity::Id::Status(value)) => {
+                        ProtoMapEnum::from_enum_or_unknown(value)
+                            .map(Self::Status)
+                            .map_err(|e| e.with_field("status"))
+                    }
+                    Some(proto::identity_entity::Id::Uuid(value)) => {
+                        uuid_as_string::from_scalar(value)
+                            .map(Self::Uuid)
+                            .map_err(|e| ProtoMapError::from(e).with_field("uuid"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::IdentityEntity").with_field("id")),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::IdentityEntity").with_field("id"))
+                }
+            }
+
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::identity_entity::Id::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    Some(proto::identity_entity::Id::Anonymous(_)) => Ok(Self::Anonymous),
+                    Some(proto::identity_entity::Id::Status(value)) => {
+                        ProtoMapEnum::from_enum_or_unknown(value)
+                            .map(Self::Status)
+                            .map_err(|e| e.with_field("status"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    Some(proto::identity_entity::Id::Uuid(value)) => {
+                        uuid_as_string::from_scalar(value)
+                            .map(Self::Uuid)
+                            .map_err(|e| ProtoMapError::from(e).with_field("uuid"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::IdentityEntity").with_field("id").into()),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::IdentityEntity").with_field("id").into())
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_one_of_not_set_variant_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "variant"))]
+        enum Contact {
+            Email(String),
+            #[proto_map(not_set)]
+            NotSet,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Contact {
+            type ProtoStruct = proto::ContactEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.contact = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.contact = ProtoMapOneOf::into_one_of(self);
+                inner
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.contact)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.contact)
+            }
+        }
+
+        impl ProtoMapOneOf for Contact {
+            type ProtoOneOf = proto::contact_entity::Contact;
+
+            const MEMBERS: &'static [&'static str] = &["email"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::Email(_) => Some("email"),
+                    Self::NotSet => None,
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Email(value) => Some(proto::contact_entity::Contact::Email(ProtoMapScalar::to_scalar(value))),
+                    Self::NotSet => None,
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
                 match self {
-                    Self::FirstEntity(value) => inner.set_first_entity(value.to_proto()),
-                    Self::SecondEntity(value) => inner.set_second_entity(value.to_proto()),
+                    Self::Email(value) => Some(proto::contact_entity::Contact::Email(ProtoMapScalar::into_scalar(value))),
+                    Self::NotSet => None,
                 }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::contact_entity::Contact::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                    }
+                    None => Ok(Self::NotSet),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::ContactEntity").with_field("contact"))
+                }
+            }
+
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::contact_entity::Contact::Email(value)) => {
+                        ProtoMapScalar::from_scalar(value)
+                            .map(Self::Email)
+                            .map_err(|e| e.with_field("email"))
+                            .map_err(ProtoMapErrors::from)
+                    }
+                    None => Ok(Self::NotSet),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::ContactEntity").with_field("contact").into())
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_one_of_named_variants_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::ShapeEntity", one_of(field = "shape"))]
+        enum Shape {
+            Circle { radius: f64 },
+            Labeled {
+                #[proto_map(rename = "name")]
+                label: String,
+                #[proto_map(skip)]
+                cached: bool,
+            },
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Shape {
+            type ProtoStruct = proto::ShapeEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.shape = ProtoMapOneOf::to_one_of(self);
+                inner
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut inner = Self::ProtoStruct::new();
+                inner.shape = ProtoMapOneOf::into_one_of(self);
                 inner
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
-                match proto.data {
-                   Some(proto::hierarchy_entity::Data::FirstEntity(value)) => { Entity::from_proto(value)
-                    .map(Self::FirstEntity) }
-                    Some(proto::hierarchy_entity::Data::SecondEntity(value)) => { NestedEntity::from_proto(value)
-                    .map(Self::SecondEntity) }
-                    _ => Err(anyhow::anyhow!(stringify!(Failed to decode enum HierarchyEntity from proto entity)))
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMapOneOf::from_one_of(proto.shape)
+            }
+
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                ProtoMapOneOf::try_from_one_of_all(proto.shape)
+            }
+        }
+
+        impl ProtoMapOneOf for Shape {
+            type ProtoOneOf = proto::shape_entity::Shape;
+
+            const MEMBERS: &'static [&'static str] = &["circle", "labeled"];
+
+            fn one_of_member(&self) -> Option<&'static str> {
+                match self {
+                    Self::Circle { .. } => Some("circle"),
+                    Self::Labeled { .. } => Some("labeled"),
+                }
+            }
+
+            fn to_one_of(&self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Circle { radius, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Circle, |proto| {
+                        proto.set_radius(ProtoMapScalar::to_scalar(&(*radius)).into());
+                    })),
+                    Self::Labeled { label, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Labeled, |proto| {
+                        proto.set_name(ProtoMapScalar::to_scalar(&(*label)).into());
+                    })),
+                }
+            }
+
+            fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+                match self {
+                    Self::Circle { radius, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Circle, |proto| {
+                        proto.set_radius(ProtoMapScalar::into_scalar(radius).into());
+                    })),
+                    Self::Labeled { label, .. } => Some(proto_mapper::to_one_of_member(proto::shape_entity::Shape::Labeled, |proto| {
+                        proto.set_name(ProtoMapScalar::into_scalar(label).into());
+                    })),
+                }
+            }
+
+            fn from_one_of(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::shape_entity::Shape::Circle(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapError> {
+                            Ok(Self::Circle {
+                                radius: ProtoMapScalar::from_scalar(proto.radius().to_owned()).map_err(|e| e.with_field("radius"))?,
+                            })
+                        })
+                        .map_err(|e| e.with_field("circle"))
+                    }
+                    Some(proto::shape_entity::Shape::Labeled(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapError> {
+                            Ok(Self::Labeled {
+                                label: ProtoMapScalar::from_scalar(proto.name().to_owned()).map_err(|e| e.with_field("name"))?,
+                                cached: Default::default(),
+                            })
+                        })
+                        .map_err(|e| e.with_field("labeled"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::ShapeEntity").with_field("shape")),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::ShapeEntity").with_field("shape"))
+                }
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                match proto {
+                    Some(proto::shape_entity::Shape::Circle(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapErrors> {
+                            let mut errors = ProtoMapErrors::default();
+                            let field_radius = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.radius().to_owned()).map_err(|e| e.with_field("radius"))?));
+                            match (field_radius,) {
+                                (Some(field_radius),) => Ok(Self::Circle {
+                                    radius: field_radius,
+                                }),
+                                _ => Err(errors),
+                            }
+                        })
+                        .map_err(|e| e.with_field("circle"))
+                    }
+                    Some(proto::shape_entity::Shape::Labeled(proto)) => {
+                        proto_mapper::from_one_of_member(proto, |proto| -> std::result::Result<Self, ProtoMapErrors> {
+                            let mut errors = ProtoMapErrors::default();
+                            let field_label = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.name().to_owned()).map_err(|e| e.with_field("name"))?));
+                            match (field_label,) {
+                                (Some(field_label),) => Ok(Self::Labeled {
+                                    label: field_label,
+                                    cached: Default::default(),
+                                }),
+                                _ => Err(errors),
+                            }
+                        })
+                        .map_err(|e| e.with_field("labeled"))
+                    }
+                    None => Err(ProtoMapError::one_of_not_set("proto::ShapeEntity").with_field("shape").into()),
+                    #[allow(unreachable_patterns)]
+                    _ => Err(ProtoMapError::one_of_not_set("proto::ShapeEntity").with_field("shape").into())
+                }
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual)
+}
+
+#[test]
+fn implement_any_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto_mapper::any::Any", any)]
+        enum Event {
+            Created(EventCreated),
+            Deleted(EventDeleted),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Event {
+            type ProtoStruct = proto_mapper::any::Any;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                match self {
+                    Self::Created(value) => ProtoMapAny::to_any(value),
+                    Self::Deleted(value) => ProtoMapAny::to_any(value),
+                }
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                match self {
+                    Self::Created(value) => ProtoMapAny::into_any(value),
+                    Self::Deleted(value) => ProtoMapAny::into_any(value),
+                }
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let type_name = proto_mapper::any::type_name_of_url(&proto.type_url).to_owned();
+                if type_name == <EventCreated as ProtoMapAny>::type_name() {
+                    return <EventCreated as ProtoMapAny>::from_any(proto).map(Self::Created);
+                }
+                if type_name == <EventDeleted as ProtoMapAny>::type_name() {
+                    return <EventDeleted as ProtoMapAny>::from_any(proto).map(Self::Deleted);
+                }
+                Err(ProtoMapError::unknown_type_url(proto.type_url))
+            }
+        }
+    };
+
+    let actual = e.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_tagged_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+        enum Event {
+            #[proto_map(tag = "proto::EventKind::Created")]
+            Created(EventCreated),
+            #[proto_map(tag = "proto::EventKind::Moved")]
+            Moved {
+                x: f64,
+                #[proto_map(skip)]
+                cached: bool,
+            },
+            #[proto_map(other)]
+            Other,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Event {
+            type ProtoStruct = proto::TaggedEventEntity;
+
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = Self::ProtoStruct::new();
+                match self {
+                    Self::Created(created) => {
+                        proto.kind = ::protobuf::EnumOrUnknown::new(proto::EventKind::Created);
+                        proto.set_created(ProtoMap::to_proto(&(*created)).into());
+                    }
+                    Self::Moved { x, .. } => {
+                        proto.kind = ::protobuf::EnumOrUnknown::new(proto::EventKind::Moved);
+                        proto.set_x(ProtoMapScalar::to_scalar(&(*x)).into());
+                    }
+                    Self::Other => {}
+                }
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = Self::ProtoStruct::new();
+                match self {
+                    Self::Created(created) => {
+                        proto.kind = ::protobuf::EnumOrUnknown::new(proto::EventKind::Created);
+                        proto.set_created(ProtoMap::into_proto(created).into());
+                    }
+                    Self::Moved { x, .. } => {
+                        proto.kind = ::protobuf::EnumOrUnknown::new(proto::EventKind::Moved);
+                        proto.set_x(ProtoMapScalar::into_scalar(x).into());
+                    }
+                    Self::Other => {}
+                }
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let tag = proto.kind.value();
+                match tag {
+                    _ if tag == ::protobuf::Enum::value(&proto::EventKind::Created) && proto.has_created() => Ok(Self::Created({
+                            if !proto.has_created() {
+                                return Err(ProtoMapError::missing_message("proto::TaggedEventEntity").with_field("created"));
+                            }
+                            ProtoMap::from_proto(proto.created().to_owned()).map_err(|e| e.with_field("created"))?
+                        })),
+                    _ if tag == ::protobuf::Enum::value(&proto::EventKind::Moved) && !proto.has_created() => Ok(Self::Moved {
+                        x: ProtoMapScalar::from_scalar(proto.x().to_owned()).map_err(|e| e.with_field("x"))?,
+                        cached: Default::default(),
+                    }),
+                    _ => Ok(Self::Other),
+                }
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let tag = proto.kind.value();
+                match tag {
+                    _ if tag == ::protobuf::Enum::value(&proto::EventKind::Created) && proto.has_created() => Ok(Self::Created({
+                            if !proto.has_created() {
+                                return Err(ProtoMapError::missing_message("proto::TaggedEventEntity").with_field("created").into());
+                            }
+                            ProtoMap::try_from_proto_all(proto.created().to_owned()).map_err(|e| e.with_field("created"))?
+                        })),
+                    _ if tag == ::protobuf::Enum::value(&proto::EventKind::Moved) && !proto.has_created() => {
+                        let mut errors = ProtoMapErrors::default();
+                        let field_x = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.x().to_owned()).map_err(|e| e.with_field("x"))?));
+                        match (field_x,) {
+                            (Some(field_x),) => Ok(Self::Moved {
+                                x: field_x,
+                                cached: Default::default(),
+                            }),
+                            _ => Err(errors),
+                        }
+                    },
+                    _ => Ok(Self::Other),
                 }
             }
         }
@@ -15,7 +15,7 @@ fn implement_struct_scalar_types_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -40,15 +40,34 @@ fn implement_struct_scalar_types_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.id = ProtoMapScalar::into_scalar(self.id);
+                proto.valid = ProtoMapScalar::into_scalar(self.valid);
+                proto.bytes = ProtoMapScalar::into_scalar(self.bytes);
+
+                if let Some(value) = self.opt_name {
+                    proto.opt_name = ProtoMapScalar::into_scalar(value);
+                }
+
+                if let Some(value) = self.opt_bytes {
+                    proto.opt_bytes = ProtoMapScalar::into_scalar(value);
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    id: ProtoMapScalar::from_scalar(proto.id)?,
-                    valid: ProtoMapScalar::from_scalar(proto.valid)?,
-                    bytes: ProtoMapScalar::from_scalar(proto.bytes)?,
+                    id: ProtoMapScalar::from_scalar(proto.id).map_err(|e| e.with_field("id"))?,
+                    valid: ProtoMapScalar::from_scalar(proto.valid).map_err(|e| e.with_field("valid"))?,
+                    bytes: ProtoMapScalar::from_scalar(proto.bytes).map_err(|e| e.with_field("bytes"))?,
                     opt_name: {
                         let value = proto.opt_name;
                         if ProtoScalar::has_value(&value) {
-                            Some(ProtoMapScalar::from_scalar(value)?)
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_name"))?)
                         } else {
                             None
                         }
@@ -56,7 +75,7 @@ fn implement_struct_scalar_types_test() {
                     opt_bytes: {
                         let value = proto.opt_bytes;
                         if ProtoScalar::has_value(&value) {
-                            Some(ProtoMapScalar::from_scalar(value)?)
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_bytes"))?)
                         } else {
                             None
                         }
@@ -64,6 +83,41 @@ fn implement_struct_scalar_types_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_id = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.id).map_err(|e| e.with_field("id"))?));
+                let field_valid = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.valid).map_err(|e| e.with_field("valid"))?));
+                let field_bytes = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.bytes).map_err(|e| e.with_field("bytes"))?));
+                let field_opt_name = errors.collect(|| Ok({
+                    let value = proto.opt_name;
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_name"))?)
+                    } else {
+                        None
+                    }
+                }));
+                let field_opt_bytes = errors.collect(|| Ok({
+                    let value = proto.opt_bytes;
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_bytes"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_id, field_valid, field_bytes, field_opt_name, field_opt_bytes,) {
+                    (Some(field_id), Some(field_valid), Some(field_bytes), Some(field_opt_name), Some(field_opt_bytes),) => Ok(Self {
+                        id: field_id,
+                        valid: field_valid,
+                        bytes: field_bytes,
+                        opt_name: field_opt_name,
+                        opt_bytes: field_opt_bytes,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -81,7 +135,7 @@ fn implement_struct_non_scalar_types_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -100,11 +154,24 @@ fn implement_struct_non_scalar_types_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.first = Some(ProtoMap::into_proto(self.first));
+
+                if let Some(value) = self.second {
+                    proto.second = Some(ProtoMap::into_proto(value));
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
                     first: {
                         if let Some(value) = proto.first {
-                            ProtoMap::from_proto(value)?
+                            ProtoMap::from_proto(value).map_err(|e| e.with_field("first"))?
                         } else {
                             Default::default()
                         }
@@ -112,7 +179,7 @@ fn implement_struct_non_scalar_types_test() {
                     },
                     second: {
                         if let Some(value) = proto.second {
-                            Some(ProtoMap::from_proto(value)?)
+                            Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("second"))?)
                         } else {
                             None
                         }
@@ -120,6 +187,105 @@ fn implement_struct_non_scalar_types_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_first = errors.collect(|| Ok({
+                    if let Some(value) = proto.first {
+                        ProtoMap::try_from_proto_all(value).map_err(|e| e.with_field("first"))?
+                    } else {
+                        Default::default()
+                    }
+                }));
+                let field_second = errors.collect(|| Ok({
+                    if let Some(value) = proto.second {
+                        Some(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_field("second"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_first, field_second,) {
+                    (Some(field_first), Some(field_second),) => Ok(Self {
+                        first: field_first,
+                        second: field_second,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_required_message_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(required)]
+            first: Entity,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.first = Some(ProtoMap::to_proto(&self.first));
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.first = Some(ProtoMap::into_proto(self.first));
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    first: {
+                        if let Some(value) = proto.first {
+                            ProtoMap::from_proto(value).map_err(|e| e.with_field("first"))?
+                        } else {
+                            return Err(ProtoMapError::missing_message("proto::Test").with_field("first"))
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_first = errors.collect(|| Ok({
+                    if let Some(value) = proto.first {
+                        ProtoMap::try_from_proto_all(value).map_err(|e| e.with_field("first"))?
+                    } else {
+                        return Err(ProtoMapError::missing_message("proto::Test").with_field("first").into())
+                    }
+                }));
+                match (field_first,) {
+                    (Some(field_first),) => Ok(Self {
+                        first: field_first,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -139,7 +305,7 @@ fn implement_struct_enumeration_types_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -158,13 +324,26 @@ fn implement_struct_enumeration_types_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.first = ProtoMapScalar::into_scalar(self.first);
+
+                if let Some(value) = self.second {
+                    proto.second = ProtoMapScalar::into_scalar(value);
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    first: ProtoMapScalar::from_scalar(proto.first)?,
+                    first: ProtoMapScalar::from_scalar(proto.first).map_err(|e| e.with_field("first"))?,
                     second: {
                         let value = proto.second;
                         if ProtoScalar::has_value(&value) {
-                            Some(ProtoMapScalar::from_scalar(value)?)
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("second"))?)
                         } else {
                             None
                         }
@@ -172,6 +351,28 @@ fn implement_struct_enumeration_types_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_first = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.first).map_err(|e| e.with_field("first"))?));
+                let field_second = errors.collect(|| Ok({
+                    let value = proto.second;
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("second"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_first, field_second,) {
+                    (Some(field_first), Some(field_second),) => Ok(Self {
+                        first: field_first,
+                        second: field_second,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -191,7 +392,7 @@ fn implement_struct_enumeration_types_test() {
 //         }
 //     };
 //
-//     let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+//     let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 //
 //     let s = from_derive_input_struct(&input).unwrap();
 //
@@ -210,13 +411,13 @@ fn implement_struct_enumeration_types_test() {
 //                 proto
 //             }
 //
-//             fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+//             fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
 //                 let inner = Self {
-//                     r#type: ProtoMap::from_proto(proto.type_().to_owned())?,
+//                     r#type: ProtoMap::from_proto(proto.type_().to_owned()).map_err(|e| e.with_field("type"))?,
 //                     opt_entity: {
 //                         let value = proto.other_name().to_owned();
 //                         if proto.has_other_name() {
-//                             Some(ProtoMap::from_proto(value)?)
+//                             Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("other_name"))?)
 //                         } else {
 //                             None
 //                         }
@@ -251,7 +452,7 @@ fn implement_struct_enumeration_types_test() {
 //         }
 //     };
 //
-//     let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+//     let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 //
 //     let s = from_derive_input_struct(&input).unwrap();
 //
@@ -275,22 +476,22 @@ fn implement_struct_enumeration_types_test() {
 //                 proto
 //             }
 //
-//             fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+//             fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
 //                 let inner = Self {
-//                     field_1: uuid_as_string::from_scalar(proto.field_1().to_owned())?,
+//                     field_1: uuid_as_string::from_scalar(proto.field_1().to_owned()).map_err(|e| ProtoMapError::from(e).with_field("field_1"))?,
 //                     field_2: {
 //                         let value = proto.field_2().to_owned();
 //                         if ProtoScalar::has_value(&value) {
-//                             Some(uuid_as_bytes::from_scalar(value)?)
+//                             Some(uuid_as_bytes::from_scalar(value).map_err(|e| ProtoMapError::from(e).with_field("field_2"))?)
 //                         } else {
 //                             None
 //                         }
 //                     },
-//                     field_3: ProtoMapScalar::from_scalar(proto.field_3().to_owned())?,
+//                     field_3: ProtoMapScalar::from_scalar(proto.field_3().to_owned()).map_err(|e| e.with_field("field_3"))?,
 //                     field_4: {
 //                         let value = proto.field_4().to_owned();
 //                         if ProtoScalar::has_value(&value) {
-//                             Some(ProtoMapScalar::from_scalar(value)?)
+//                             Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("field_4"))?)
 //                         } else {
 //                             None
 //                         }
@@ -304,3 +505,927 @@ fn implement_struct_enumeration_types_test() {
 //     let actual = s.implement_proto_map();
 //     assert_tokens_eq(&expected, &actual);
 // }
+
+#[test]
+fn implement_struct_repeated_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            ids: Vec<u32>,
+            entities: Vec<Entity>,
+            #[proto_map(enumeration)]
+            statuses: Vec<Status>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.ids = self.ids.iter().map(ProtoMapScalar::to_scalar).collect();
+                proto.entities = self.entities.iter().map(ProtoMap::to_proto).collect();
+                proto.statuses = self.statuses.iter().map(ProtoMapScalar::to_scalar).collect();
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.ids = self.ids.into_iter().map(ProtoMapScalar::into_scalar).collect();
+                proto.entities = self.entities.into_iter().map(ProtoMap::into_proto).collect();
+                proto.statuses = self.statuses.into_iter().map(ProtoMapScalar::into_scalar).collect();
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    ids: proto.ids
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, value)| ProtoMapScalar::from_scalar(value).map_err(|e| e.with_index(index)))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| e.with_field("ids"))?,
+                    entities: proto.entities
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, value)| ProtoMap::from_proto(value).map_err(|e| e.with_index(index)))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| e.with_field("entities"))?,
+                    statuses: proto.statuses
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, value)| ProtoMapScalar::from_scalar(value).map_err(|e| e.with_index(index)))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| e.with_field("statuses"))?,
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_ids = errors.collect(|| Ok({
+                    let mut element_errors = ProtoMapErrors::default();
+                    let elements: Vec<_> = proto.ids
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            element_errors.collect(|| Ok(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_index(index))?))
+                        })
+                        .collect();
+                    if element_errors.is_empty() {
+                        Ok(elements)
+                    } else {
+                        Err(element_errors)
+                    }
+                }.map_err(|e| e.with_field("ids"))?));
+                let field_entities = errors.collect(|| Ok({
+                    let mut element_errors = ProtoMapErrors::default();
+                    let elements: Vec<_> = proto.entities
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            element_errors.collect(|| Ok(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_index(index))?))
+                        })
+                        .collect();
+                    if element_errors.is_empty() {
+                        Ok(elements)
+                    } else {
+                        Err(element_errors)
+                    }
+                }.map_err(|e| e.with_field("entities"))?));
+                let field_statuses = errors.collect(|| Ok({
+                    let mut element_errors = ProtoMapErrors::default();
+                    let elements: Vec<_> = proto.statuses
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            element_errors.collect(|| Ok(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_index(index))?))
+                        })
+                        .collect();
+                    if element_errors.is_empty() {
+                        Ok(elements)
+                    } else {
+                        Err(element_errors)
+                    }
+                }.map_err(|e| e.with_field("statuses"))?));
+                match (field_ids, field_entities, field_statuses,) {
+                    (Some(field_ids), Some(field_entities), Some(field_statuses),) => Ok(Self {
+                        ids: field_ids,
+                        entities: field_entities,
+                        statuses: field_statuses,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_map_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            names: HashMap<String, u32>,
+            #[proto_map(key_with = "uuid_as_string")]
+            entities: BTreeMap<Uuid, Entity>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.names = self.names
+                    .iter()
+                    .map(|(key, value)| (ProtoMapScalar::to_scalar(key), ProtoMapScalar::to_scalar(value)))
+                    .collect();
+                proto.entities = self.entities
+                    .iter()
+                    .map(|(key, value)| (uuid_as_string::to_scalar(key), ProtoMap::to_proto(value)))
+                    .collect();
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.names = self.names
+                    .into_iter()
+                    .map(|(key, value)| (ProtoMapScalar::into_scalar(key), ProtoMapScalar::into_scalar(value)))
+                    .collect();
+                proto.entities = self.entities
+                    .iter()
+                    .map(|(key, value)| (uuid_as_string::to_scalar(key), ProtoMap::to_proto(value)))
+                    .collect();
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    names: proto.names
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let value = ProtoMapScalar::from_scalar(value).map_err(|e| e.with_key(&key))?;
+                            Ok((ProtoMapScalar::from_scalar(key)?, value))
+                        })
+                        .collect::<std::result::Result<std::collections::HashMap<_, _>, ProtoMapError>>()
+                        .map_err(|e| e.with_field("names"))?,
+                    entities: proto.entities
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let value = ProtoMap::from_proto(value).map_err(|e| e.with_key(&key))?;
+                            Ok((uuid_as_string::from_scalar(key).map_err(ProtoMapError::from)?, value))
+                        })
+                        .collect::<std::result::Result<std::collections::BTreeMap<_, _>, ProtoMapError>>()
+                        .map_err(|e| e.with_field("entities"))?,
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_names = errors.collect(|| Ok({
+                    let mut entry_errors = ProtoMapErrors::default();
+                    let entries: std::collections::HashMap<_, _> = proto.names
+                        .into_iter()
+                        .filter_map(|(key, value)| {
+                            let entry_value = entry_errors.collect(|| Ok(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_key(&key))?));
+                            let entry_key = entry_errors.collect(|| Ok(ProtoMapScalar::from_scalar(key)?));
+                            Some((entry_key?, entry_value?))
+                        })
+                        .collect();
+                    if entry_errors.is_empty() {
+                        Ok(entries)
+                    } else {
+                        Err(entry_errors)
+                    }
+                }.map_err(|e| e.with_field("names"))?));
+                let field_entities = errors.collect(|| Ok({
+                    let mut entry_errors = ProtoMapErrors::default();
+                    let entries: std::collections::BTreeMap<_, _> = proto.entities
+                        .into_iter()
+                        .filter_map(|(key, value)| {
+                            let entry_value = entry_errors.collect(|| Ok(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_key(&key))?));
+                            let entry_key = entry_errors.collect(|| Ok(uuid_as_string::from_scalar(key).map_err(ProtoMapError::from)?));
+                            Some((entry_key?, entry_value?))
+                        })
+                        .collect();
+                    if entry_errors.is_empty() {
+                        Ok(entries)
+                    } else {
+                        Err(entry_errors)
+                    }
+                }.map_err(|e| e.with_field("entities"))?));
+                match (field_names, field_entities,) {
+                    (Some(field_names), Some(field_entities),) => Ok(Self {
+                        names: field_names,
+                        entities: field_entities,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_boxed_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(boxed)]
+            first: Box<Test>,
+            #[proto_map(boxed)]
+            second: Option<Box<Test>>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.first = Some(Box::new(ProtoMap::to_proto(&self.first)));
+                if let Some(value) = &self.second {
+                    proto.second = Some(Box::new(ProtoMap::to_proto(value)));
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.first = Some(Box::new(ProtoMap::into_proto(self.first)));
+                if let Some(value) = self.second {
+                    proto.second = Some(Box::new(ProtoMap::into_proto(value)));
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    first: {
+                        if let Some(value) = proto.first {
+                            ProtoMap::from_proto(*value).map_err(|e| e.with_field("first"))?
+                        } else {
+                            Default::default()
+                        }
+                    },
+                    second: {
+                        if let Some(value) = proto.second {
+                            Some(ProtoMap::from_proto(*value).map_err(|e| e.with_field("second"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_first = errors.collect(|| Ok({
+                    if let Some(value) = proto.first {
+                        ProtoMap::try_from_proto_all(*value).map_err(|e| e.with_field("first"))?
+                    } else {
+                        Default::default()
+                    }
+                }));
+                let field_second = errors.collect(|| Ok({
+                    if let Some(value) = proto.second {
+                        Some(ProtoMap::try_from_proto_all(*value).map_err(|e| e.with_field("second"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_first, field_second,) {
+                    (Some(field_first), Some(field_second),) => Ok(Self {
+                        first: field_first,
+                        second: field_second,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_wrapper_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(wrapper)]
+            opt_id: Option<u32>,
+            #[proto_map(wrapper, scalar, with = "uuid_as_string")]
+            opt_uuid: Option<Uuid>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = &self.opt_id {
+                    proto.opt_id = Some(ProtoMapScalar::to_scalar(value));
+                }
+                if let Some(value) = &self.opt_uuid {
+                    proto.opt_uuid = Some(uuid_as_string::to_scalar(value));
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = self.opt_id {
+                    proto.opt_id = Some(ProtoMapScalar::into_scalar(value));
+                }
+                if let Some(value) = &self.opt_uuid {
+                    proto.opt_uuid = Some(uuid_as_string::to_scalar(value));
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    opt_id: {
+                        if let Some(value) = proto.opt_id {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_id"))?)
+                        } else {
+                            None
+                        }
+                    },
+                    opt_uuid: {
+                        if let Some(value) = proto.opt_uuid {
+                            Some(uuid_as_string::from_scalar(value).map_err(|e| ProtoMapError::from(e).with_field("opt_uuid"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_opt_id = errors.collect(|| Ok({
+                    if let Some(value) = proto.opt_id {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_id"))?)
+                    } else {
+                        None
+                    }
+                }));
+                let field_opt_uuid = errors.collect(|| Ok({
+                    if let Some(value) = proto.opt_uuid {
+                        Some(uuid_as_string::from_scalar(value).map_err(|e| ProtoMapError::from(e).with_field("opt_uuid"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_opt_id, field_opt_uuid,) {
+                    (Some(field_opt_id), Some(field_opt_uuid),) => Ok(Self {
+                        opt_id: field_opt_id,
+                        opt_uuid: field_opt_uuid,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_explicit_presence_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(presence = "explicit")]
+            count: Option<i64>,
+            #[proto_map(enumeration, presence = "explicit")]
+            status: Option<Status>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = &self.count {
+                    proto.count = Some(ProtoMapScalar::to_scalar(value));
+                }
+                if let Some(value) = &self.status {
+                    proto.status = Some(ProtoMapScalar::to_scalar(value));
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = self.count {
+                    proto.count = Some(ProtoMapScalar::into_scalar(value));
+                }
+                if let Some(value) = self.status {
+                    proto.status = Some(ProtoMapScalar::into_scalar(value));
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    count: {
+                        if let Some(value) = proto.count {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("count"))?)
+                        } else {
+                            None
+                        }
+                    },
+                    status: {
+                        if let Some(value) = proto.status {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("status"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_count = errors.collect(|| Ok({
+                    if let Some(value) = proto.count {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("count"))?)
+                    } else {
+                        None
+                    }
+                }));
+                let field_status = errors.collect(|| Ok({
+                    if let Some(value) = proto.status {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("status"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_count, field_status,) {
+                    (Some(field_count), Some(field_status),) => Ok(Self {
+                        count: field_count,
+                        status: field_status,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_presence_policies_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test", presence = "always")]
+        struct Test {
+            count: Option<i64>,
+            #[proto_map(enumeration)]
+            status: Option<Status>,
+            #[proto_map(presence(sentinel = -1))]
+            index: Option<i32>,
+            #[proto_map(presence = "implicit")]
+            name: Option<String>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = &self.count {
+                    proto.count = ProtoMapScalar::to_scalar(value);
+                }
+                if let Some(value) = &self.status {
+                    proto.status = ProtoMapScalar::to_scalar(value);
+                }
+                if let Some(value) = &self.index {
+                    proto.index = ProtoMapScalar::to_scalar(value);
+                } else {
+                    proto.index = Into::into(-1);
+                }
+                if let Some(value) = &self.name {
+                    proto.name = ProtoMapScalar::to_scalar(value);
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = self.count {
+                    proto.count = ProtoMapScalar::into_scalar(value);
+                }
+                if let Some(value) = self.status {
+                    proto.status = ProtoMapScalar::into_scalar(value);
+                }
+                if let Some(value) = self.index {
+                    proto.index = ProtoMapScalar::into_scalar(value);
+                } else {
+                    proto.index = Into::into(-1);
+                }
+                if let Some(value) = self.name {
+                    proto.name = ProtoMapScalar::into_scalar(value);
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    count: Some(ProtoMapScalar::from_scalar(proto.count).map_err(|e| e.with_field("count"))?),
+                    status: Some(ProtoMapScalar::from_scalar(proto.status).map_err(|e| e.with_field("status"))?),
+                    index: {
+                        let value = proto.index;
+                        if value == -1 {
+                            None
+                        } else {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("index"))?)
+                        }
+                    },
+                    name: {
+                        let value = proto.name;
+                        if ProtoScalar::has_value(&value) {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("name"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_count = errors.collect(|| Ok(Some(ProtoMapScalar::from_scalar(proto.count).map_err(|e| e.with_field("count"))?)));
+                let field_status = errors.collect(|| Ok(Some(ProtoMapScalar::from_scalar(proto.status).map_err(|e| e.with_field("status"))?)));
+                let field_index = errors.collect(|| Ok({
+                    let value = proto.index;
+                    if value == -1 {
+                        None
+                    } else {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("index"))?)
+                    }
+                }));
+                let field_name = errors.collect(|| Ok({
+                    let value = proto.name;
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("name"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_count, field_status, field_index, field_name,) {
+                    (Some(field_count), Some(field_status), Some(field_index), Some(field_name),) => Ok(Self {
+                        count: field_count,
+                        status: field_status,
+                        index: field_index,
+                        name: field_name,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_any_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::prost::EventCreated", any)]
+        struct EventCreated {
+            #[proto_map(skip)]
+            id: String,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for EventCreated {
+            type ProtoStruct = proto::prost::EventCreated;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::prost::EventCreated::default();
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::prost::EventCreated::default();
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    id: Default::default(),
+                };
+                Ok(inner)
+            }
+        }
+
+        impl ProtoMapAny for EventCreated {
+            fn type_name() -> String {
+                proto_mapper::any::full_name::<proto::prost::EventCreated>()
+            }
+
+            fn to_any(&self) -> proto_mapper::any::Any {
+                proto_mapper::any::pack(Self::type_url(), &ProtoMap::to_proto(self))
+            }
+
+            fn into_any(self) -> proto_mapper::any::Any {
+                proto_mapper::any::pack(Self::type_url(), &ProtoMap::into_proto(self))
+            }
+
+            fn from_any(any: proto_mapper::any::Any) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMap::from_proto(proto_mapper::any::unpack(any, &Self::type_name())?)
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_field_mask_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::prost::Profile", field_mask)]
+        struct Profile {
+            name: String,
+            address: Address,
+            #[proto_map(rename = "billing")]
+            billing_address: Option<Address>,
+            #[proto_map(skip)]
+            cached: bool,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapFieldMask for Profile {
+            fn merge_from_proto_paths(
+                &mut self,
+                proto: Self::ProtoStruct,
+                paths: &proto_mapper::field_mask::FieldMaskPaths,
+            ) -> std::result::Result<(), ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                paths.check_fields(&["name", "address", "billing"], "proto::prost::Profile")?;
+                if let Some(paths) = paths.field("name") {
+                    paths.check_all().map_err(|e| e.with_field("name"))?;
+                    self.name = ProtoMapScalar::from_scalar(proto.name).map_err(|e| e.with_field("name"))?;
+                }
+                if let Some(paths) = paths.field("address") {
+                    if paths.is_all() {
+                        self.address = {
+                            if let Some(value) = proto.address {
+                                ProtoMap::from_proto(value).map_err(|e| e.with_field("address"))?
+                            } else {
+                                Default::default()
+                            }
+                        };
+                    } else {
+                        ProtoMapFieldMask::merge_from_proto_paths(&mut self.address, proto.address.unwrap_or_default(), paths)
+                            .map_err(|e| e.with_field("address"))?;
+                    }
+                }
+                if let Some(paths) = paths.field("billing") {
+                    match &mut self.billing_address {
+                        Some(value) if !paths.is_all() => ProtoMapFieldMask::merge_from_proto_paths(value, proto.billing.unwrap_or_default(), paths)
+                            .map_err(|e| e.with_field("billing"))?,
+                        _ => self.billing_address = {
+                            if let Some(value) = proto.billing {
+                                Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("billing"))?)
+                            } else {
+                                None
+                            }
+                        },
+                    }
+                }
+                Ok(())
+            }
+
+            fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+                if a.name != b.name {
+                    paths.push(proto_mapper::field_mask::join_path(prefix, "name"));
+                }
+                ProtoMapFieldMask::push_field_mask_paths(&a.address, &b.address, &proto_mapper::field_mask::join_path(prefix, "address"), paths);
+                match (&a.billing_address, &b.billing_address) {
+                    (Some(a), Some(b)) => ProtoMapFieldMask::push_field_mask_paths(a, b, &proto_mapper::field_mask::join_path(prefix, "billing"), paths),
+                    (None, None) => {}
+                    _ => paths.push(proto_mapper::field_mask::join_path(prefix, "billing")),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map_field_mask();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_merge_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::prost::MergeEntity", merge)]
+        struct MergeEntity {
+            name: String,
+            settings: Settings,
+            #[proto_map(rename = "extra")]
+            overrides: Option<Settings>,
+            #[proto_map(skip)]
+            cached: bool,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapMerge for MergeEntity {
+            fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> std::result::Result<(), ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                if ProtoScalar::has_value(&proto.name) {
+                    self.name = ProtoMapScalar::from_scalar(proto.name).map_err(|e| e.with_field("name"))?;
+                }
+                if let Some(value) = proto.settings {
+                    ProtoMapMerge::merge_from_proto(&mut self.settings, value).map_err(|e| e.with_field("settings"))?;
+                }
+                if let Some(value) = proto.extra {
+                    match &mut self.overrides {
+                        Some(current) => ProtoMapMerge::merge_from_proto(current, value).map_err(|e| e.with_field("extra"))?,
+                        None => self.overrides = Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("extra"))?),
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map_merge();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_one_of_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::prost::DocumentEntity", merge)]
+        struct Document {
+            title: String,
+            #[proto_map(one_of = "content")]
+            body: DocumentContent,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Document {
+            type ProtoStruct = proto::prost::DocumentEntity;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::prost::DocumentEntity::default();
+                proto.title = ProtoMapScalar::to_scalar(&self.title);
+                proto.content = ProtoMapOneOf::to_one_of(&self.body);
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::prost::DocumentEntity::default();
+                proto.title = ProtoMapScalar::into_scalar(self.title);
+                proto.content = ProtoMapOneOf::into_one_of(self.body);
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    title: ProtoMapScalar::from_scalar(proto.title).map_err(|e| e.with_field("title"))?,
+                    body: ProtoMapOneOf::from_one_of(proto.content)?,
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_title = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.title).map_err(|e| e.with_field("title"))?));
+                let field_body = errors.collect(|| Ok(ProtoMapOneOf::try_from_one_of_all(proto.content)?));
+                match (field_title, field_body,) {
+                    (Some(field_title), Some(field_body),) => Ok(Self {
+                        title: field_title,
+                        body: field_body,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+
+        impl ProtoMapMerge for Document {
+            fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> std::result::Result<(), ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                if ProtoScalar::has_value(&proto.title) {
+                    self.title = ProtoMapScalar::from_scalar(proto.title).map_err(|e| e.with_field("title"))?;
+                }
+                if let Some(value) = proto.content {
+                    self.body = ProtoMapOneOf::from_one_of(Some(value)).map_err(|e| e.with_field("content"))?;
+                }
+                Ok(())
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
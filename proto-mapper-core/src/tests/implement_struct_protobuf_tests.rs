@@ -13,7 +13,7 @@ fn implement_struct_scalar_types_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -33,14 +33,28 @@ fn implement_struct_scalar_types_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_id(ProtoMapScalar::into_scalar(self.id).into());
+                proto.set_valid(ProtoMapScalar::into_scalar(self.valid).into());
+
+                if let Some(value) = self.opt_name {
+                    proto.set_opt_name(ProtoMapScalar::into_scalar(value).into());
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    id: ProtoMapScalar::from_scalar(proto.id().to_owned())?,
-                    valid: ProtoMapScalar::from_scalar(proto.valid().to_owned())?,
+                    id: ProtoMapScalar::from_scalar(proto.id().to_owned()).map_err(|e| e.with_field("id"))?,
+                    valid: ProtoMapScalar::from_scalar(proto.valid().to_owned()).map_err(|e| e.with_field("valid"))?,
                     opt_name: {
                         let value = proto.opt_name().to_owned();
                         if ProtoScalar::has_value(&value) {
-                            Some(ProtoMapScalar::from_scalar(value)?)
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_name"))?)
                         } else {
                             None
                         }
@@ -48,6 +62,30 @@ fn implement_struct_scalar_types_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_id = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.id().to_owned()).map_err(|e| e.with_field("id"))?));
+                let field_valid = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.valid().to_owned()).map_err(|e| e.with_field("valid"))?));
+                let field_opt_name = errors.collect(|| Ok({
+                    let value = proto.opt_name().to_owned();
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("opt_name"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_id, field_valid, field_opt_name,) {
+                    (Some(field_id), Some(field_valid), Some(field_opt_name),) => Ok(Self {
+                        id: field_id,
+                        valid: field_valid,
+                        opt_name: field_opt_name,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -65,7 +103,7 @@ fn implement_struct_non_scalar_types_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -84,13 +122,26 @@ fn implement_struct_non_scalar_types_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_entity(ProtoMap::into_proto(self.entity).into());
+
+                if let Some(value) = self.opt_entity {
+                    proto.set_opt_entity(ProtoMap::into_proto(value).into());
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    entity: ProtoMap::from_proto(proto.entity().to_owned())?,
+                    entity: ProtoMap::from_proto(proto.entity().to_owned()).map_err(|e| e.with_field("entity"))?,
                     opt_entity: {
                         let value = proto.opt_entity().to_owned();
                         if proto.has_opt_entity() {
-                            Some(ProtoMap::from_proto(value)?)
+                            Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("opt_entity"))?)
                         } else {
                             None
                         }
@@ -98,6 +149,98 @@ fn implement_struct_non_scalar_types_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_entity = errors.collect(|| Ok(ProtoMap::try_from_proto_all(proto.entity().to_owned()).map_err(|e| e.with_field("entity"))?));
+                let field_opt_entity = errors.collect(|| Ok({
+                    let value = proto.opt_entity().to_owned();
+                    if proto.has_opt_entity() {
+                        Some(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_field("opt_entity"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_entity, field_opt_entity,) {
+                    (Some(field_entity), Some(field_opt_entity),) => Ok(Self {
+                        entity: field_entity,
+                        opt_entity: field_opt_entity,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_required_message_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(required)]
+            entity: Entity,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_entity(ProtoMap::to_proto(&self.entity).into());
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_entity(ProtoMap::into_proto(self.entity).into());
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    entity: {
+                        if !proto.has_entity() {
+                            return Err(ProtoMapError::missing_message("proto::Test").with_field("entity"));
+                        }
+                        ProtoMap::from_proto(proto.entity().to_owned()).map_err(|e| e.with_field("entity"))?
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_entity = errors.collect(|| Ok({
+                    if !proto.has_entity() {
+                        return Err(ProtoMapError::missing_message("proto::Test").with_field("entity").into());
+                    }
+                    ProtoMap::try_from_proto_all(proto.entity().to_owned()).map_err(|e| e.with_field("entity"))?
+                }));
+                match (field_entity,) {
+                    (Some(field_entity),) => Ok(Self {
+                        entity: field_entity,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -117,7 +260,7 @@ fn implement_struct_rename_attributes_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -136,13 +279,26 @@ fn implement_struct_rename_attributes_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_type(ProtoMap::into_proto(self.r#type).into());
+
+                if let Some(value) = self.opt_entity {
+                    proto.set_other_name(ProtoMap::into_proto(value).into());
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    r#type: ProtoMap::from_proto(proto.type_().to_owned())?,
+                    r#type: ProtoMap::from_proto(proto.type_().to_owned()).map_err(|e| e.with_field("type"))?,
                     opt_entity: {
                         let value = proto.other_name().to_owned();
                         if proto.has_other_name() {
-                            Some(ProtoMap::from_proto(value)?)
+                            Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("other_name"))?)
                         } else {
                             None
                         }
@@ -150,6 +306,28 @@ fn implement_struct_rename_attributes_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_type = errors.collect(|| Ok(ProtoMap::try_from_proto_all(proto.type_().to_owned()).map_err(|e| e.with_field("type"))?));
+                let field_opt_entity = errors.collect(|| Ok({
+                    let value = proto.other_name().to_owned();
+                    if proto.has_other_name() {
+                        Some(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_field("other_name"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_type, field_opt_entity,) {
+                    (Some(field_type), Some(field_opt_entity),) => Ok(Self {
+                        r#type: field_type,
+                        opt_entity: field_opt_entity,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -177,7 +355,7 @@ fn implement_struct_scalars_with_attribute_overrides_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -201,22 +379,40 @@ fn implement_struct_scalars_with_attribute_overrides_test() {
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_field_1(uuid_as_string::to_scalar(&self.field_1).into());
+
+                if let Some(value) = &self.field_2 {
+                    proto.set_field_2(uuid_as_bytes::to_scalar(value).into());
+                }
+
+                proto.set_field_3(ProtoMapScalar::into_scalar(self.field_3).into());
+
+                if let Some(value) = self.field_4 {
+                    proto.set_field_4(ProtoMapScalar::into_scalar(value).into());
+                }
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    field_1: uuid_as_string::from_scalar(proto.field_1().to_owned())?,
+                    field_1: uuid_as_string::from_scalar(proto.field_1().to_owned()).map_err(|e| ProtoMapError::from(e).with_field("field_1"))?,
                     field_2: {
                         let value = proto.field_2().to_owned();
                         if ProtoScalar::has_value(&value) {
-                            Some(uuid_as_bytes::from_scalar(value)?)
+                            Some(uuid_as_bytes::from_scalar(value).map_err(|e| ProtoMapError::from(e).with_field("field_2"))?)
                         } else {
                             None
                         }
                     },
-                    field_3: ProtoMapScalar::from_scalar(proto.field_3().to_owned())?,
+                    field_3: ProtoMapScalar::from_scalar(proto.field_3().to_owned()).map_err(|e| e.with_field("field_3"))?,
                     field_4: {
                         let value = proto.field_4().to_owned();
                         if ProtoScalar::has_value(&value) {
-                            Some(ProtoMapScalar::from_scalar(value)?)
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("field_4"))?)
                         } else {
                             None
                         }
@@ -224,6 +420,39 @@ fn implement_struct_scalars_with_attribute_overrides_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_field_1 = errors.collect(|| Ok(uuid_as_string::from_scalar(proto.field_1().to_owned()).map_err(|e| ProtoMapError::from(e).with_field("field_1"))?));
+                let field_field_2 = errors.collect(|| Ok({
+                    let value = proto.field_2().to_owned();
+                    if ProtoScalar::has_value(&value) {
+                        Some(uuid_as_bytes::from_scalar(value).map_err(|e| ProtoMapError::from(e).with_field("field_2"))?)
+                    } else {
+                        None
+                    }
+                }));
+                let field_field_3 = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.field_3().to_owned()).map_err(|e| e.with_field("field_3"))?));
+                let field_field_4 = errors.collect(|| Ok({
+                    let value = proto.field_4().to_owned();
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("field_4"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_field_1, field_field_2, field_field_3, field_field_4,) {
+                    (Some(field_field_1), Some(field_field_2), Some(field_field_3), Some(field_field_4),) => Ok(Self {
+                        field_1: field_field_1,
+                        field_2: field_field_2,
+                        field_3: field_field_3,
+                        field_4: field_field_4,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -243,7 +472,7 @@ fn implement_struct_enumerations_with_attribute_overrides_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let s = from_derive_input_struct(&input).unwrap();
 
@@ -253,22 +482,35 @@ fn implement_struct_enumerations_with_attribute_overrides_test() {
             fn to_proto(&self) -> Self::ProtoStruct {
                 let mut proto = proto::Test::default();
 
-                proto.set_enum_1(ProtoMap::to_proto(&self.enum_1).into());
+                proto.enum_1 = ProtoMapEnum::to_enum_or_unknown(&self.enum_1);
 
                 if let Some(value) = &self.enum_2 {
-                    proto.set_enum_2(ProtoMap::to_proto(value).into());
+                    proto.enum_2 = ProtoMapEnum::to_enum_or_unknown(value);
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.enum_1 = ProtoMapEnum::into_enum_or_unknown(self.enum_1);
+
+                if let Some(value) = self.enum_2 {
+                    proto.enum_2 = ProtoMapEnum::into_enum_or_unknown(value);
                 }
 
                 proto
             }
 
-            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
                 let inner = Self {
-                    enum_1: ProtoMap::from_proto(proto.enum_1().to_owned())?,
+                    enum_1: ProtoMapEnum::from_enum_or_unknown(proto.enum_1).map_err(|e| e.with_field("enum_1"))?,
                     enum_2: {
-                        let value = proto.enum_2().to_owned();
+                        let value = proto.enum_2;
                         if ProtoScalar::has_value(&value.value())  {
-                            Some(ProtoMap::from_proto(value)?)
+                            Some(ProtoMapEnum::from_enum_or_unknown(value).map_err(|e| e.with_field("enum_2"))?)
                         } else {
                             None
                         }
@@ -276,6 +518,28 @@ fn implement_struct_enumerations_with_attribute_overrides_test() {
                 };
                 Ok(inner)
             }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_enum_1 = errors.collect(|| Ok(ProtoMapEnum::from_enum_or_unknown(proto.enum_1).map_err(|e| e.with_field("enum_1"))?));
+                let field_enum_2 = errors.collect(|| Ok({
+                    let value = proto.enum_2;
+                    if ProtoScalar::has_value(&value.value())  {
+                        Some(ProtoMapEnum::from_enum_or_unknown(value).map_err(|e| e.with_field("enum_2"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_enum_1, field_enum_2,) {
+                    (Some(field_enum_1), Some(field_enum_2),) => Ok(Self {
+                        enum_1: field_enum_1,
+                        enum_2: field_enum_2,
+                    }),
+                    _ => Err(errors),
+                }
+            }
         }
     };
 
@@ -285,3 +549,853 @@ fn implement_struct_enumerations_with_attribute_overrides_test() {
 
 // #[test]
 // fn implement_struct_with_attribute_overrides_test() {}
+
+#[test]
+fn implement_struct_repeated_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            ids: Vec<u32>,
+            entities: Vec<Entity>,
+            #[proto_map(enumeration)]
+            statuses: Vec<Status>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_ids(self.ids.iter().map(|value| ProtoMapScalar::to_scalar(value).into()).collect());
+                proto.set_entities(self.entities.iter().map(|value| ProtoMap::to_proto(value).into()).collect());
+                proto.set_statuses(self.statuses.iter().map(|value| ProtoMapEnum::to_enum_or_unknown(value).into()).collect());
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_ids(self.ids.into_iter().map(|value| ProtoMapScalar::into_scalar(value).into()).collect());
+                proto.set_entities(self.entities.into_iter().map(|value| ProtoMap::into_proto(value).into()).collect());
+                proto.set_statuses(self.statuses.into_iter().map(|value| ProtoMapEnum::into_enum_or_unknown(value).into()).collect());
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    ids: proto.ids()
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .map(|(index, value)| ProtoMapScalar::from_scalar(value).map_err(|e| e.with_index(index)))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| e.with_field("ids"))?,
+                    entities: proto.entities()
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .map(|(index, value)| ProtoMap::from_proto(value).map_err(|e| e.with_index(index)))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| e.with_field("entities"))?,
+                    statuses: proto.statuses()
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .map(|(index, value)| ProtoMapEnum::from_enum_or_unknown(value).map_err(|e| e.with_index(index)))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| e.with_field("statuses"))?,
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_ids = errors.collect(|| Ok({
+                    let mut element_errors = ProtoMapErrors::default();
+                    let elements: Vec<_> = proto.ids()
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            element_errors.collect(|| Ok(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_index(index))?))
+                        })
+                        .collect();
+                    if element_errors.is_empty() {
+                        Ok(elements)
+                    } else {
+                        Err(element_errors)
+                    }
+                }.map_err(|e| e.with_field("ids"))?));
+                let field_entities = errors.collect(|| Ok({
+                    let mut element_errors = ProtoMapErrors::default();
+                    let elements: Vec<_> = proto.entities()
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            element_errors.collect(|| Ok(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_index(index))?))
+                        })
+                        .collect();
+                    if element_errors.is_empty() {
+                        Ok(elements)
+                    } else {
+                        Err(element_errors)
+                    }
+                }.map_err(|e| e.with_field("entities"))?));
+                let field_statuses = errors.collect(|| Ok({
+                    let mut element_errors = ProtoMapErrors::default();
+                    let elements: Vec<_> = proto.statuses()
+                        .iter()
+                        .cloned()
+                        .enumerate()
+                        .filter_map(|(index, value)| {
+                            element_errors.collect(|| Ok(ProtoMapEnum::from_enum_or_unknown(value).map_err(|e| e.with_index(index))?))
+                        })
+                        .collect();
+                    if element_errors.is_empty() {
+                        Ok(elements)
+                    } else {
+                        Err(element_errors)
+                    }
+                }.map_err(|e| e.with_field("statuses"))?));
+                match (field_ids, field_entities, field_statuses,) {
+                    (Some(field_ids), Some(field_entities), Some(field_statuses),) => Ok(Self {
+                        ids: field_ids,
+                        entities: field_entities,
+                        statuses: field_statuses,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_map_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            names: HashMap<String, u32>,
+            #[proto_map(key_with = "uuid_as_string")]
+            entities: BTreeMap<Uuid, Entity>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_names(
+                    self.names
+                        .iter()
+                        .map(|(key, value)| (ProtoMapScalar::to_scalar(key), ProtoMapScalar::to_scalar(value).into()))
+                        .collect(),
+                );
+                proto.set_entities(
+                    self.entities
+                        .iter()
+                        .map(|(key, value)| (uuid_as_string::to_scalar(key), ProtoMap::to_proto(value).into()))
+                        .collect(),
+                );
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                proto.set_names(
+                    self.names
+                        .into_iter()
+                        .map(|(key, value)| (ProtoMapScalar::into_scalar(key), ProtoMapScalar::into_scalar(value).into()))
+                        .collect(),
+                );
+                proto.set_entities(
+                    self.entities
+                        .iter()
+                        .map(|(key, value)| (uuid_as_string::to_scalar(key), ProtoMap::to_proto(value).into()))
+                        .collect(),
+                );
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    names: proto.names()
+                        .clone()
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let value = ProtoMapScalar::from_scalar(value).map_err(|e| e.with_key(&key))?;
+                            Ok((ProtoMapScalar::from_scalar(key)?, value))
+                        })
+                        .collect::<std::result::Result<std::collections::HashMap<_, _>, ProtoMapError>>()
+                        .map_err(|e| e.with_field("names"))?,
+                    entities: proto.entities()
+                        .clone()
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let value = ProtoMap::from_proto(value).map_err(|e| e.with_key(&key))?;
+                            Ok((uuid_as_string::from_scalar(key).map_err(ProtoMapError::from)?, value))
+                        })
+                        .collect::<std::result::Result<std::collections::BTreeMap<_, _>, ProtoMapError>>()
+                        .map_err(|e| e.with_field("entities"))?,
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_names = errors.collect(|| Ok({
+                    let mut entry_errors = ProtoMapErrors::default();
+                    let entries: std::collections::HashMap<_, _> = proto.names()
+                        .clone()
+                        .into_iter()
+                        .filter_map(|(key, value)| {
+                            let entry_value = entry_errors.collect(|| Ok(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_key(&key))?));
+                            let entry_key = entry_errors.collect(|| Ok(ProtoMapScalar::from_scalar(key)?));
+                            Some((entry_key?, entry_value?))
+                        })
+                        .collect();
+                    if entry_errors.is_empty() {
+                        Ok(entries)
+                    } else {
+                        Err(entry_errors)
+                    }
+                }.map_err(|e| e.with_field("names"))?));
+                let field_entities = errors.collect(|| Ok({
+                    let mut entry_errors = ProtoMapErrors::default();
+                    let entries: std::collections::BTreeMap<_, _> = proto.entities()
+                        .clone()
+                        .into_iter()
+                        .filter_map(|(key, value)| {
+                            let entry_value = entry_errors.collect(|| Ok(ProtoMap::try_from_proto_all(value).map_err(|e| e.with_key(&key))?));
+                            let entry_key = entry_errors.collect(|| Ok(uuid_as_string::from_scalar(key).map_err(ProtoMapError::from)?));
+                            Some((entry_key?, entry_value?))
+                        })
+                        .collect();
+                    if entry_errors.is_empty() {
+                        Ok(entries)
+                    } else {
+                        Err(entry_errors)
+                    }
+                }.map_err(|e| e.with_field("entities"))?));
+                match (field_names, field_entities,) {
+                    (Some(field_names), Some(field_entities),) => Ok(Self {
+                        names: field_names,
+                        entities: field_entities,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_wrapper_types_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(wrapper)]
+            opt_id: Option<u32>,
+            #[proto_map(wrapper, scalar, with = "uuid_as_string")]
+            opt_uuid: Option<Uuid>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = &self.opt_id {
+                    proto.mut_opt_id().value = ProtoMapScalar::to_scalar(value);
+                }
+                if let Some(value) = &self.opt_uuid {
+                    proto.mut_opt_uuid().value = uuid_as_string::to_scalar(value);
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = self.opt_id {
+                    proto.mut_opt_id().value = ProtoMapScalar::into_scalar(value);
+                }
+                if let Some(value) = &self.opt_uuid {
+                    proto.mut_opt_uuid().value = uuid_as_string::to_scalar(value);
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    opt_id: {
+                        if proto.has_opt_id() {
+                            Some(ProtoMapScalar::from_scalar(proto.opt_id().value.to_owned()).map_err(|e| e.with_field("opt_id"))?)
+                        } else {
+                            None
+                        }
+                    },
+                    opt_uuid: {
+                        if proto.has_opt_uuid() {
+                            Some(uuid_as_string::from_scalar(proto.opt_uuid().value.to_owned()).map_err(|e| ProtoMapError::from(e).with_field("opt_uuid"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_opt_id = errors.collect(|| Ok({
+                    if proto.has_opt_id() {
+                        Some(ProtoMapScalar::from_scalar(proto.opt_id().value.to_owned()).map_err(|e| e.with_field("opt_id"))?)
+                    } else {
+                        None
+                    }
+                }));
+                let field_opt_uuid = errors.collect(|| Ok({
+                    if proto.has_opt_uuid() {
+                        Some(uuid_as_string::from_scalar(proto.opt_uuid().value.to_owned()).map_err(|e| ProtoMapError::from(e).with_field("opt_uuid"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_opt_id, field_opt_uuid,) {
+                    (Some(field_opt_id), Some(field_opt_uuid),) => Ok(Self {
+                        opt_id: field_opt_id,
+                        opt_uuid: field_opt_uuid,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_explicit_presence_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test")]
+        struct Test {
+            #[proto_map(presence = "explicit")]
+            count: Option<i64>,
+            #[proto_map(enumeration, presence = "explicit")]
+            status: Option<Status>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = &self.count {
+                    proto.set_count(ProtoMapScalar::to_scalar(value).into());
+                }
+                if let Some(value) = &self.status {
+                    proto.status = Some(ProtoMapEnum::to_enum_or_unknown(value));
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = self.count {
+                    proto.set_count(ProtoMapScalar::into_scalar(value).into());
+                }
+                if let Some(value) = self.status {
+                    proto.status = Some(ProtoMapEnum::into_enum_or_unknown(value));
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    count: {
+                        if proto.has_count() {
+                            Some(ProtoMapScalar::from_scalar(proto.count().to_owned()).map_err(|e| e.with_field("count"))?)
+                        } else {
+                            None
+                        }
+                    },
+                    status: {
+                        if let Some(value) = proto.status {
+                            Some(ProtoMapEnum::from_enum_or_unknown(value).map_err(|e| e.with_field("status"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_count = errors.collect(|| Ok({
+                    if proto.has_count() {
+                        Some(ProtoMapScalar::from_scalar(proto.count().to_owned()).map_err(|e| e.with_field("count"))?)
+                    } else {
+                        None
+                    }
+                }));
+                let field_status = errors.collect(|| Ok({
+                    if let Some(value) = proto.status {
+                        Some(ProtoMapEnum::from_enum_or_unknown(value).map_err(|e| e.with_field("status"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_count, field_status,) {
+                    (Some(field_count), Some(field_status),) => Ok(Self {
+                        count: field_count,
+                        status: field_status,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_presence_policies_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Test", presence = "always")]
+        struct Test {
+            count: Option<i64>,
+            #[proto_map(enumeration)]
+            status: Option<Status>,
+            #[proto_map(presence(sentinel = -1))]
+            index: Option<i32>,
+            #[proto_map(presence = "implicit")]
+            name: Option<String>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Test {
+            type ProtoStruct = proto::Test;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = &self.count {
+                    proto.set_count(ProtoMapScalar::to_scalar(value).into());
+                }
+                if let Some(value) = &self.status {
+                    proto.status = ProtoMapEnum::to_enum_or_unknown(value);
+                }
+                if let Some(value) = &self.index {
+                    proto.set_index(ProtoMapScalar::to_scalar(value).into());
+                } else {
+                    proto.set_index(Into::into(-1));
+                }
+                if let Some(value) = &self.name {
+                    proto.set_name(ProtoMapScalar::to_scalar(value).into());
+                }
+
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::Test::default();
+
+                if let Some(value) = self.count {
+                    proto.set_count(ProtoMapScalar::into_scalar(value).into());
+                }
+                if let Some(value) = self.status {
+                    proto.status = ProtoMapEnum::into_enum_or_unknown(value);
+                }
+                if let Some(value) = self.index {
+                    proto.set_index(ProtoMapScalar::into_scalar(value).into());
+                } else {
+                    proto.set_index(Into::into(-1));
+                }
+                if let Some(value) = self.name {
+                    proto.set_name(ProtoMapScalar::into_scalar(value).into());
+                }
+
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    count: Some(ProtoMapScalar::from_scalar(proto.count().to_owned()).map_err(|e| e.with_field("count"))?),
+                    status: Some(ProtoMapEnum::from_enum_or_unknown(proto.status).map_err(|e| e.with_field("status"))?),
+                    index: {
+                        let value = proto.index().to_owned();
+                        if value == -1 {
+                            None
+                        } else {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("index"))?)
+                        }
+                    },
+                    name: {
+                        let value = proto.name().to_owned();
+                        if ProtoScalar::has_value(&value) {
+                            Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("name"))?)
+                        } else {
+                            None
+                        }
+                    },
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_count = errors.collect(|| Ok(Some(ProtoMapScalar::from_scalar(proto.count().to_owned()).map_err(|e| e.with_field("count"))?)));
+                let field_status = errors.collect(|| Ok(Some(ProtoMapEnum::from_enum_or_unknown(proto.status).map_err(|e| e.with_field("status"))?)));
+                let field_index = errors.collect(|| Ok({
+                    let value = proto.index().to_owned();
+                    if value == -1 {
+                        None
+                    } else {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("index"))?)
+                    }
+                }));
+                let field_name = errors.collect(|| Ok({
+                    let value = proto.name().to_owned();
+                    if ProtoScalar::has_value(&value) {
+                        Some(ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("name"))?)
+                    } else {
+                        None
+                    }
+                }));
+                match (field_count, field_status, field_index, field_name,) {
+                    (Some(field_count), Some(field_status), Some(field_index), Some(field_name),) => Ok(Self {
+                        count: field_count,
+                        status: field_status,
+                        index: field_index,
+                        name: field_name,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_any_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::protobuf::EventCreated", any)]
+        struct EventCreated {
+            #[proto_map(skip)]
+            id: String,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for EventCreated {
+            type ProtoStruct = proto::protobuf::EventCreated;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::protobuf::EventCreated::default();
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::protobuf::EventCreated::default();
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    id: Default::default(),
+                };
+                Ok(inner)
+            }
+        }
+
+        impl ProtoMapAny for EventCreated {
+            fn type_name() -> String {
+                proto_mapper::any::full_name::<proto::protobuf::EventCreated>()
+            }
+
+            fn to_any(&self) -> proto_mapper::any::Any {
+                proto_mapper::any::pack(Self::type_url(), &ProtoMap::to_proto(self))
+            }
+
+            fn into_any(self) -> proto_mapper::any::Any {
+                proto_mapper::any::pack(Self::type_url(), &ProtoMap::into_proto(self))
+            }
+
+            fn from_any(any: proto_mapper::any::Any) -> std::result::Result<Self, ProtoMapError> {
+                ProtoMap::from_proto(proto_mapper::any::unpack(any, &Self::type_name())?)
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_field_mask_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::protobuf::Profile", field_mask)]
+        struct Profile {
+            name: String,
+            address: Address,
+            #[proto_map(rename = "billing")]
+            billing_address: Option<Address>,
+            #[proto_map(skip)]
+            cached: bool,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapFieldMask for Profile {
+            fn merge_from_proto_paths(
+                &mut self,
+                proto: Self::ProtoStruct,
+                paths: &proto_mapper::field_mask::FieldMaskPaths,
+            ) -> std::result::Result<(), ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                paths.check_fields(&["name", "address", "billing"], "proto::protobuf::Profile")?;
+                if let Some(paths) = paths.field("name") {
+                    paths.check_all().map_err(|e| e.with_field("name"))?;
+                    self.name = ProtoMapScalar::from_scalar(proto.name().to_owned()).map_err(|e| e.with_field("name"))?;
+                }
+                if let Some(paths) = paths.field("address") {
+                    if paths.is_all() {
+                        self.address = ProtoMap::from_proto(proto.address().to_owned()).map_err(|e| e.with_field("address"))?;
+                    } else {
+                        ProtoMapFieldMask::merge_from_proto_paths(&mut self.address, proto.address().to_owned(), paths)
+                            .map_err(|e| e.with_field("address"))?;
+                    }
+                }
+                if let Some(paths) = paths.field("billing") {
+                    match &mut self.billing_address {
+                        Some(value) if !paths.is_all() => ProtoMapFieldMask::merge_from_proto_paths(value, proto.billing().to_owned(), paths)
+                            .map_err(|e| e.with_field("billing"))?,
+                        _ => self.billing_address = {
+                            let value = proto.billing().to_owned();
+                            if proto.has_billing() {
+                                Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("billing"))?)
+                            } else {
+                                None
+                            }
+                        },
+                    }
+                }
+                Ok(())
+            }
+
+            fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+                if a.name != b.name {
+                    paths.push(proto_mapper::field_mask::join_path(prefix, "name"));
+                }
+                ProtoMapFieldMask::push_field_mask_paths(&a.address, &b.address, &proto_mapper::field_mask::join_path(prefix, "address"), paths);
+                match (&a.billing_address, &b.billing_address) {
+                    (Some(a), Some(b)) => ProtoMapFieldMask::push_field_mask_paths(a, b, &proto_mapper::field_mask::join_path(prefix, "billing"), paths),
+                    (None, None) => {}
+                    _ => paths.push(proto_mapper::field_mask::join_path(prefix, "billing")),
+                }
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map_field_mask();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_merge_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::protobuf::MergeEntity", merge)]
+        struct MergeEntity {
+            name: String,
+            settings: Settings,
+            #[proto_map(rename = "extra")]
+            overrides: Option<Settings>,
+            #[proto_map(skip)]
+            cached: bool,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMapMerge for MergeEntity {
+            fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> std::result::Result<(), ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                {
+                    let value = proto.name().to_owned();
+                    if ProtoScalar::has_value(&value) {
+                        self.name = ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("name"))?;
+                    }
+                }
+                if proto.has_settings() {
+                    let value = proto.settings().to_owned();
+                    ProtoMapMerge::merge_from_proto(&mut self.settings, value).map_err(|e| e.with_field("settings"))?;
+                }
+                if proto.has_extra() {
+                    let value = proto.extra().to_owned();
+                    match &mut self.overrides {
+                        Some(current) => ProtoMapMerge::merge_from_proto(current, value).map_err(|e| e.with_field("extra"))?,
+                        None => self.overrides = Some(ProtoMap::from_proto(value).map_err(|e| e.with_field("extra"))?),
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map_merge();
+    assert_tokens_eq(&expected, &actual);
+}
+
+#[test]
+fn implement_struct_one_of_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::protobuf::DocumentEntity", merge)]
+        struct Document {
+            title: String,
+            #[proto_map(one_of = "content")]
+            body: DocumentContent,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let s = from_derive_input_struct(&input).unwrap();
+
+    let expected = quote! {
+        impl ProtoMap for Document {
+            type ProtoStruct = proto::protobuf::DocumentEntity;
+            fn to_proto(&self) -> Self::ProtoStruct {
+                let mut proto = proto::protobuf::DocumentEntity::default();
+                proto.set_title(ProtoMapScalar::to_scalar(&self.title).into());
+                proto.content = ProtoMapOneOf::to_one_of(&self.body);
+                proto
+            }
+
+            fn into_proto(self) -> Self::ProtoStruct {
+                let mut proto = proto::protobuf::DocumentEntity::default();
+                proto.set_title(ProtoMapScalar::into_scalar(self.title).into());
+                proto.content = ProtoMapOneOf::into_one_of(self.body);
+                proto
+            }
+
+            fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                let inner = Self {
+                    title: ProtoMapScalar::from_scalar(proto.title().to_owned()).map_err(|e| e.with_field("title"))?,
+                    body: ProtoMapOneOf::from_one_of(proto.content.clone())?,
+                };
+                Ok(inner)
+            }
+
+            #[allow(clippy::needless_question_mark)]
+            fn try_from_proto_all(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapErrors> {
+                let _guard = RecursionGuard::enter()?;
+                let mut errors = ProtoMapErrors::default();
+                let field_title = errors.collect(|| Ok(ProtoMapScalar::from_scalar(proto.title().to_owned()).map_err(|e| e.with_field("title"))?));
+                let field_body = errors.collect(|| Ok(ProtoMapOneOf::try_from_one_of_all(proto.content.clone())?));
+                match (field_title, field_body,) {
+                    (Some(field_title), Some(field_body),) => Ok(Self {
+                        title: field_title,
+                        body: field_body,
+                    }),
+                    _ => Err(errors),
+                }
+            }
+        }
+
+        impl ProtoMapMerge for Document {
+            fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> std::result::Result<(), ProtoMapError> {
+                let _guard = RecursionGuard::enter()?;
+                {
+                    let value = proto.title().to_owned();
+                    if ProtoScalar::has_value(&value) {
+                        self.title = ProtoMapScalar::from_scalar(value).map_err(|e| e.with_field("title"))?;
+                    }
+                }
+                if let Some(value) = proto.content.clone() {
+                    self.body = ProtoMapOneOf::from_one_of(Some(value)).map_err(|e| e.with_field("content"))?;
+                }
+                Ok(())
+            }
+        }
+    };
+
+    let actual = s.implement_proto_map();
+    assert_tokens_eq(&expected, &actual);
+}
@@ -1,9 +1,10 @@
 use std::ops::Deref;
 
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{parse_quote, Data, DeriveInput, Path};
 
-use crate::enums::{EnumAttrs, EnumVariant};
+use crate::enums::{EnumAttrs, EnumVariant, NotSet, OneOf};
+use crate::tests::from_derive_input_enum;
 
 
 #[test]
@@ -20,7 +21,7 @@ fn parse_enum_one_of_attributes_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let attrs = EnumAttrs::try_from(input.attrs.deref()).unwrap();
     let expected_source: Path = parse_quote! { proto::HierarchyEntity };
@@ -29,12 +30,48 @@ fn parse_enum_one_of_attributes_test() {
     if let Some(one_of) = attrs.one_of {
         assert_eq!(one_of.field, "data")
     } else {
-        assert!(false, "Missing one of attribute")
+        panic!("Missing one of attribute")
     }
 
     assert_eq!(attrs.rename_variants, Some("snake_case".to_string()))
 }
 
+#[test]
+fn parse_enum_one_of_path_attribute_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::catalog_entity::Section",
+            one_of(field = "content", path = "proto::catalog_entity::section::Content")
+        )]
+        enum SectionContent {
+            Scalar(Entity),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let attrs = EnumAttrs::try_from(input.attrs.deref()).unwrap();
+    let one_of = attrs.one_of.expect("Missing one of attribute");
+    let expected_path: Path = parse_quote! { proto::catalog_entity::section::Content };
+    assert_eq!(one_of.field, "content");
+    assert_eq!(one_of.path, Some(expected_path));
+    assert_eq!(
+        one_of.proto_enum_path(&attrs.source).to_string(),
+        quote! { proto::catalog_entity::section::Content }.to_string()
+    );
+
+    // The default path is the `oneof` enum in the module of the source message
+    let one_of = OneOf {
+        field: format_ident!("content"),
+        path: None,
+        not_set: NotSet::Error,
+    };
+    assert_eq!(
+        one_of.proto_enum_path(&attrs.source).to_string(),
+        quote! { proto::catalog_entity::section::Content }.to_string()
+    );
+}
+
 #[test]
 fn parse_enumeration_attributes_test() {
     let fragment = quote! {
@@ -51,7 +88,7 @@ fn parse_enumeration_attributes_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let attrs = EnumAttrs::try_from(input.attrs.deref()).unwrap();
     let expected_source: Path = parse_quote! { proto::Entity };
@@ -65,6 +102,66 @@ fn parse_enumeration_attributes_test() {
     )
 }
 
+#[test]
+fn parse_enumeration_naming_attributes_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityType",
+            enumeration,
+            rename_variants = "auto",
+            strip_prefix = "Kind",
+            add_prefix = "TYPE_"
+        )]
+        enum EntityKind {
+            KindA,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let attrs = EnumAttrs::try_from(input.attrs.deref()).unwrap();
+    assert!(attrs.is_auto_naming());
+    assert_eq!(attrs.strip_prefix, Some("Kind".to_string()));
+    assert_eq!(attrs.add_prefix, Some("TYPE_".to_string()));
+
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+    let variant = EnumVariant::try_from_enumeration_variant(data.variants.first().unwrap()).unwrap();
+    assert_eq!(attrs.proto_variant_name(&variant.name, &variant.attrs), "TYPE_A");
+}
+
+#[test]
+fn parse_enumeration_naming_attributes_errors_test() {
+    let fragments = [
+        quote! {
+            #[proto_map(source = "proto::Entity", enumeration, rename_variants = "kebab-case")]
+            enum Entity {
+                One,
+            }
+        },
+        quote! {
+            #[proto_map(source = "proto::Entity", one_of(field = "data"), rename_variants = "auto")]
+            enum Entity {
+                One(Inner),
+            }
+        },
+        quote! {
+            #[proto_map(source = "proto::Entity", one_of(field = "data"), add_prefix = "ENTITY_")]
+            enum Entity {
+                One(Inner),
+            }
+        },
+    ];
+
+    for fragment in fragments {
+        let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+        if EnumAttrs::try_from(input.attrs.deref()).is_ok() {
+            panic!("Expected naming attributes error")
+        }
+    }
+}
+
 #[test]
 fn parse_enumeration_one_of_attributes_mutual_exclusive_test() {
     let fragment = quote! {
@@ -81,13 +178,95 @@ fn parse_enumeration_one_of_attributes_mutual_exclusive_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     if let Ok(_attrs) = EnumAttrs::try_from(input.attrs.deref()) {
         panic!("Expected mutual exclusion error on `enumeration` and `one_of` attributes")
     }
 }
 
+#[test]
+fn parse_any_attributes_test() {
+    let fragment = quote! {
+        #[derive(Debug, ProtoConvert, PartialEq)]
+        #[proto_map(source = "proto_mapper::any::Any", any)]
+        enum Event {
+            Created(EventCreated),
+            Deleted(EventDeleted),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let attrs = EnumAttrs::try_from(input.attrs.deref()).unwrap();
+    assert!(attrs.is_any());
+    assert!(!attrs.is_enumeration());
+    assert_eq!(attrs.one_of, None);
+}
+
+#[test]
+fn parse_any_one_of_attributes_mutual_exclusive_test() {
+    let fragment = quote! {
+        #[derive(Debug, ProtoConvert, PartialEq)]
+        #[proto_map(
+            source = "proto_mapper::any::Any",
+            any,
+            one_of(field="foo")
+        )]
+        enum Event {
+            Created(EventCreated),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    if let Ok(_attrs) = EnumAttrs::try_from(input.attrs.deref()) {
+        panic!("Expected mutual exclusion error on `any` and `one_of` attributes")
+    }
+}
+
+#[test]
+fn parse_field_mask_enumeration_attributes_mutual_exclusive_test() {
+    let fragment = quote! {
+        #[derive(Debug, ProtoConvert, PartialEq)]
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+            field_mask
+        )]
+        enum EntityStatus {
+            StatusA,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    if let Ok(_attrs) = EnumAttrs::try_from(input.attrs.deref()) {
+        panic!("Expected mutual exclusion error on `field_mask` and `enumeration` attributes")
+    }
+}
+
+#[test]
+fn parse_merge_enumeration_attributes_mutual_exclusive_test() {
+    let fragment = quote! {
+        #[derive(Debug, ProtoConvert, PartialEq)]
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+            merge
+        )]
+        enum EntityStatus {
+            StatusA,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    if let Ok(_attrs) = EnumAttrs::try_from(input.attrs.deref()) {
+        panic!("Expected mutual exclusion error on `merge` and `enumeration` attributes")
+    }
+}
+
 #[test]
 fn parse_unnamed_variant_success_test() {
     let fragment = quote! {
@@ -100,7 +279,7 @@ fn parse_unnamed_variant_success_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let Data::Enum(data) = &input.data else {
         panic!("Expected `enum` data item")
     };
@@ -124,7 +303,7 @@ fn parse_enumeration_variant_success_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let Data::Enum(data) = &input.data else {
         panic!("Expected `enum` data item")
     };
@@ -147,13 +326,13 @@ fn parse_u_variant_error_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let Data::Enum(data) = &input.data else {
         panic!("Expected `enum` data item")
     };
 
     let variant = data.variants.first().unwrap();
-    if let Ok(_) = EnumVariant::try_from_enumeration_variant(variant) {
+    if EnumVariant::try_from_enumeration_variant(variant).is_ok() {
         panic!("Expected unnamed variant error")
     }
 }
@@ -171,19 +350,529 @@ fn parse_unnamed_variant_errors_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
     let Data::Enum(data) = &input.data else {
         panic!("Expected `enum` data item")
     };
 
     let mut variants = data.variants.iter();
     let variant = variants.next().unwrap();
-    if let Ok(_) = EnumVariant::try_from_unnamed_variant(variant) {
+    if EnumVariant::try_from_unnamed_variant(variant).is_ok() {
         panic!("Expected unnamed variant no inner error")
     }
 
     let variant = variants.next().unwrap();
-    if let Ok(_) = EnumVariant::try_from_unnamed_variant(variant) {
+    if EnumVariant::try_from_unnamed_variant(variant).is_ok() {
         panic!("Expected unnamed variant more that one inner error")
     }
 }
+
+#[test]
+fn parse_unknown_variant_success_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+        )]
+        enum Foo {
+            #[proto_map(unknown)]
+            Unrecognized(i32),
+            #[proto_map(other)]
+            Other,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let mut variants = data.variants.iter();
+    let variant = EnumVariant::try_from_enumeration_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.name, "Unrecognized");
+    assert!(variant.attrs.unknown);
+
+    let variant = EnumVariant::try_from_enumeration_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.name, "Other");
+    assert!(variant.attrs.other);
+}
+
+#[test]
+fn parse_unknown_variant_errors_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+        )]
+        enum Foo {
+            #[proto_map(unknown)]
+            Unrecognized,
+            #[proto_map(unknown)]
+            Invalid(u32),
+            #[proto_map(unknown, other)]
+            Both(i32),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    for variant in data.variants.iter() {
+        if EnumVariant::try_from_enumeration_variant(variant).is_ok() {
+            panic!("Expected unknown variant error for `{}`", variant.ident)
+        }
+    }
+
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+        )]
+        enum Foo {
+            #[proto_map(unknown)]
+            Unrecognized(i32),
+            #[proto_map(other)]
+            Other,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    if from_derive_input_enum(&input).is_ok() {
+        panic!("Expected single catch all variant error")
+    }
+}
+
+#[test]
+fn parse_variant_rename_alias_value_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityState",
+            enumeration,
+        )]
+        enum Foo {
+            #[proto_map(rename = "ENTITY_STATE_ACTIVE", alias = "ENTITY_STATE_ENABLED", alias = "ENTITY_STATE_ON")]
+            Active,
+            #[proto_map(value = 3)]
+            Archived,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let mut variants = data.variants.iter();
+    let variant = EnumVariant::try_from_enumeration_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.attrs.rename.as_deref(), Some("ENTITY_STATE_ACTIVE"));
+    assert_eq!(variant.attrs.alias, vec!["ENTITY_STATE_ENABLED", "ENTITY_STATE_ON"]);
+    assert_eq!(variant.attrs.value, None);
+
+    let variant = EnumVariant::try_from_enumeration_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.attrs.rename, None);
+    assert_eq!(variant.attrs.value, Some(3));
+}
+
+#[test]
+fn parse_variant_rename_alias_value_errors_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityState",
+            enumeration,
+        )]
+        enum Foo {
+            #[proto_map(rename = "ENTITY_STATE_ACTIVE", value = 1)]
+            Active,
+            #[proto_map(unknown, alias = "ENTITY_STATE_ENABLED")]
+            Unrecognized(i32),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    for variant in data.variants.iter() {
+        if EnumVariant::try_from_enumeration_variant(variant).is_ok() {
+            panic!("Expected variant attributes error for `{}`", variant.ident)
+        }
+    }
+
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::HierarchyEntity",
+            one_of(field = "data"),
+        )]
+        enum Foo {
+            #[proto_map(rename = "first")]
+            First(Inner),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let variant = data.variants.first().unwrap();
+    if EnumVariant::try_from_unnamed_variant(variant).is_ok() {
+        panic!("Expected enumeration only variant attributes error")
+    }
+}
+
+#[test]
+fn parse_one_of_payload_variants_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::IdentityEntity",
+            one_of(field = "id"),
+        )]
+        enum Identity {
+            Email(String),
+            Anonymous,
+            #[proto_map(enumeration)]
+            Status(EntityStatus),
+            #[proto_map(scalar, with = "uuid_as_string")]
+            Uuid(Uuid),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let mut variants = data.variants.iter();
+    let variant = EnumVariant::try_from_one_of_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.name, "Email");
+    assert!(variant.ty.unwrap().is_scalar());
+
+    let variant = EnumVariant::try_from_one_of_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.name, "Anonymous");
+    assert_eq!(variant.field_name, None);
+    assert!(variant.ty.is_none());
+
+    let variant = EnumVariant::try_from_one_of_variant(variants.next().unwrap()).unwrap();
+    let expected_field_name: Path = parse_quote! { EntityStatus };
+    assert_eq!(variant.field_name, Some(expected_field_name));
+    assert!(variant.attrs.enumeration);
+
+    let variant = EnumVariant::try_from_one_of_variant(variants.next().unwrap()).unwrap();
+    let expected_with: Path = parse_quote! { uuid_as_string };
+    assert!(variant.attrs.scalar);
+    assert_eq!(variant.attrs.with, Some(expected_with));
+}
+
+#[test]
+fn parse_one_of_payload_variant_errors_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::IdentityEntity",
+            one_of(field = "id"),
+        )]
+        enum Foo {
+            #[proto_map(scalar)]
+            Anonymous,
+            Emails(Vec<String>),
+            Email(Option<String>),
+            #[proto_map(scalar, enumeration)]
+            Status(EntityStatus),
+            #[proto_map(enumeration, with = "status_mapping")]
+            OtherStatus(EntityStatus),
+            #[proto_map(scalar, rename = "email")]
+            Renamed(String),
+            Invalid(Inner1, Inner2),
+            #[proto_map(scalar)]
+            Circle { radius: f64 },
+            #[proto_map(not_set)]
+            Square { side: f64 },
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    for variant in data.variants.iter() {
+        if EnumVariant::try_from_one_of_variant(variant).is_ok() {
+            panic!("Expected `one_of` variant error for `{}`", variant.ident)
+        }
+    }
+
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::EntityStatus",
+            enumeration,
+        )]
+        enum Foo {
+            #[proto_map(scalar)]
+            Bar,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let variant = data.variants.first().unwrap();
+    if EnumVariant::try_from_enumeration_variant(variant).is_ok() {
+        panic!("Expected `one_of` only variant attributes error")
+    }
+}
+
+#[test]
+fn parse_one_of_named_variants_test() {
+    let fragment = quote! {
+        #[proto_map(
+            source = "proto::ShapeEntity",
+            one_of(field = "shape"),
+        )]
+        enum Shape {
+            Circle { radius: f64 },
+            Rectangle {
+                width: f64,
+                #[proto_map(rename = "height")]
+                length: f64,
+                #[proto_map(skip)]
+                area: f64,
+            },
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let mut variants = data.variants.iter();
+    let variant = EnumVariant::try_from_one_of_variant(variants.next().unwrap()).unwrap();
+    assert_eq!(variant.name, "Circle");
+    assert_eq!(variant.field_name, None);
+    let fields = variant.fields.unwrap();
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].name, "radius");
+    assert!(fields[0].ty.is_scalar());
+
+    let variant = EnumVariant::try_from_one_of_variant(variants.next().unwrap()).unwrap();
+    let fields = variant.fields.unwrap();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[1].proto_field_path_name(), "height");
+    assert!(fields[2].is_skipped());
+}
+
+#[test]
+fn parse_one_of_not_set_attributes_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "default"))]
+        enum Contact {
+            Email(String),
+            #[proto_map(default)]
+            Phone(i64),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+    assert_eq!(e.attrs.one_of.unwrap().not_set, NotSet::Default);
+    assert!(e.variants[1].attrs.default);
+
+    let fragment = quote! {
+        #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "variant"))]
+        enum Contact {
+            Email(String),
+            #[proto_map(not_set)]
+            NotSet,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+    assert_eq!(e.attrs.one_of.unwrap().not_set, NotSet::Variant);
+    assert!(e.variants[1].attrs.not_set);
+
+    let fragment = quote! {
+        #[proto_map(source = "proto::ContactEntity", one_of(field = "contact"))]
+        enum Contact {
+            Email(String),
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let e = from_derive_input_enum(&input).unwrap();
+    assert_eq!(e.attrs.one_of.unwrap().not_set, NotSet::Error);
+}
+
+#[test]
+fn parse_one_of_not_set_attributes_errors_test() {
+    let fragments = [
+        // Marked variants without the `not_set` policy
+        quote! {
+            #[proto_map(source = "proto::ContactEntity", one_of(field = "contact"))]
+            enum Contact {
+                #[proto_map(default)]
+                Email(String),
+            }
+        },
+        // Policy without a marked variant
+        quote! {
+            #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "default"))]
+            enum Contact {
+                Email(String),
+            }
+        },
+        // Policy with more than one marked variants
+        quote! {
+            #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "default"))]
+            enum Contact {
+                #[proto_map(default)]
+                Email(String),
+                #[proto_map(default)]
+                Phone(i64),
+            }
+        },
+        // Policy with the marked variant of the other policy
+        quote! {
+            #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "variant"))]
+            enum Contact {
+                Email(String),
+                #[proto_map(default)]
+                NotSet,
+            }
+        },
+        // Non unit `not_set` variant
+        quote! {
+            #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "variant"))]
+            enum Contact {
+                Email(String),
+                #[proto_map(not_set)]
+                NotSet(Empty),
+            }
+        },
+        // Unknown policy
+        quote! {
+            #[proto_map(source = "proto::ContactEntity", one_of(field = "contact", not_set = "ignore"))]
+            enum Contact {
+                Email(String),
+            }
+        },
+    ];
+
+    for fragment in fragments {
+        let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+        if from_derive_input_enum(&input).is_ok() {
+            panic!("Expected `not_set` policy error")
+        }
+    }
+}
+
+#[test]
+fn parse_tagged_attributes_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+        enum Event {
+            #[proto_map(tag = "proto::EventKind::Created")]
+            Created(EventCreated),
+            #[proto_map(tag = "proto::EventKind::Renamed", payload = "name")]
+            Renamed(String),
+            #[proto_map(tag = "proto::EventKind::Moved")]
+            Moved { x: f64, y: f64 },
+            #[proto_map(other)]
+            Other,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let attrs = EnumAttrs::try_from(input.attrs.deref()).unwrap();
+    assert_eq!(attrs.tagged.unwrap().tag, "kind");
+    assert_eq!(attrs.one_of, None);
+
+    let Data::Enum(data) = &input.data else {
+        panic!("Expected `enum` data item")
+    };
+
+    let mut variants = data.variants.iter();
+    let variant = EnumVariant::try_from_tagged_variant(variants.next().unwrap()).unwrap();
+    let expected_tag: Path = parse_quote! { proto::EventKind::Created };
+    assert_eq!(variant.attrs.tag, Some(expected_tag));
+    let payload = variant.payload.unwrap();
+    assert_eq!(payload.name, "created");
+    assert!(payload.is_required());
+
+    let variant = EnumVariant::try_from_tagged_variant(variants.next().unwrap()).unwrap();
+    let payload = variant.payload.unwrap();
+    assert_eq!(payload.name, "name");
+    assert!(!payload.is_required());
+
+    let variant = EnumVariant::try_from_tagged_variant(variants.next().unwrap()).unwrap();
+    assert!(variant.payload.is_none());
+    assert_eq!(variant.fields.unwrap().len(), 2);
+
+    let variant = EnumVariant::try_from_tagged_variant(variants.next().unwrap()).unwrap();
+    assert!(variant.attrs.other);
+    assert_eq!(variant.attrs.tag, None);
+}
+
+#[test]
+fn parse_tagged_attributes_errors_test() {
+    let fragments = [
+        // Mutually excluded with `one_of`
+        quote! {
+            #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"), one_of(field = "kind"))]
+            enum Event {
+                #[proto_map(tag = "proto::EventKind::Created")]
+                Created(EventCreated),
+            }
+        },
+        // Missing tag
+        quote! {
+            #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+            enum Event {
+                Created(EventCreated),
+            }
+        },
+        // Non unit `other` variant
+        quote! {
+            #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+            enum Event {
+                #[proto_map(other)]
+                Other(EventCreated),
+            }
+        },
+        // Payload of a unit variant
+        quote! {
+            #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+            enum Event {
+                #[proto_map(tag = "proto::EventKind::Deleted", payload = "id")]
+                Deleted,
+            }
+        },
+        // `one_of` only attributes
+        quote! {
+            #[proto_map(source = "proto::TaggedEventEntity", tagged(tag = "kind"))]
+            enum Event {
+                #[proto_map(tag = "proto::EventKind::Deleted", default)]
+                Deleted,
+            }
+        },
+        // `tag` of a `one_of` variant
+        quote! {
+            #[proto_map(source = "proto::DocumentEntity", one_of(field = "content"))]
+            enum Content {
+                #[proto_map(tag = "proto::EventKind::Created")]
+                Scalar(ScalarEntity),
+            }
+        },
+    ];
+
+    for fragment in fragments {
+        let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+        if from_derive_input_enum(&input).is_ok() {
+            panic!("Expected `tagged` attributes error")
+        }
+    }
+}
@@ -1,10 +1,10 @@
-use crate::structs::StructAttrs;
+use crate::structs::{Presence, StructAttrs};
 use darling::FromMeta;
 use quote::quote;
 use syn::{Data, DeriveInput};
 
 use crate::structs::StructField;
-use crate::types::{ScalarType, Ty};
+use crate::types::{MapType, ScalarType, Ty};
 
 #[test]
 fn parse_struct_attributes_test() {
@@ -13,7 +13,7 @@ fn parse_struct_attributes_test() {
         struct Test;
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let meta = &input.attrs.first().unwrap().meta;
 
@@ -21,6 +21,49 @@ fn parse_struct_attributes_test() {
     let path = attrs.source;
     assert_eq!(quote! { #path }.to_string(), "proto :: Entity");
     assert_eq!(attrs.rename_all, Some("snake_case".into()));
+    assert!(attrs.presence.is_none());
+}
+
+#[test]
+fn parse_struct_presence_attribute_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Entity", presence = "always")]
+        struct Test;
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let meta = &input.attrs.first().unwrap().meta;
+
+    let attrs = StructAttrs::from_meta(meta).unwrap();
+    assert_eq!(attrs.presence, Some(Presence::Always));
+}
+
+#[test]
+fn parse_struct_any_attributes_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Entity", any)]
+        struct Test;
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let meta = &input.attrs.first().unwrap().meta;
+
+    let attrs = StructAttrs::from_meta(meta).unwrap();
+    assert!(attrs.is_any());
+    assert_eq!(attrs.type_name, None);
+
+    let fragment = quote! {
+        #[proto_map(source = "proto::Entity", type_name = "entities.Entity")]
+        struct Test;
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+    let meta = &input.attrs.first().unwrap().meta;
+
+    let attrs = StructAttrs::from_meta(meta).unwrap();
+    assert!(attrs.is_any());
+    assert_eq!(attrs.type_name, Some("entities.Entity".into()));
 }
 
 #[test]
@@ -38,7 +81,7 @@ fn parse_struct_primitive_fields_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let Data::Struct(data) = &input.data else {
         panic!("Expected Data::Struct here");
@@ -128,7 +171,7 @@ fn parse_nested_types_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let Data::Struct(data) = &input.data else {
         panic!("Expected Data::Struct here");
@@ -167,13 +210,14 @@ fn parse_nested_types_test() {
 
     let field = StructField::try_from_field(field).unwrap();
     assert_eq!(field.name, "hash_map".to_string());
-    assert!(matches!(
+    assert_eq!(
         field.ty,
-        Ty::Other {
-            optional: false,
-            ..
-        }
-    ));
+        Ty::map(
+            MapType::HashMap,
+            Ty::scalar(ScalarType::U32, false),
+            Ty::scalar(ScalarType::String, false)
+        )
+    );
 
     assert!(field.attrs.is_none());
 }
@@ -186,7 +230,7 @@ fn unsupported_tuple_primitive_field_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let Data::Struct(data) = &input.data else {
         panic!("Expected Data::Struct here");
@@ -211,7 +255,7 @@ fn parse_struct_field_attributes_test() {
         }
     };
 
-    let input = syn::parse2::<DeriveInput>(fragment.into()).unwrap();
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
 
     let Data::Struct(data) = &input.data else {
         panic!("Expected Data::Struct here");
@@ -248,3 +292,396 @@ fn parse_struct_field_attributes_test() {
     let attrs = field.attrs.unwrap();
     assert!(attrs.enumeration);
 }
+
+#[test]
+fn unsupported_required_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(required)]
+            a: u32,
+            #[proto_map(required)]
+            b: Option<Entity>,
+            #[proto_map(required, enumeration)]
+            c: Status,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn parse_repeated_types_test() {
+    let fragment = quote! {
+        struct Test {
+            ids: Vec<u32>,
+            bytes: Vec<u8>,
+            entities: std::vec::Vec<Entity>,
+            blobs: Vec<Vec<u8>>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    let mut fields = data.fields.iter();
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.ty, Ty::repeated(Ty::scalar(ScalarType::U32, false)));
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.ty, Ty::scalar(ScalarType::VecBytes, false));
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.ty, Ty::repeated(Ty::other(false)));
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.ty, Ty::repeated(Ty::scalar(ScalarType::VecBytes, false)));
+}
+
+#[test]
+fn unsupported_repeated_types_test() {
+    let fragment = quote! {
+        struct Test {
+            a: Vec<Option<Entity>>,
+            b: Vec<Vec<Entity>>,
+            #[proto_map(required)]
+            c: Vec<Entity>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn parse_map_types_test() {
+    let fragment = quote! {
+        struct Test {
+            entities: HashMap<String, Entity>,
+            names: std::collections::BTreeMap<u32, String>,
+            #[proto_map(key_with = "uuid_as_string")]
+            counts: HashMap<Uuid, u64>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    let mut fields = data.fields.iter();
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(
+        field.ty,
+        Ty::map(MapType::HashMap, Ty::scalar(ScalarType::String, false), Ty::other(false))
+    );
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(
+        field.ty,
+        Ty::map(
+            MapType::BTreeMap,
+            Ty::scalar(ScalarType::U32, false),
+            Ty::scalar(ScalarType::String, false)
+        )
+    );
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(
+        field.ty,
+        Ty::map(MapType::HashMap, Ty::other(false), Ty::scalar(ScalarType::U64, false))
+    );
+}
+
+#[test]
+fn unsupported_map_types_test() {
+    let fragment = quote! {
+        struct Test {
+            a: HashMap<String, Option<Entity>>,
+            b: HashMap<Option<String>, Entity>,
+            c: BTreeMap<String, Vec<Entity>>,
+            #[proto_map(required)]
+            d: HashMap<String, Entity>,
+            #[proto_map(key_with = "uuid_as_string")]
+            e: Vec<Entity>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn unsupported_boxed_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(boxed)]
+            a: u32,
+            #[proto_map(boxed, enumeration)]
+            b: Status,
+            #[proto_map(boxed)]
+            c: Vec<Entity>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn unsupported_wrapper_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(wrapper)]
+            a: u32,
+            #[proto_map(wrapper, enumeration)]
+            b: Option<Status>,
+            #[proto_map(wrapper)]
+            c: Option<Entity>,
+            #[proto_map(wrapper)]
+            d: Vec<u32>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn parse_presence_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(presence = "explicit")]
+            a: Option<u32>,
+            #[proto_map(enumeration, presence = "implicit")]
+            b: Option<Status>,
+            #[proto_map(presence = "always")]
+            c: Option<String>,
+            #[proto_map(presence(sentinel = -1))]
+            d: Option<i32>,
+            #[proto_map(presence(sentinel = "none"))]
+            e: Option<String>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    let mut fields = data.fields.iter();
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.attrs.unwrap().presence, Some(Presence::Explicit));
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.attrs.unwrap().presence, Some(Presence::Implicit));
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    assert_eq!(field.attrs.unwrap().presence, Some(Presence::Always));
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    let Some(Presence::Sentinel(sentinel)) = field.attrs.unwrap().presence else {
+        panic!("Expected Presence::Sentinel here");
+    };
+    assert_eq!(quote! { #sentinel }.to_string(), "- 1");
+
+    let field = StructField::try_from_field(fields.next().unwrap()).unwrap();
+    let Some(Presence::Sentinel(sentinel)) = field.attrs.unwrap().presence else {
+        panic!("Expected Presence::Sentinel here");
+    };
+    assert_eq!(quote! { #sentinel }.to_string(), "\"none\"");
+}
+
+#[test]
+fn unsupported_presence_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(presence = "explicit")]
+            a: u32,
+            #[proto_map(presence = "explicit")]
+            b: Option<Entity>,
+            #[proto_map(wrapper, presence = "explicit")]
+            c: Option<u32>,
+            #[proto_map(presence = "unknown")]
+            d: Option<u32>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn parse_one_of_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(one_of = "content")]
+            body: DocumentContent,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    let field = StructField::try_from_field(data.fields.iter().next().unwrap()).unwrap();
+    assert!(!field.is_nested_message());
+    assert_eq!(field.proto_field_path_name(), "content");
+    assert_eq!(field.attrs.unwrap().one_of, Some("content".to_string()));
+
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(one_of = "content")]
+            body: Option<DocumentContent>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    let field = StructField::try_from_field(data.fields.iter().next().unwrap()).unwrap();
+    assert!(field.ty.is_optional());
+    assert_eq!(field.proto_field_path_name(), "content");
+}
+
+#[test]
+fn unsupported_one_of_field_attribute_test() {
+    let fragment = quote! {
+        struct Test {
+            #[proto_map(one_of = "content")]
+            a: u32,
+            #[proto_map(one_of = "content")]
+            b: Vec<DocumentContent>,
+            #[proto_map(one_of = "content", rename = "body")]
+            c: DocumentContent,
+            #[proto_map(one_of = "content", with = "custom")]
+            d: DocumentContent,
+            #[proto_map(one_of = "content", required)]
+            e: DocumentContent,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    for field in data.fields.iter() {
+        let res = StructField::try_from_field(field);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn unsupported_struct_presence_attribute_test() {
+    let fragment = quote! {
+        #[proto_map(source = "proto::Entity", presence(sentinel = 0))]
+        struct Test {
+            a: Option<u32>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let res = crate::tests::from_derive_input_struct(&input);
+    assert!(res.is_err());
+}
+
+#[test]
+fn parse_numeric_types_test() {
+    let fragment = quote! {
+        struct Test {
+            a: u8,
+            b: Option<i16>,
+            c: usize,
+            d: std::num::NonZeroU32,
+            e: Option<NonZeroI64>,
+            f: char,
+            g: Vec<u16>,
+        }
+    };
+
+    let input = syn::parse2::<DeriveInput>(fragment).unwrap();
+
+    let Data::Struct(data) = &input.data else {
+        panic!("Expected Data::Struct here");
+    };
+
+    let expected = [
+        Ty::scalar(ScalarType::U8, false),
+        Ty::scalar(ScalarType::I16, true),
+        Ty::scalar(ScalarType::Usize, false),
+        Ty::scalar(ScalarType::NonZeroU32, false),
+        Ty::scalar(ScalarType::NonZeroI64, true),
+        Ty::scalar(ScalarType::Char, false),
+        Ty::repeated(Ty::scalar(ScalarType::U16, false)),
+    ];
+
+    for (field, expected) in data.fields.iter().zip(expected) {
+        let field = StructField::try_from_field(field).unwrap();
+        assert_eq!(field.ty, expected);
+        assert!(field.attrs.is_none());
+    }
+}
@@ -4,5 +4,5 @@ mod type_scanner;
 
 pub(crate) use nested_type::NestedType;
 #[allow(unused_imports)] // For test purposes
-pub(crate) use ty::{ScalarType, Ty};
+pub(crate) use ty::{MapType, ScalarType, Ty};
 pub(crate) use type_scanner::TypeScanner;
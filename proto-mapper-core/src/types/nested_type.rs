@@ -1,4 +1,5 @@
 use proc_macro2::Ident;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone)]
 pub(crate) enum NestedType {
@@ -49,32 +50,28 @@ impl NestedType {
     }
 }
 
-impl ToString for NestedType {
-    fn to_string(&self) -> String {
-        let mut res = String::new();
-
+impl Display for NestedType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let name = self.name();
         if let Some(name) = &name {
-            res.push_str(name);
+            f.write_str(name)?;
         }
 
         if self.args().is_empty() {
-            return res;
+            return Ok(());
         }
         if name.is_some() {
-            res.push('<');
+            f.write_str("<")?;
         } else {
-            res.push('(')
+            f.write_str("(")?;
         }
 
         let args: Vec<_> = self.args().iter().map(|arg| arg.to_string()).collect();
-        res.push_str(&args.join(","));
+        f.write_str(&args.join(","))?;
         if name.is_some() {
-            res.push('>');
+            f.write_str(">")
         } else {
-            res.push(')')
+            f.write_str(")")
         }
-
-        res
     }
 }
@@ -1,4 +1,6 @@
 use crate::types::{NestedType, TypeScanner};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 use syn::Type;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -12,12 +14,77 @@ pub(crate) enum ScalarType {
     Bool,
     String,
     VecBytes,
+    /// Narrower and wider integer types mapped to the proto scalars with checked conversions
+    U8,
+    U16,
+    I8,
+    I16,
+    Usize,
+    Isize,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroI32,
+    NonZeroI64,
+    Char,
+}
+
+impl ScalarType {
+    fn from_type_name(name: &str) -> Option<Self> {
+        let ty = match name {
+            "bool" => Self::Bool,
+            "String" => Self::String,
+            "u32" => Self::U32,
+            "i32" => Self::I32,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            "u64" => Self::U64,
+            "i64" => Self::I64,
+            "Vec<u8>" => Self::VecBytes,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "usize" => Self::Usize,
+            "isize" => Self::Isize,
+            "NonZeroU32" => Self::NonZeroU32,
+            "NonZeroU64" => Self::NonZeroU64,
+            "NonZeroI32" => Self::NonZeroI32,
+            "NonZeroI64" => Self::NonZeroI64,
+            "char" => Self::Char,
+            _ => return None,
+        };
+        Some(ty)
+    }
+}
+
+/// The collection types supported for proto `map` fields
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum MapType {
+    HashMap,
+    BTreeMap,
+}
+
+impl ToTokens for MapType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            MapType::HashMap => quote! { std::collections::HashMap },
+            MapType::BTreeMap => quote! { std::collections::BTreeMap },
+        })
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum Ty {
     Scalar { ty: ScalarType, optional: bool },
     Other { optional: bool },
+    /// `Vec<T>` types (except `Vec<u8>`) mapped to proto `repeated` fields
+    Repeated { ty: Box<Ty> },
+    /// `HashMap<K, V>` and `BTreeMap<K, V>` types mapped to proto `map` fields
+    Map {
+        map: MapType,
+        key: Box<Ty>,
+        value: Box<Ty>,
+    },
 }
 
 impl Ty {
@@ -29,11 +96,24 @@ impl Ty {
         Self::Other { optional }
     }
 
+    pub(crate) fn repeated(ty: Ty) -> Self {
+        Self::Repeated { ty: Box::new(ty) }
+    }
+
+    pub(crate) fn map(map: MapType, key: Ty, value: Ty) -> Self {
+        Self::Map {
+            map,
+            key: Box::new(key),
+            value: Box::new(value),
+        }
+    }
+
     #[inline]
     pub(crate) fn is_optional(&self) -> bool {
         match self {
             Ty::Scalar { optional, .. } => *optional,
             Ty::Other { optional, .. } => *optional,
+            Ty::Repeated { .. } | Ty::Map { .. } => false,
         }
     }
 
@@ -42,6 +122,27 @@ impl Ty {
         matches!(self, Ty::Scalar { .. })
     }
 
+    #[inline]
+    pub(crate) fn is_repeated(&self) -> bool {
+        matches!(self, Ty::Repeated { .. })
+    }
+
+    #[inline]
+    pub(crate) fn is_map(&self) -> bool {
+        matches!(self, Ty::Map { .. })
+    }
+
+    /// Returns the element type of repeated types, the value type of map types
+    /// or self for all the other types.
+    #[inline]
+    pub(crate) fn element(&self) -> &Ty {
+        match self {
+            Ty::Repeated { ty } => ty,
+            Ty::Map { value, .. } => value,
+            _ => self,
+        }
+    }
+
     // TODO handle enumeration case via attrs
     pub(crate) fn try_from_field(field: &syn::Field) -> darling::Result<Self> {
         let mut scanner = TypeScanner::default();
@@ -49,7 +150,27 @@ impl Ty {
         match &field.ty {
             Type::Path(syn::TypePath { path, .. }) => {
                 let nested_type = scanner.scan(path.clone());
-                Ok(Ty::from(&nested_type))
+                let ty = Ty::from(&nested_type);
+                if let Ty::Repeated { ty: element } = &ty {
+                    if element.is_optional() || element.is_repeated() || element.is_map() {
+                        return Err(darling::Error::unsupported_shape(
+                            "Repeated fields support only non optional elements e.g. `Vec<Foo>`",
+                        ));
+                    }
+                }
+                if let Ty::Map { key, value, .. } = &ty {
+                    if key.is_optional() || key.is_repeated() || key.is_map() {
+                        return Err(darling::Error::unsupported_shape(
+                            "Map fields support only non optional keys e.g. `HashMap<String, Foo>`",
+                        ));
+                    }
+                    if value.is_optional() || value.is_repeated() || value.is_map() {
+                        return Err(darling::Error::unsupported_shape(
+                            "Map fields support only non optional values e.g. `HashMap<String, Foo>`",
+                        ));
+                    }
+                }
+                Ok(ty)
             }
             _ => Err(
                 darling::Error::unexpected_type("Macro supports only path types"), // .with_span(&field.span()), TODO test this
@@ -60,29 +181,34 @@ impl Ty {
 
 impl From<&NestedType> for Ty {
     fn from(value: &NestedType) -> Self {
+        // Repeated types other than `Vec<u8>` (bytes scalar)
+        if let (Some("Vec"), [element]) = (value.name().as_deref(), value.args().as_slice()) {
+            if element.to_string() != "u8" {
+                return Self::repeated(Ty::from(element));
+            }
+        }
+
+        // Map types
+        if let [key, map_value] = value.args().as_slice() {
+            let map = match value.name().as_deref() {
+                Some("HashMap") => Some(MapType::HashMap),
+                Some("BTreeMap") => Some(MapType::BTreeMap),
+                _ => None,
+            };
+            if let Some(map) = map {
+                return Self::map(map, Ty::from(key), Ty::from(map_value));
+            }
+        }
+
         // TODO how do I traverse NestedType efficiently???
         let value = value.to_string();
-        match value.to_string() {
-            _ if value == "bool" => Self::scalar(ScalarType::Bool, false),
-            _ if value == "String" => Self::scalar(ScalarType::String, false),
-            _ if value == "u32" => Self::scalar(ScalarType::U32, false),
-            _ if value == "i32" => Self::scalar(ScalarType::I32, false),
-            _ if value == "f32" => Self::scalar(ScalarType::F32, false),
-            _ if value == "f64" => Self::scalar(ScalarType::F64, false),
-            _ if value == "u64" => Self::scalar(ScalarType::U64, false),
-            _ if value == "i64" => Self::scalar(ScalarType::I64, false),
-            _ if value == "Vec<u8>" => Self::scalar(ScalarType::VecBytes, false),
-            _ if value == "Option<bool>" => Self::scalar(ScalarType::Bool, true),
-            _ if value == "Option<String>" => Self::scalar(ScalarType::String, true),
-            _ if value == "Option<u32>" => Self::scalar(ScalarType::U32, true),
-            _ if value == "Option<i32>" => Self::scalar(ScalarType::I32, true),
-            _ if value == "Option<f32>" => Self::scalar(ScalarType::F32, true),
-            _ if value == "Option<f64>" => Self::scalar(ScalarType::F64, true),
-            _ if value == "Option<u64>" => Self::scalar(ScalarType::U64, true),
-            _ if value == "Option<i64>" => Self::scalar(ScalarType::I64, true),
-            _ if value == "Option<Vec<u8>>" => Self::scalar(ScalarType::VecBytes, true),
-            _ if value.starts_with("Option<") => Self::other(true),
-            _ => Self::other(false),
+        let (name, optional) = match value.strip_prefix("Option<").and_then(|v| v.strip_suffix('>')) {
+            Some(inner) => (inner, true),
+            None => (value.as_str(), false),
+        };
+        match ScalarType::from_type_name(name) {
+            Some(ty) => Self::scalar(ty, optional),
+            None => Self::other(optional),
         }
     }
 }
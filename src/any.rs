@@ -0,0 +1,189 @@
+//! Packing of mapped types to `google.protobuf.Any` messages and a registry that unpacks them.
+//!
+//! Types that derive `ProtoMap` with the `any` struct attribute implement [`ProtoMapAny`] with the type url of their
+//! `source` message e.g. `type.googleapis.com/entities.schema.Entity`.
+use crate::{ProtoMap, ProtoMapError};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+/// The `google.protobuf.Any` message of the enabled proto framework feature
+#[cfg(feature = "prost")]
+pub use prost_types::Any;
+/// The `google.protobuf.Any` message of the enabled proto framework feature
+#[cfg(feature = "protobuf")]
+pub use protobuf::well_known_types::any::Any;
+
+/// Prefix of the type urls of packed messages
+pub const TYPE_URL_PREFIX: &str = "type.googleapis.com/";
+
+/// Types that are packed to and unpacked from `google.protobuf.Any` messages as their [`ProtoMap::ProtoStruct`].
+pub trait ProtoMapAny: ProtoMap {
+    /// Returns the full proto name of the packed message e.g. `entities.schema.Entity`
+    fn type_name() -> String;
+
+    /// Returns the type url of the packed message e.g. `type.googleapis.com/entities.schema.Entity`
+    fn type_url() -> String {
+        format!("{}{}", TYPE_URL_PREFIX, Self::type_name())
+    }
+
+    /// Converts a reference of [`Self`] to a packed `google.protobuf.Any` message
+    fn to_any(&self) -> Any;
+
+    /// Consumes [`Self`] and returns a packed `google.protobuf.Any` message
+    fn into_any(self) -> Any {
+        self.to_any()
+    }
+
+    /// Unpacks a `google.protobuf.Any` message and returns a [`Self`] or error if the type url does not match
+    /// or the conversion failed
+    fn from_any(any: Any) -> Result<Self, ProtoMapError>;
+}
+
+/// Returns the full proto name of a type url (the part after the last `/`)
+pub fn type_name_of_url(type_url: &str) -> &str {
+    type_url.rsplit_once('/').map_or(type_url, |(_, name)| name)
+}
+
+/// Returns the full proto name of a `prost` message (the message must implement [`prost::Name`], see
+/// `prost_build::Config::enable_type_names`)
+#[cfg(feature = "prost")]
+pub fn full_name<M: prost::Name>() -> String {
+    M::full_name()
+}
+
+/// Returns the full proto name of a `rust-protobuf` message
+#[cfg(feature = "protobuf")]
+pub fn full_name<M: protobuf::MessageFull>() -> String {
+    M::descriptor().full_name().to_owned()
+}
+
+/// Packs a proto message to a `google.protobuf.Any` message of `type_url`
+#[cfg(feature = "prost")]
+pub fn pack<M: prost::Message>(type_url: String, message: &M) -> Any {
+    Any {
+        type_url,
+        value: message.encode_to_vec(),
+    }
+}
+
+/// Packs a proto message to a `google.protobuf.Any` message of `type_url`
+#[cfg(feature = "protobuf")]
+pub fn pack<M: protobuf::Message>(type_url: String, message: &M) -> Any {
+    // Note: unlike `Message::write_to_bytes` the message is written without checking the proto2 required fields
+    let mut value = Vec::with_capacity(message.compute_size() as usize);
+    let mut os = protobuf::CodedOutputStream::vec(&mut value);
+    message
+        .write_to_with_cached_sizes(&mut os)
+        .and_then(|_| os.flush())
+        .expect("writing a message to a vector never fails");
+    drop(os);
+    Any {
+        type_url,
+        value,
+        ..Default::default()
+    }
+}
+
+/// Unpacks the proto message of a `google.protobuf.Any` message or fails if the type url does not refer to `type_name`
+#[cfg(feature = "prost")]
+pub fn unpack<M: prost::Message + Default>(any: Any, type_name: &str) -> Result<M, ProtoMapError> {
+    if type_name_of_url(&any.type_url) != type_name {
+        return Err(ProtoMapError::unknown_type_url(any.type_url));
+    }
+    M::decode(any.value.as_slice()).map_err(ProtoMapError::custom)
+}
+
+/// Unpacks the proto message of a `google.protobuf.Any` message or fails if the type url does not refer to `type_name`
+#[cfg(feature = "protobuf")]
+pub fn unpack<M: protobuf::Message>(any: Any, type_name: &str) -> Result<M, ProtoMapError> {
+    if type_name_of_url(&any.type_url) != type_name {
+        return Err(ProtoMapError::unknown_type_url(any.type_url));
+    }
+    M::parse_from_bytes(&any.value).map_err(ProtoMapError::custom)
+}
+
+type Unpack<T> = Box<dyn Fn(Any) -> Result<T, ProtoMapError> + Send + Sync>;
+
+/// Registry of [`ProtoMapAny`] types that unpacks `google.protobuf.Any` messages according to their type url.
+///
+/// The unpacked types are converted to `T` e.g. a `Box<dyn std::any::Any>` (the default) or an enum of the
+/// known types.
+///
+/// ```ignore
+/// let mut registry = AnyRegistry::<Event>::new();
+/// registry
+///     .register_with(Event::Created)
+///     .register_with(Event::Deleted);
+///
+/// let event = registry.unpack(any)?;
+/// ```
+pub struct AnyRegistry<T = Box<dyn std::any::Any + Send>> {
+    types: HashMap<String, Unpack<T>>,
+}
+
+impl<T: 'static> AnyRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            types: HashMap::new(),
+        }
+    }
+
+    /// Registers the type `M` converted to `T` with [`From`]
+    pub fn register<M>(&mut self) -> &mut Self
+    where
+        M: ProtoMapAny + 'static,
+        T: From<M>,
+    {
+        self.register_with(<T as From<M>>::from)
+    }
+
+    /// Registers the type `M` converted to `T` with `map` (e.g. an enum variant `Event::Created`)
+    pub fn register_with<M, F>(&mut self, map: F) -> &mut Self
+    where
+        M: ProtoMapAny + 'static,
+        F: Fn(M) -> T + Send + Sync + 'static,
+    {
+        self.types.insert(
+            M::type_name(),
+            Box::new(move |any| M::from_any(any).map(&map)),
+        );
+        self
+    }
+
+    /// Returns true if the type of a type url (or full proto name) is registered
+    pub fn contains(&self, type_url: &str) -> bool {
+        self.types.contains_key(type_name_of_url(type_url))
+    }
+
+    /// Unpacks a `google.protobuf.Any` message to the registered type of its type url
+    pub fn unpack(&self, any: Any) -> Result<T, ProtoMapError> {
+        match self.types.get(type_name_of_url(&any.type_url)) {
+            Some(unpack) => unpack(any),
+            None => Err(ProtoMapError::unknown_type_url(any.type_url)),
+        }
+    }
+}
+
+impl AnyRegistry<Box<dyn std::any::Any + Send>> {
+    /// Registers the type `M` unpacked as a `Box<dyn std::any::Any>` (see [`Box::downcast`])
+    pub fn register_boxed<M>(&mut self) -> &mut Self
+    where
+        M: ProtoMapAny + Send + 'static,
+    {
+        self.register_with(|value: M| Box::new(value) as Box<dyn std::any::Any + Send>)
+    }
+}
+
+impl<T: 'static> Default for AnyRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for AnyRegistry<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnyRegistry")
+            .field("types", &self.types.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,416 @@
+use std::fmt::{Debug, Display, Formatter};
+
+/// A single segment of a [`FieldPath`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A named proto field
+    Field(String),
+    /// The index of an element in a repeated field
+    Index(usize),
+    /// The key of an entry in a map field
+    Key(String),
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, "{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+            PathSegment::Key(key) => write!(f, "[{}]", key),
+        }
+    }
+}
+
+/// The path of proto fields that leads from the root message to the field that failed to map.
+///
+/// Displays as a dotted path e.g. `nested.first.status`, `entities[2].status` for repeated fields
+/// or `entities[foo].status` for map fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldPath(Vec<PathSegment>);
+
+impl FieldPath {
+    /// Returns the segments of the path starting from the root message
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    /// Returns true if the error occurred at the root message
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Prepends a segment (used while an error bubbles up through nested messages)
+    pub fn push_front(&mut self, segment: PathSegment) {
+        self.0.insert(0, segment);
+    }
+}
+
+impl Display for FieldPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            if i > 0 && matches!(segment, PathSegment::Field(_)) {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by `from_proto` and `from_scalar` conversions.
+///
+/// Every variant carries the [`FieldPath`] of the failed field, relative to the message
+/// that `from_proto` was called on.
+#[derive(Debug)]
+pub enum ProtoMapError {
+    /// A proto enumeration value that does not match any variant of the mapped enum
+    UnknownEnumValue {
+        /// The proto enumeration type
+        type_name: &'static str,
+        value: i32,
+        path: FieldPath,
+    },
+    /// A message field marked as `required` that is not set
+    MissingMessage {
+        /// The proto message that contains the missing field
+        type_name: &'static str,
+        path: FieldPath,
+    },
+    /// A `oneof` field without any member set
+    OneOfNotSet {
+        /// The proto message that contains the `oneof` field
+        type_name: &'static str,
+        path: FieldPath,
+    },
+    /// A number that does not fit into the target type
+    OutOfRange {
+        /// The target type of the conversion
+        type_name: &'static str,
+        value: String,
+        path: FieldPath,
+    },
+    /// Messages nested deeper than the configured recursion limit (see [`crate::with_recursion_limit`])
+    RecursionLimitExceeded { limit: usize, path: FieldPath },
+    /// A `google.protobuf.Any` message with a type url that does not match any of the expected types
+    UnknownTypeUrl { type_url: String, path: FieldPath },
+    /// A field mask path that does not select a mapped field of the proto message (see [`crate::field_mask`])
+    UnknownFieldMaskPath {
+        /// The proto message of the selected field
+        type_name: &'static str,
+        /// The name of the unknown field
+        field: String,
+        path: FieldPath,
+    },
+    /// A payload field of a `tagged` message that belongs to another tag than the set one
+    TaggedFieldMismatch {
+        /// The proto message of the tagged union
+        type_name: &'static str,
+        /// The set tag value
+        tag: i32,
+        path: FieldPath,
+    },
+    /// A field mask path that selects nested fields of a field that is overwritten only as a whole
+    /// (see [`crate::field_mask`])
+    InvalidFieldMaskPath {
+        /// The name of the selected nested field
+        field: String,
+        path: FieldPath,
+    },
+    /// Failure of a custom (`with` module or manual implementation) conversion
+    Custom {
+        source: anyhow::Error,
+        path: FieldPath,
+    },
+}
+
+impl ProtoMapError {
+    pub fn unknown_enum_value(type_name: &'static str, value: i32) -> Self {
+        Self::UnknownEnumValue {
+            type_name,
+            value,
+            path: Default::default(),
+        }
+    }
+
+    pub fn missing_message(type_name: &'static str) -> Self {
+        Self::MissingMessage {
+            type_name,
+            path: Default::default(),
+        }
+    }
+
+    pub fn one_of_not_set(type_name: &'static str) -> Self {
+        Self::OneOfNotSet {
+            type_name,
+            path: Default::default(),
+        }
+    }
+
+    pub fn out_of_range(type_name: &'static str, value: impl Display) -> Self {
+        Self::OutOfRange {
+            type_name,
+            value: value.to_string(),
+            path: Default::default(),
+        }
+    }
+
+    pub fn unknown_type_url(type_url: impl Into<String>) -> Self {
+        Self::UnknownTypeUrl {
+            type_url: type_url.into(),
+            path: Default::default(),
+        }
+    }
+
+    pub fn recursion_limit_exceeded(limit: usize) -> Self {
+        Self::RecursionLimitExceeded {
+            limit,
+            path: Default::default(),
+        }
+    }
+
+    pub fn tagged_field_mismatch(type_name: &'static str, tag: i32) -> Self {
+        Self::TaggedFieldMismatch {
+            type_name,
+            tag,
+            path: Default::default(),
+        }
+    }
+
+    pub fn unknown_field_mask_path(type_name: &'static str, field: impl Into<String>) -> Self {
+        Self::UnknownFieldMaskPath {
+            type_name,
+            field: field.into(),
+            path: Default::default(),
+        }
+    }
+
+    pub fn invalid_field_mask_path(field: impl Into<String>) -> Self {
+        Self::InvalidFieldMaskPath {
+            field: field.into(),
+            path: Default::default(),
+        }
+    }
+
+    pub fn custom(source: impl Into<anyhow::Error>) -> Self {
+        Self::Custom {
+            source: source.into(),
+            path: Default::default(),
+        }
+    }
+
+    /// Returns the path of the field that failed to map
+    pub fn path(&self) -> &FieldPath {
+        match self {
+            Self::UnknownEnumValue { path, .. }
+            | Self::MissingMessage { path, .. }
+            | Self::OneOfNotSet { path, .. }
+            | Self::OutOfRange { path, .. }
+            | Self::RecursionLimitExceeded { path, .. }
+            | Self::UnknownTypeUrl { path, .. }
+            | Self::TaggedFieldMismatch { path, .. }
+            | Self::UnknownFieldMaskPath { path, .. }
+            | Self::InvalidFieldMaskPath { path, .. }
+            | Self::Custom { path, .. } => path,
+        }
+    }
+
+    fn path_mut(&mut self) -> &mut FieldPath {
+        match self {
+            Self::UnknownEnumValue { path, .. }
+            | Self::MissingMessage { path, .. }
+            | Self::OneOfNotSet { path, .. }
+            | Self::OutOfRange { path, .. }
+            | Self::RecursionLimitExceeded { path, .. }
+            | Self::UnknownTypeUrl { path, .. }
+            | Self::TaggedFieldMismatch { path, .. }
+            | Self::UnknownFieldMaskPath { path, .. }
+            | Self::InvalidFieldMaskPath { path, .. }
+            | Self::Custom { path, .. } => path,
+        }
+    }
+
+    /// Prepends a field name to the error path
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.path_mut().push_front(PathSegment::Field(field.into()));
+        self
+    }
+
+    /// Prepends a repeated field element index to the error path
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.path_mut().push_front(PathSegment::Index(index));
+        self
+    }
+
+    /// Prepends a map field entry key to the error path
+    pub fn with_key(mut self, key: impl Display) -> Self {
+        self.path_mut().push_front(PathSegment::Key(key.to_string()));
+        self
+    }
+}
+
+impl Display for ProtoMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownEnumValue {
+                type_name, value, ..
+            } => write!(f, "unknown value {} for enumeration `{}`", value, type_name)?,
+            Self::MissingMessage { type_name, .. } => {
+                write!(f, "missing required message field of `{}`", type_name)?
+            }
+            Self::OneOfNotSet { type_name, .. } => {
+                write!(f, "no oneof member is set in `{}`", type_name)?
+            }
+            Self::OutOfRange {
+                type_name, value, ..
+            } => write!(f, "value {} is out of range for `{}`", value, type_name)?,
+            Self::RecursionLimitExceeded { limit, .. } => {
+                write!(f, "recursion limit of {} nested messages exceeded", limit)?
+            }
+            Self::UnknownTypeUrl { type_url, .. } => {
+                write!(f, "unknown type url `{}` of `google.protobuf.Any`", type_url)?
+            }
+            Self::TaggedFieldMismatch { type_name, tag, .. } => write!(
+                f,
+                "payload field of another tag than {} is set in `{}`",
+                tag, type_name
+            )?,
+            Self::UnknownFieldMaskPath {
+                type_name, field, ..
+            } => {
+                write!(f, "unknown field `{}` in field mask of `{}`", field, type_name)?
+            }
+            Self::InvalidFieldMaskPath { field, .. } => write!(
+                f,
+                "field mask selects nested field `{}` of a field that is overwritten only as a whole",
+                field
+            )?,
+            Self::Custom { source, .. } => write!(f, "{}", source)?,
+        }
+
+        let path = self.path();
+        if !path.is_empty() {
+            write!(f, " at `{}`", path)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ProtoMapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Custom { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Converts errors of custom (`with` module) conversions.
+///
+/// A [`ProtoMapError`] wrapped inside the [`anyhow::Error`] is recovered as is.
+impl From<anyhow::Error> for ProtoMapError {
+    fn from(value: anyhow::Error) -> Self {
+        match value.downcast::<ProtoMapError>() {
+            Ok(error) => error,
+            Err(source) => Self::Custom {
+                source,
+                path: Default::default(),
+            },
+        }
+    }
+}
+
+/// Errors returned by `try_from_proto_all` conversions.
+///
+/// Contains every failed field of the conversion (each one tagged with its [`FieldPath`])
+/// instead of just the first one.
+#[derive(Debug, Default)]
+pub struct ProtoMapErrors(Vec<ProtoMapError>);
+
+impl ProtoMapErrors {
+    /// Returns the collected errors in the order of the failed fields
+    pub fn errors(&self) -> &[ProtoMapError] {
+        &self.0
+    }
+
+    /// Consumes [`Self`] and returns the collected errors
+    pub fn into_errors(self) -> Vec<ProtoMapError> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, error: ProtoMapError) {
+        self.0.push(error)
+    }
+
+    /// Prepends a field name to the path of every collected error
+    pub fn with_field(self, field: impl Into<String>) -> Self {
+        let field = field.into();
+        Self(
+            self.0
+                .into_iter()
+                .map(|error| error.with_field(field.clone()))
+                .collect(),
+        )
+    }
+
+    /// Prepends a repeated field element index to the path of every collected error
+    pub fn with_index(self, index: usize) -> Self {
+        Self(self.0.into_iter().map(|error| error.with_index(index)).collect())
+    }
+
+    /// Prepends a map field entry key to the path of every collected error
+    pub fn with_key(self, key: impl Display) -> Self {
+        let key = key.to_string();
+        Self(self.0.into_iter().map(|error| error.with_key(&key)).collect())
+    }
+
+    /// Runs a single field conversion and keeps its errors (if any) so that the conversion
+    /// of the remaining fields can proceed.
+    ///
+    /// Returns `None` if the field conversion failed.
+    pub fn collect<T>(&mut self, f: impl FnOnce() -> Result<T, ProtoMapErrors>) -> Option<T> {
+        match f() {
+            Ok(value) => Some(value),
+            Err(errors) => {
+                self.0.extend(errors.0);
+                None
+            }
+        }
+    }
+}
+
+impl From<ProtoMapError> for ProtoMapErrors {
+    fn from(value: ProtoMapError) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl IntoIterator for ProtoMapErrors {
+    type Item = ProtoMapError;
+    type IntoIter = std::vec::IntoIter<ProtoMapError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Display for ProtoMapErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ProtoMapErrors {}
@@ -0,0 +1,259 @@
+//! Partial updates of mapped types with `google.protobuf.FieldMask` messages.
+//!
+//! Types that derive `ProtoMap` with the `field_mask` attribute implement [`ProtoMapFieldMask`]. The paths of the
+//! masks are proto field names (following the `rename` and `skip` attributes) e.g. `name` or `first.status`.
+use crate::{ProtoMap, ProtoMapError, ProtoMapOneOf};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// The `google.protobuf.FieldMask` message of the enabled proto framework feature
+#[cfg(feature = "prost")]
+pub use prost_types::FieldMask;
+/// The `google.protobuf.FieldMask` message of the enabled proto framework feature
+#[cfg(feature = "protobuf")]
+pub use protobuf::well_known_types::field_mask::FieldMask;
+
+/// Types that merge the fields of a proto message selected by a `google.protobuf.FieldMask`.
+///
+/// The fields of nested message types are selected with nested paths (e.g. `first.status`), every other field
+/// (scalars, repeated, map and `with` fields) is overwritten as a whole.
+pub trait ProtoMapFieldMask: ProtoMap {
+    /// Overwrites the fields of [`Self`] selected by the paths of `mask` with the fields of a proto
+    /// [`Self::ProtoStruct`] or fails if a path does not refer to a mapped field or the conversion failed.
+    ///
+    /// Note: the fields of the mask that are not set in `proto` are overwritten with their default values (the method
+    /// is not named `merge_from_proto` to not clash with [`crate::ProtoMapMerge::merge_from_proto`]).
+    fn merge_from_proto_with_mask(
+        &mut self,
+        proto: Self::ProtoStruct,
+        mask: &FieldMask,
+    ) -> Result<(), ProtoMapError> {
+        let paths = FieldMaskPaths::new(mask.paths.iter().map(String::as_str));
+        self.merge_from_proto_paths(proto, &paths)
+    }
+
+    /// Overwrites the fields of [`Self`] selected by a tree of field mask paths (see [`Self::merge_from_proto_with_mask`])
+    fn merge_from_proto_paths(
+        &mut self,
+        proto: Self::ProtoStruct,
+        paths: &FieldMaskPaths,
+    ) -> Result<(), ProtoMapError>;
+
+    /// Returns the field mask of the fields that differ between two values (see [`field_mask_between`])
+    fn field_mask_between(a: &Self, b: &Self) -> FieldMask {
+        let mut paths = Vec::new();
+        Self::push_field_mask_paths(a, b, "", &mut paths);
+        new_field_mask(paths)
+    }
+
+    /// Appends the paths (prefixed by `prefix`) of the fields that differ between two values
+    fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>);
+}
+
+/// Returns the field mask of the fields that differ between two values.
+///
+/// Merging `b` with the returned mask into `a` (see [`ProtoMapFieldMask::merge_from_proto_with_mask`]) results in `b`.
+pub fn field_mask_between<T: ProtoMapFieldMask>(a: &T, b: &T) -> FieldMask {
+    T::field_mask_between(a, b)
+}
+
+#[cfg(feature = "prost")]
+fn new_field_mask(paths: Vec<String>) -> FieldMask {
+    FieldMask { paths }
+}
+
+#[cfg(feature = "protobuf")]
+fn new_field_mask(paths: Vec<String>) -> FieldMask {
+    FieldMask {
+        paths,
+        ..Default::default()
+    }
+}
+
+/// Fields of `one_of` enums (optional or not) that are selected by the names of their members in field masks.
+///
+/// The `oneof` is overwritten as a whole if any of its members is selected.
+pub trait OneOfField {
+    /// Returns the proto field names of the `oneof` members
+    fn one_of_members(&self) -> &'static [&'static str];
+
+    /// Returns the proto field name of the set member (`None` if the `oneof` is unset)
+    fn one_of_member(&self) -> Option<&'static str>;
+}
+
+impl<T: ProtoMapOneOf> OneOfField for T {
+    fn one_of_members(&self) -> &'static [&'static str] {
+        T::MEMBERS
+    }
+
+    fn one_of_member(&self) -> Option<&'static str> {
+        ProtoMapOneOf::one_of_member(self)
+    }
+}
+
+impl<T: ProtoMapOneOf> OneOfField for Option<T> {
+    fn one_of_members(&self) -> &'static [&'static str] {
+        T::MEMBERS
+    }
+
+    fn one_of_member(&self) -> Option<&'static str> {
+        self.as_ref().and_then(ProtoMapOneOf::one_of_member)
+    }
+}
+
+/// Appends the path (prefixed by `prefix`) of the member of a `oneof` field that differs between two values (the
+/// member of `b` or the member of `a` if the `oneof` of `b` is unset)
+pub fn push_one_of_paths<T: OneOfField + PartialEq>(
+    a: &T,
+    b: &T,
+    prefix: &str,
+    paths: &mut Vec<String>,
+) {
+    if a == b {
+        return;
+    }
+    if let Some(member) = b.one_of_member().or_else(|| a.one_of_member()) {
+        paths.push(join_path(prefix, member));
+    }
+}
+
+/// Returns the path of `field` nested in `prefix` (a root field for an empty prefix)
+pub fn join_path(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_owned()
+    } else {
+        format!("{}.{}", prefix, field)
+    }
+}
+
+/// The tree of the paths of a field mask.
+///
+/// A selected field either selects the whole field or the (nested) paths of its fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMaskPaths {
+    all: bool,
+    fields: BTreeMap<String, FieldMaskPaths>,
+}
+
+static ALL: FieldMaskPaths = FieldMaskPaths {
+    all: true,
+    fields: BTreeMap::new(),
+};
+
+impl FieldMaskPaths {
+    /// Returns the tree of dotted paths e.g. `["name", "first.status"]` (paths nested in a selected field are ignored)
+    pub fn new<'a>(paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut tree = Self::default();
+        for path in paths {
+            tree.insert(path.split('.').filter(|segment| !segment.is_empty()));
+        }
+        tree
+    }
+
+    fn insert<'a>(&mut self, mut segments: impl Iterator<Item = &'a str>) {
+        if self.all {
+            return;
+        }
+        match segments.next() {
+            Some(segment) => self
+                .fields
+                .entry(segment.to_owned())
+                .or_default()
+                .insert(segments),
+            None => {
+                self.all = true;
+                self.fields.clear();
+            }
+        }
+    }
+
+    /// Returns true if the whole field is selected
+    pub fn is_all(&self) -> bool {
+        self.all
+    }
+
+    /// Returns the paths of a selected field or `None` if the field is not selected
+    pub fn field(&self, name: &str) -> Option<&FieldMaskPaths> {
+        if self.all {
+            Some(&ALL)
+        } else {
+            self.fields.get(name)
+        }
+    }
+
+    /// Fails if the paths select any field that is not one of `fields` of the proto message `type_name`
+    pub fn check_fields(
+        &self,
+        fields: &[&str],
+        type_name: &'static str,
+    ) -> Result<(), ProtoMapError> {
+        match self
+            .fields
+            .keys()
+            .find(|name| !fields.contains(&name.as_str()))
+        {
+            Some(name) => Err(ProtoMapError::unknown_field_mask_path(type_name, name)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns true if the paths select any of the `members` of a `oneof` field or fails if they select nested fields
+    /// of a member (the `oneof` is overwritten only as a whole)
+    pub fn one_of_selected(&self, members: &[&str]) -> Result<bool, ProtoMapError> {
+        let mut selected = false;
+        for member in members {
+            if let Some(paths) = self.field(member) {
+                paths.check_all().map_err(|e| e.with_field(*member))?;
+                selected = true;
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Fails if the paths select nested fields of a field that is overwritten only as a whole
+    pub fn check_all(&self) -> Result<(), ProtoMapError> {
+        match self.fields.keys().next() {
+            Some(name) if !self.all => Err(ProtoMapError::invalid_field_mask_path(name)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Boxed fields (e.g. of recursive messages) merge as their inner type
+impl<T: ProtoMapFieldMask> ProtoMapFieldMask for Box<T> {
+    fn merge_from_proto_paths(
+        &mut self,
+        proto: Self::ProtoStruct,
+        paths: &FieldMaskPaths,
+    ) -> Result<(), ProtoMapError> {
+        T::merge_from_proto_paths(self, proto, paths)
+    }
+
+    fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+        T::push_field_mask_paths(a, b, prefix, paths)
+    }
+}
+
+macro_rules! impl_proto_map_field_mask_shared {
+    ( $( $name:ident ),* )=> {
+        $(
+            /// Shared fields merge as their inner type (the value is cloned if it is shared)
+            impl<T: ProtoMapFieldMask + Clone> ProtoMapFieldMask for $name<T> {
+                fn merge_from_proto_paths(
+                    &mut self,
+                    proto: Self::ProtoStruct,
+                    paths: &FieldMaskPaths,
+                ) -> Result<(), ProtoMapError> {
+                    T::merge_from_proto_paths($name::make_mut(self), proto, paths)
+                }
+
+                fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+                    T::push_field_mask_paths(a, b, prefix, paths)
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_map_field_mask_shared! { Arc, Rc }
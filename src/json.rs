@@ -0,0 +1,235 @@
+//! Maps [`serde_json::Value`] and [`serde_json::Map`] fields to the `google.protobuf.Value` and
+//! `google.protobuf.Struct` well known types (arrays are mapped to `google.protobuf.ListValue`).
+//!
+//! Proto values have only `double` numbers so integers that do not fit exactly to an `f64` are mapped to `string`
+//! values (like the proto JSON mapping of 64 bit integers) instead of losing precision. Note that this mapping does
+//! not round trip: such integers are converted back to [`Value::String`] values. Whole proto numbers are
+//! converted back to integers and non finite numbers (`NaN`, `inf`) fail with [`ProtoMapError::OutOfRange`].
+use crate::field_mask::{FieldMaskPaths, ProtoMapFieldMask};
+use crate::merge::ProtoMapMerge;
+use crate::{ProtoMap, ProtoMapError, RecursionGuard};
+use serde_json::{Map, Number, Value};
+
+#[cfg(feature = "prost")]
+use prost_types::{
+    value::Kind, ListValue as ProtoListValue, Struct as ProtoStruct, Value as ProtoValue,
+};
+#[cfg(feature = "protobuf")]
+use protobuf::well_known_types::struct_::{
+    value::Kind, ListValue as ProtoListValue, NullValue, Struct as ProtoStruct, Value as ProtoValue,
+};
+
+const TYPE_NAME: &str = "serde_json::Number";
+
+/// Integers of absolute value up to 2^53 are exactly representable by `f64`
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
+#[cfg(feature = "prost")]
+fn null_kind() -> Kind {
+    Kind::NullValue(prost_types::NullValue::NullValue as i32)
+}
+
+#[cfg(feature = "protobuf")]
+fn null_kind() -> Kind {
+    Kind::NullValue(NullValue::NULL_VALUE.into())
+}
+
+#[cfg(feature = "prost")]
+fn new_value(kind: Kind) -> ProtoValue {
+    ProtoValue { kind: Some(kind) }
+}
+
+#[cfg(feature = "protobuf")]
+fn new_value(kind: Kind) -> ProtoValue {
+    ProtoValue {
+        kind: Some(kind),
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "prost")]
+fn new_list(values: Vec<ProtoValue>) -> ProtoListValue {
+    ProtoListValue { values }
+}
+
+#[cfg(feature = "protobuf")]
+fn new_list(values: Vec<ProtoValue>) -> ProtoListValue {
+    ProtoListValue {
+        values,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "prost")]
+fn new_struct(fields: impl Iterator<Item = (String, ProtoValue)>) -> ProtoStruct {
+    ProtoStruct {
+        fields: fields.collect(),
+    }
+}
+
+#[cfg(feature = "protobuf")]
+fn new_struct(fields: impl Iterator<Item = (String, ProtoValue)>) -> ProtoStruct {
+    ProtoStruct {
+        fields: fields.collect(),
+        ..Default::default()
+    }
+}
+
+/// Returns the `f64` of numbers that are exactly representable or the `string` kind of the rest of the integers
+/// (converted back to [`Value::String`] by `from_proto`)
+fn number_kind(number: &Number) -> Kind {
+    match (number.as_u64(), number.as_i64()) {
+        (Some(value), _) if value > MAX_SAFE_INTEGER => Kind::StringValue(value.to_string()),
+        (_, Some(value)) if value.unsigned_abs() > MAX_SAFE_INTEGER => {
+            Kind::StringValue(value.to_string())
+        }
+        // Note: numbers are always finite `f64` values without the `arbitrary_precision` feature of `serde_json`
+        _ => Kind::NumberValue(number.as_f64().unwrap_or_default()),
+    }
+}
+
+/// Converts whole numbers to integers (when in range) and fails for non finite numbers
+fn number_from_proto(proto: f64) -> Result<Number, ProtoMapError> {
+    if proto.fract() == 0.0 && proto.abs() <= MAX_SAFE_INTEGER as f64 {
+        Ok(Number::from(proto as i64))
+    } else {
+        Number::from_f64(proto).ok_or_else(|| ProtoMapError::out_of_range(TYPE_NAME, proto))
+    }
+}
+
+fn kind_from_value(value: &Value) -> Kind {
+    match value {
+        Value::Null => null_kind(),
+        Value::Bool(value) => Kind::BoolValue(*value),
+        Value::Number(number) => number_kind(number),
+        Value::String(value) => Kind::StringValue(value.clone()),
+        Value::Array(values) => {
+            Kind::ListValue(new_list(values.iter().map(ProtoMap::to_proto).collect()))
+        }
+        Value::Object(fields) => Kind::StructValue(fields.to_proto()),
+    }
+}
+
+fn kind_into_value(value: Value) -> Kind {
+    match value {
+        Value::String(value) => Kind::StringValue(value),
+        Value::Array(values) => Kind::ListValue(new_list(
+            values.into_iter().map(ProtoMap::into_proto).collect(),
+        )),
+        Value::Object(fields) => Kind::StructValue(fields.into_proto()),
+        value => kind_from_value(&value),
+    }
+}
+
+/// `google.protobuf.Value` fields without a kind are converted to [`Value::Null`].
+///
+/// Nested lists and structs count to the [`recursion_limit`](crate::recursion_limit) of the conversion.
+impl ProtoMap for Value {
+    type ProtoStruct = ProtoValue;
+
+    fn to_proto(&self) -> Self::ProtoStruct {
+        new_value(kind_from_value(self))
+    }
+
+    fn into_proto(self) -> Self::ProtoStruct {
+        new_value(kind_into_value(self))
+    }
+
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
+        let _guard = RecursionGuard::enter()?;
+        let value = match proto.kind {
+            None | Some(Kind::NullValue(_)) => Value::Null,
+            Some(Kind::BoolValue(value)) => Value::Bool(value),
+            Some(Kind::NumberValue(value)) => Value::Number(number_from_proto(value)?),
+            Some(Kind::StringValue(value)) => Value::String(value),
+            Some(Kind::ListValue(list)) => Value::Array(
+                list.values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| Value::from_proto(value).map_err(|e| e.with_index(index)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Some(Kind::StructValue(fields)) => Value::Object(Map::from_proto(fields)?),
+            // Note: the kinds of `rust-protobuf` are non exhaustive
+            #[cfg(feature = "protobuf")]
+            Some(_) => {
+                return Err(ProtoMapError::custom(anyhow::anyhow!(
+                    "unsupported kind of `google.protobuf.Value`"
+                )))
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl ProtoMap for Map<String, Value> {
+    type ProtoStruct = ProtoStruct;
+
+    fn to_proto(&self) -> Self::ProtoStruct {
+        new_struct(
+            self.iter()
+                .map(|(key, value)| (key.clone(), value.to_proto())),
+        )
+    }
+
+    fn into_proto(self) -> Self::ProtoStruct {
+        new_struct(
+            self.into_iter()
+                .map(|(key, value)| (key, value.into_proto())),
+        )
+    }
+
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
+        proto
+            .fields
+            .into_iter()
+            .map(|(key, value)| match Value::from_proto(value) {
+                Ok(value) => Ok((key, value)),
+                Err(e) => Err(e.with_key(key)),
+            })
+            .collect()
+    }
+}
+
+macro_rules! impl_proto_map_field_mask_whole {
+    ( $( $name:ty ),* )=> {
+        $(
+            /// JSON values are merged by field masks only as a whole (the keys of objects are not field mask paths)
+            impl ProtoMapFieldMask for $name {
+                fn merge_from_proto_paths(
+                    &mut self,
+                    proto: Self::ProtoStruct,
+                    paths: &FieldMaskPaths,
+                ) -> Result<(), ProtoMapError> {
+                    paths.check_all()?;
+                    *self = Self::from_proto(proto)?;
+                    Ok(())
+                }
+
+                fn push_field_mask_paths(a: &Self, b: &Self, prefix: &str, paths: &mut Vec<String>) {
+                    if a != b {
+                        paths.push(prefix.to_owned());
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_map_field_mask_whole! { Value, Map<String, Value> }
+
+/// Values replace the current value (the kind of `google.protobuf.Value` is a oneof)
+impl ProtoMapMerge for Value {
+    fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> Result<(), ProtoMapError> {
+        *self = Value::from_proto(proto)?;
+        Ok(())
+    }
+}
+
+/// Objects upsert their fields (the fields of `google.protobuf.Struct` are a map)
+impl ProtoMapMerge for Map<String, Value> {
+    fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> Result<(), ProtoMapError> {
+        self.extend(Map::from_proto(proto)?);
+        Ok(())
+    }
+}
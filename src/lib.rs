@@ -1,5 +1,23 @@
+#[cfg(any(feature = "prost", feature = "protobuf"))]
+pub mod any;
+mod error;
+#[cfg(any(feature = "prost", feature = "protobuf"))]
+pub mod field_mask;
+#[cfg(all(feature = "serde_json", any(feature = "prost", feature = "protobuf")))]
+mod json;
+pub mod merge;
 mod proto_mapper;
+mod recursion;
+#[cfg(any(feature = "prost", feature = "protobuf"))]
+pub use any::{AnyRegistry, ProtoMapAny};
+pub use error::*;
+#[cfg(any(feature = "prost", feature = "protobuf"))]
+pub use field_mask::{field_mask_between, ProtoMapFieldMask};
+pub use merge::ProtoMapMerge;
 pub use proto_mapper::*;
+pub use recursion::*;
+#[cfg(any(feature = "prost", feature = "protobuf"))]
+pub mod with;
 pub mod derive {
     pub use proto_mapper_derive::ProtoMap;
 }
@@ -0,0 +1,54 @@
+//! Merging of proto messages into existing values with the protobuf merge rules.
+//!
+//! Types that derive `ProtoMap` with the `merge` attribute implement [`ProtoMapMerge`].
+use crate::{ProtoMap, ProtoMapError};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Types that merge proto messages into an existing value (e.g. to layer configuration overlays or streaming
+/// deltas onto a domain state).
+///
+/// The fields are merged with the protobuf merge rules:
+/// - set scalars overwrite (default values of fields without presence are not set)
+/// - set nested messages merge recursively
+/// - repeated fields append
+/// - maps upsert their entries
+/// - set oneofs replace the current variant
+///
+/// Note: unlike the protobuf merge rules, a set oneof replaces the current variant as a whole even if the member
+/// is the same (the messages of the same member are not merged recursively).
+pub trait ProtoMapMerge: ProtoMap {
+    /// Merges a proto [`Self::ProtoStruct`] into [`Self`] or fails if the conversion failed (the fields merged
+    /// before the failure remain merged)
+    fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> Result<(), ProtoMapError>;
+}
+
+/// Appends the elements of repeated fields or upserts the entries of map fields
+pub fn extend<C>(target: &mut C, values: C)
+where
+    C: IntoIterator + Extend<<C as IntoIterator>::Item>,
+{
+    target.extend(values)
+}
+
+/// Boxed fields (e.g. of recursive messages) merge as their inner type
+impl<T: ProtoMapMerge> ProtoMapMerge for Box<T> {
+    fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> Result<(), ProtoMapError> {
+        T::merge_from_proto(self, proto)
+    }
+}
+
+macro_rules! impl_proto_map_merge_shared {
+    ( $( $name:ident ),* )=> {
+        $(
+            /// Shared fields merge as their inner type (the value is cloned if it is shared)
+            impl<T: ProtoMapMerge + Clone> ProtoMapMerge for $name<T> {
+                fn merge_from_proto(&mut self, proto: Self::ProtoStruct) -> Result<(), ProtoMapError> {
+                    T::merge_from_proto($name::make_mut(self), proto)
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_map_merge_shared! { Arc, Rc }
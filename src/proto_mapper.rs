@@ -1,4 +1,7 @@
-use anyhow::Error;
+use crate::{ProtoMapError, ProtoMapErrors};
+use std::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
+use std::rc::Rc;
+use std::sync::Arc;
 
 pub trait ProtoScalar: Sized + private::Sealed {
     fn has_value(&self) -> bool;
@@ -22,8 +25,13 @@ pub trait ProtoMapScalar<P: ProtoScalar>: Sized {
     /// Converts a reference of [`Self`] to a [`ProtoScalar`]
     fn to_scalar(&self) -> P;
 
+    /// Consumes [`Self`] and returns a [`ProtoScalar`] (avoids cloning for owned types e.g. `String`)
+    fn into_scalar(self) -> P {
+        self.to_scalar()
+    }
+
     /// Consumes a [`ProtoScalar`] and returns a [`Self`] or error in the conversion failed
-    fn from_scalar(proto: P) -> Result<Self, anyhow::Error>;
+    fn from_scalar(proto: P) -> Result<Self, ProtoMapError>;
 }
 
 pub trait ProtoMap
@@ -34,8 +42,111 @@ where
     /// Converts a reference of [`Self`] struct to proto [`Self::ProtoStruct`]
     fn to_proto(&self) -> Self::ProtoStruct;
 
+    /// Consumes [`Self`] struct and returns proto [`Self::ProtoStruct`] moving (instead of cloning) the field values
+    fn into_proto(self) -> Self::ProtoStruct {
+        self.to_proto()
+    }
+
     /// Consumes a proto [`Self::ProtoStruct`] and returns a [`Self`] struct or error in the conversion failed
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error>;
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError>;
+
+    /// Consumes a proto [`Self::ProtoStruct`] and returns a [`Self`] struct or all the errors of the conversion.
+    ///
+    /// Unlike [`ProtoMap::from_proto`] the conversion does not stop at the first failed field.
+    fn try_from_proto_all(proto: Self::ProtoStruct) -> Result<Self, ProtoMapErrors> {
+        Self::from_proto(proto).map_err(ProtoMapErrors::from)
+    }
+}
+
+/// Enums mapped to the generated enum of a proto `oneof` field (the `one_of` attributed enums).
+///
+/// Struct fields of these enums marked with `#[proto_map(one_of = "...")]` map to a `oneof` field of the parent
+/// message that may mix the `oneof` with any other fields. The conversions use the value of the `oneof` field
+/// (the `Option` of the generated enum), an unset `oneof` maps to the `not_set` policy of the enum.
+pub trait ProtoMapOneOf
+where
+    Self: Sized,
+{
+    /// The enum generated for the `oneof` field e.g. `proto::hierarchy_entity::Data`
+    type ProtoOneOf;
+
+    /// The proto field names of the `oneof` members (the paths of the `oneof` field in field masks)
+    const MEMBERS: &'static [&'static str];
+
+    /// Returns the proto field name of the member of [`Self`] (`None` if it leaves the `oneof` unset)
+    fn one_of_member(&self) -> Option<&'static str>;
+
+    /// Converts a reference of [`Self`] to the value of the `oneof` field (`None` leaves the `oneof` unset)
+    fn to_one_of(&self) -> Option<Self::ProtoOneOf>;
+
+    /// Consumes [`Self`] and returns the value of the `oneof` field moving (instead of cloning) the member
+    fn into_one_of(self) -> Option<Self::ProtoOneOf> {
+        self.to_one_of()
+    }
+
+    /// Consumes the value of the `oneof` field and returns a [`Self`] or error in the conversion failed
+    fn from_one_of(proto: Option<Self::ProtoOneOf>) -> Result<Self, ProtoMapError>;
+
+    /// Consumes the value of the `oneof` field and returns a [`Self`] or all the errors of the conversion
+    fn try_from_one_of_all(proto: Option<Self::ProtoOneOf>) -> Result<Self, ProtoMapErrors> {
+        Self::from_one_of(proto).map_err(ProtoMapErrors::from)
+    }
+}
+
+/// Returns the `oneof` member of a message built from the default message updated by `set`.
+///
+/// Internal to the derive macro (not part of the public API): used by the variants of `one_of` enums with named
+/// fields that map to the message of their member e.g. `Shape::Circle { radius }` to
+/// `shape::Shape::Circle(Circle { radius })`. The generated code only knows the member, so the function infers the
+/// type of the member message for `set`.
+#[doc(hidden)]
+pub fn to_one_of_member<T: Default, M>(member: fn(T) -> M, set: impl FnOnce(&mut T)) -> M {
+    let mut proto = T::default();
+    set(&mut proto);
+    member(proto)
+}
+
+/// Consumes the message of a `oneof` member and returns the result of `from`.
+///
+/// Internal to the derive macro (not part of the public API): the counterpart of [`to_one_of_member`] for the
+/// variants of `one_of` enums with named fields, that infers the type of the member message for `from`.
+#[doc(hidden)]
+pub fn from_one_of_member<T, V, E>(proto: T, from: impl FnOnce(T) -> Result<V, E>) -> Result<V, E> {
+    from(proto)
+}
+
+/// `rust-protobuf` enumerations mapped from and to the possibly unknown values of `EnumOrUnknown` fields.
+///
+/// Enumerations with an `unknown` variant keep the unknown values, enumerations with an `other` variant map them
+/// to that variant and the rest fail with [`ProtoMapError::UnknownEnumValue`].
+#[cfg(feature = "protobuf")]
+pub trait ProtoMapEnum: ProtoMap
+where
+    Self::ProtoStruct: protobuf::Enum,
+{
+    /// Converts a reference of [`Self`] to a possibly unknown enumeration value
+    fn to_enum_or_unknown(&self) -> protobuf::EnumOrUnknown<Self::ProtoStruct> {
+        protobuf::EnumOrUnknown::new(self.to_proto())
+    }
+
+    /// Consumes [`Self`] and returns a possibly unknown enumeration value
+    fn into_enum_or_unknown(self) -> protobuf::EnumOrUnknown<Self::ProtoStruct> {
+        self.to_enum_or_unknown()
+    }
+
+    /// Consumes a possibly unknown enumeration value and returns a [`Self`] or error if the conversion failed
+    /// (unknown values fail with [`ProtoMapError::UnknownEnumValue`])
+    fn from_enum_or_unknown(
+        proto: protobuf::EnumOrUnknown<Self::ProtoStruct>,
+    ) -> Result<Self, ProtoMapError> {
+        match proto.enum_value() {
+            Ok(value) => Self::from_proto(value),
+            Err(value) => Err(ProtoMapError::unknown_enum_value(
+                <Self::ProtoStruct as protobuf::Enum>::NAME,
+                value,
+            )),
+        }
+    }
 }
 
 macro_rules! impl_proto_scalar {
@@ -77,7 +188,7 @@ macro_rules! impl_proto_convert_scalar {
                 fn to_scalar(&self) -> $name {
                     *self
                 }
-                fn from_scalar(proto: $name) -> Result<Self, Error> {
+                fn from_scalar(proto: $name) -> Result<Self, ProtoMapError> {
                     Ok(proto)
                 }
             }
@@ -92,7 +203,11 @@ impl ProtoMapScalar<String> for String {
         self.clone()
     }
 
-    fn from_scalar(proto: String) -> Result<Self, Error> {
+    fn into_scalar(self) -> String {
+        self
+    }
+
+    fn from_scalar(proto: String) -> Result<Self, ProtoMapError> {
         Ok(proto)
     }
 }
@@ -102,7 +217,128 @@ impl ProtoMapScalar<Vec<u8>> for Vec<u8> {
         self.clone()
     }
 
-    fn from_scalar(proto: Vec<u8>) -> Result<Self, Error> {
+    fn into_scalar(self) -> Vec<u8> {
+        self
+    }
+
+    fn from_scalar(proto: Vec<u8>) -> Result<Self, ProtoMapError> {
         Ok(proto)
     }
 }
+
+/// Narrower integer types are widened to the proto scalar and checked against their range when narrowed back
+macro_rules! impl_proto_convert_narrow_scalar {
+    ( $( $name:tt => $proto:tt ),* )=> {
+        $(
+            impl ProtoMapScalar<$proto> for $name {
+                fn to_scalar(&self) -> $proto {
+                    $proto::from(*self)
+                }
+                fn from_scalar(proto: $proto) -> Result<Self, ProtoMapError> {
+                    $name::try_from(proto).map_err(|_| ProtoMapError::out_of_range(stringify!($name), proto))
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_convert_narrow_scalar! { u8 => u32, u16 => u32, i8 => i32, i16 => i32 }
+
+/// Pointer sized integers are mapped to 64 bit proto scalars (checked on 32 bit targets)
+macro_rules! impl_proto_convert_size_scalar {
+    ( $( $name:tt => $proto:tt ),* )=> {
+        $(
+            impl ProtoMapScalar<$proto> for $name {
+                fn to_scalar(&self) -> $proto {
+                    *self as $proto
+                }
+                fn from_scalar(proto: $proto) -> Result<Self, ProtoMapError> {
+                    $name::try_from(proto).map_err(|_| ProtoMapError::out_of_range(stringify!($name), proto))
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_convert_size_scalar! { usize => u64, isize => i64 }
+
+/// Non zero integers are mapped to proto scalars of the same width (zero values are out of range)
+macro_rules! impl_proto_convert_non_zero_scalar {
+    ( $( $name:tt => $proto:tt ),* )=> {
+        $(
+            impl ProtoMapScalar<$proto> for $name {
+                fn to_scalar(&self) -> $proto {
+                    self.get()
+                }
+                fn from_scalar(proto: $proto) -> Result<Self, ProtoMapError> {
+                    $name::new(proto).ok_or_else(|| ProtoMapError::out_of_range(stringify!($name), proto))
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_convert_non_zero_scalar! {
+    NonZeroU32 => u32, NonZeroU64 => u64, NonZeroI32 => i32, NonZeroI64 => i64
+}
+
+/// Characters are mapped to their unicode code point (invalid code points are out of range)
+impl ProtoMapScalar<u32> for char {
+    fn to_scalar(&self) -> u32 {
+        u32::from(*self)
+    }
+
+    fn from_scalar(proto: u32) -> Result<Self, ProtoMapError> {
+        char::from_u32(proto).ok_or_else(|| ProtoMapError::out_of_range("char", proto))
+    }
+}
+
+/// Boxed fields (e.g. of recursive messages) are mapped as their inner type
+impl<T: ProtoMap> ProtoMap for Box<T> {
+    type ProtoStruct = T::ProtoStruct;
+
+    fn to_proto(&self) -> Self::ProtoStruct {
+        T::to_proto(self)
+    }
+
+    fn into_proto(self) -> Self::ProtoStruct {
+        T::into_proto(*self)
+    }
+
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
+        T::from_proto(proto).map(Box::new)
+    }
+
+    fn try_from_proto_all(proto: Self::ProtoStruct) -> Result<Self, ProtoMapErrors> {
+        T::try_from_proto_all(proto).map(Box::new)
+    }
+}
+
+macro_rules! impl_proto_map_shared {
+    ( $( $name:ident ),* )=> {
+        $(
+            /// Shared fields are mapped as their inner type (`into_proto` moves the value only if it is not shared)
+            impl<T: ProtoMap> ProtoMap for $name<T> {
+                type ProtoStruct = T::ProtoStruct;
+
+                fn to_proto(&self) -> Self::ProtoStruct {
+                    T::to_proto(self)
+                }
+
+                fn into_proto(self) -> Self::ProtoStruct {
+                    $name::try_unwrap(self).map_or_else(|shared| shared.to_proto(), T::into_proto)
+                }
+
+                fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
+                    T::from_proto(proto).map($name::new)
+                }
+
+                fn try_from_proto_all(proto: Self::ProtoStruct) -> Result<Self, ProtoMapErrors> {
+                    T::try_from_proto_all(proto).map($name::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_proto_map_shared! { Arc, Rc }
@@ -0,0 +1,61 @@
+use crate::ProtoMapError;
+use std::cell::Cell;
+
+/// The default maximum nesting depth of the messages converted by `from_proto` (same as the `prost` decoding limit)
+pub const DEFAULT_RECURSION_LIMIT: usize = 100;
+
+thread_local! {
+    static RECURSION_DEPTH: Cell<usize> = const { Cell::new(0) };
+    static RECURSION_LIMIT: Cell<usize> = const { Cell::new(DEFAULT_RECURSION_LIMIT) };
+}
+
+/// Returns the maximum nesting depth of the messages converted by `from_proto` and `try_from_proto_all` in the
+/// current thread
+pub fn recursion_limit() -> usize {
+    RECURSION_LIMIT.with(Cell::get)
+}
+
+/// Runs `f` with a maximum nesting depth of the messages converted by `from_proto` and `try_from_proto_all`.
+///
+/// Deeper messages (e.g. of untrusted input with recursive messages) fail with
+/// [`ProtoMapError::RecursionLimitExceeded`] instead of overflowing the stack. The limit applies only to the
+/// conversions of `f` in the current thread, the previous limit is restored when `f` returns (or panics).
+pub fn with_recursion_limit<R>(limit: usize, f: impl FnOnce() -> R) -> R {
+    let _restore = RestoreRecursionLimit(RECURSION_LIMIT.with(|current| current.replace(limit)));
+    f()
+}
+
+/// Restores the previous recursion limit when dropped
+struct RestoreRecursionLimit(usize);
+
+impl Drop for RestoreRecursionLimit {
+    fn drop(&mut self) {
+        RECURSION_LIMIT.with(|current| current.set(self.0))
+    }
+}
+
+/// Tracks the nesting depth of the running conversion in the current thread.
+///
+/// Implementations generated by the derive macro enter a new level for every converted message,
+/// the level is left when the guard is dropped.
+#[doc(hidden)]
+pub struct RecursionGuard(());
+
+impl RecursionGuard {
+    pub fn enter() -> Result<Self, ProtoMapError> {
+        let limit = recursion_limit();
+        RECURSION_DEPTH.with(|depth| {
+            if depth.get() >= limit {
+                return Err(ProtoMapError::recursion_limit_exceeded(limit));
+            }
+            depth.set(depth.get() + 1);
+            Ok(Self(()))
+        })
+    }
+}
+
+impl Drop for RecursionGuard {
+    fn drop(&mut self) {
+        RECURSION_DEPTH.with(|depth| depth.set(depth.get() - 1))
+    }
+}
@@ -0,0 +1,18 @@
+//! Maps `google.protobuf.Timestamp` fields to [`chrono::DateTime<Utc>`] (requires the `chrono` feature).
+use super::{out_of_range, saturated_timestamp, timestamp_parts, ProtoTimestamp, NANOS_PER_SECOND};
+use crate::ProtoMapError;
+use chrono::{DateTime, Utc};
+
+const TYPE_NAME: &str = "chrono::DateTime<Utc>";
+
+/// Saturates times outside the valid range of `google.protobuf.Timestamp`
+pub fn to_proto(value: &DateTime<Utc>) -> ProtoTimestamp {
+    // Leap seconds are represented by chrono as nanos over a second
+    let nanos = (value.timestamp_subsec_nanos() as i32).min(NANOS_PER_SECOND - 1);
+    saturated_timestamp(value.timestamp(), nanos)
+}
+
+pub fn from_proto(proto: ProtoTimestamp) -> Result<DateTime<Utc>, ProtoMapError> {
+    let (seconds, nanos) = timestamp_parts(&proto, TYPE_NAME)?;
+    DateTime::from_timestamp(seconds, nanos as u32).ok_or_else(|| out_of_range(TYPE_NAME, seconds, nanos))
+}
@@ -0,0 +1,18 @@
+//! Maps `google.protobuf.Duration` fields to [`chrono::Duration`] (requires the `chrono` feature).
+use super::{duration_parts, out_of_range, saturated_duration, ProtoDuration};
+use crate::ProtoMapError;
+use chrono::Duration;
+
+const TYPE_NAME: &str = "chrono::Duration";
+
+/// Saturates durations longer than the valid range of `google.protobuf.Duration`
+pub fn to_proto(value: &Duration) -> ProtoDuration {
+    saturated_duration(value.num_seconds(), value.subsec_nanos())
+}
+
+pub fn from_proto(proto: ProtoDuration) -> Result<Duration, ProtoMapError> {
+    let (seconds, nanos) = duration_parts(&proto, TYPE_NAME)?;
+    Duration::try_seconds(seconds)
+        .and_then(|duration| duration.checked_add(&Duration::nanoseconds(nanos as i64)))
+        .ok_or_else(|| out_of_range(TYPE_NAME, seconds, nanos))
+}
@@ -0,0 +1,85 @@
+//! Maps [`Decimal`](rust_decimal::Decimal) fields to decimal `string` fields (e.g. `google.type.Decimal`) or to messages of `units` and
+//! `nanos` (e.g. `google.type.Money`).
+
+const TYPE_NAME: &str = "rust_decimal::Decimal";
+
+/// Proto messages that hold a decimal as whole `units` and `nanos` (10^-9) of the unit with the same sign e.g.
+///
+/// ```protobuf
+/// message Money {
+///   string currency_code = 1;
+///   int64 units = 2;
+///   int32 nanos = 3;
+/// }
+/// ```
+pub trait UnitsNanos {
+    fn from_units_nanos(units: i64, nanos: i32) -> Self;
+
+    /// Returns the `(units, nanos)` of the message
+    fn units_nanos(&self) -> (i64, i32);
+}
+
+/// Maps [`Decimal`] fields to decimal `string` fields.
+pub mod decimal_as_string {
+    use super::TYPE_NAME;
+    use crate::ProtoMapError;
+    use rust_decimal::{Decimal, Error};
+
+    pub fn to_scalar(value: &Decimal) -> String {
+        value.to_string()
+    }
+
+    /// Fails for invalid decimal strings, out of range values and values with more fractional digits than
+    /// [`Decimal`] can hold (precision loss)
+    pub fn from_scalar(proto: String) -> Result<Decimal, ProtoMapError> {
+        Decimal::from_str_exact(&proto).map_err(|e| match e {
+            Error::ExceedsMaximumPossibleValue | Error::LessThanMinimumPossibleValue => {
+                ProtoMapError::out_of_range(TYPE_NAME, &proto)
+            }
+            Error::Underflow => ProtoMapError::custom(anyhow::anyhow!(
+                "precision loss of `{}` value `{}`",
+                TYPE_NAME,
+                proto
+            )),
+            e => crate::with::invalid_value(TYPE_NAME, &proto, e),
+        })
+    }
+}
+
+/// Maps [`Decimal`] fields to messages of `units` and `nanos` (see [`UnitsNanos`](super::UnitsNanos)).
+pub mod decimal_as_units_nanos {
+    use super::{UnitsNanos, TYPE_NAME};
+    use crate::ProtoMapError;
+    use rust_decimal::prelude::ToPrimitive;
+    use rust_decimal::Decimal;
+
+    const NANOS_SCALE: u32 = 9;
+    const NANOS_PER_UNIT: i32 = 1_000_000_000;
+
+    /// Rounds the value to nanos and saturates the units that do not fit to `i64`
+    pub fn to_proto<P: UnitsNanos>(value: &Decimal) -> P {
+        let value = value.round_dp(NANOS_SCALE);
+        let units = value.trunc().to_i64().unwrap_or(if value.is_sign_negative() {
+            i64::MIN
+        } else {
+            i64::MAX
+        });
+        let nanos = (value.fract() * Decimal::from(NANOS_PER_UNIT))
+            .to_i32()
+            .unwrap_or_default();
+        P::from_units_nanos(units, nanos)
+    }
+
+    /// Fails for nanos out of range or with a different sign than the units
+    pub fn from_proto<P: UnitsNanos>(proto: P) -> Result<Decimal, ProtoMapError> {
+        let (units, nanos) = proto.units_nanos();
+        let same_sign = (units >= 0 && nanos >= 0) || (units <= 0 && nanos <= 0);
+        if nanos.abs() >= NANOS_PER_UNIT || !same_sign {
+            return Err(ProtoMapError::out_of_range(
+                TYPE_NAME,
+                format!("{} units {} nanos", units, nanos),
+            ));
+        }
+        Ok(Decimal::from(units) + Decimal::new(i64::from(nanos), NANOS_SCALE))
+    }
+}
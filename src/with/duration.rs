@@ -0,0 +1,21 @@
+//! Maps `google.protobuf.Duration` fields to [`std::time::Duration`].
+use super::{duration_parts, out_of_range, saturated_duration, ProtoDuration};
+use crate::ProtoMapError;
+use std::time::Duration;
+
+const TYPE_NAME: &str = "std::time::Duration";
+
+/// Saturates durations longer than the valid range of `google.protobuf.Duration`
+pub fn to_proto(value: &Duration) -> ProtoDuration {
+    let seconds = i64::try_from(value.as_secs()).unwrap_or(i64::MAX);
+    saturated_duration(seconds, value.subsec_nanos() as i32)
+}
+
+/// Fails for negative durations (not supported by [`Duration`])
+pub fn from_proto(proto: ProtoDuration) -> Result<Duration, ProtoMapError> {
+    let (seconds, nanos) = duration_parts(&proto, TYPE_NAME)?;
+    if seconds < 0 || nanos < 0 {
+        return Err(out_of_range(TYPE_NAME, seconds, nanos));
+    }
+    Ok(Duration::new(seconds as u64, nanos as u32))
+}
@@ -0,0 +1,84 @@
+//! Maps `u128` and `i128` fields to decimal `string`, 16 byte big endian `bytes` or messages of two `uint64` words.
+use crate::ProtoMapError;
+use std::num::IntErrorKind;
+
+/// Proto messages that hold a 128 bit integer as high and low `uint64` words e.g.
+///
+/// ```protobuf
+/// message U128 {
+///   uint64 hi = 1;
+///   uint64 lo = 2;
+/// }
+/// ```
+pub trait Words128 {
+    fn from_words(hi: u64, lo: u64) -> Self;
+
+    /// Returns the `(hi, lo)` words of the message
+    fn words(&self) -> (u64, u64);
+}
+
+/// Overflows are out of range errors, the rest of the errors are invalid decimal strings
+fn parse_error(error: std::num::ParseIntError, proto: &str, type_name: &'static str) -> ProtoMapError {
+    match error.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            ProtoMapError::out_of_range(type_name, proto)
+        }
+        _ => crate::with::invalid_value(type_name, proto, error),
+    }
+}
+
+macro_rules! impl_int128_with {
+    ( $name:ident, $as_string:ident, $as_bytes:ident, $as_words:ident ) => {
+        #[doc = concat!("Maps `", stringify!($name), "` fields to decimal `string` fields.")]
+        pub mod $as_string {
+            use crate::ProtoMapError;
+
+            pub fn to_scalar(value: &$name) -> String {
+                value.to_string()
+            }
+
+            /// Fails for invalid decimal strings and out of range values
+            pub fn from_scalar(proto: String) -> Result<$name, ProtoMapError> {
+                proto
+                    .parse()
+                    .map_err(|e| super::parse_error(e, &proto, stringify!($name)))
+            }
+        }
+
+        #[doc = concat!("Maps `", stringify!($name), "` fields to 16 byte big endian `bytes` fields.")]
+        pub mod $as_bytes {
+            use crate::ProtoMapError;
+
+            pub fn to_scalar(value: &$name) -> Vec<u8> {
+                value.to_be_bytes().to_vec()
+            }
+
+            /// Fails for values that are not exactly 16 bytes long
+            pub fn from_scalar(proto: Vec<u8>) -> Result<$name, ProtoMapError> {
+                <[u8; 16]>::try_from(proto.as_slice())
+                    .map($name::from_be_bytes)
+                    .map_err(|_| crate::with::invalid_length(stringify!($name), "16", proto.len()))
+            }
+        }
+
+        #[doc = concat!("Maps `", stringify!($name), "` fields to messages of two `uint64` words ")]
+        #[doc = "(see [`Words128`](super::Words128))."]
+        pub mod $as_words {
+            use super::Words128;
+            use crate::ProtoMapError;
+
+            pub fn to_proto<P: Words128>(value: &$name) -> P {
+                let bits = *value as u128;
+                P::from_words((bits >> 64) as u64, bits as u64)
+            }
+
+            pub fn from_proto<P: Words128>(proto: P) -> Result<$name, ProtoMapError> {
+                let (hi, lo) = proto.words();
+                Ok((((hi as u128) << 64) | lo as u128) as $name)
+            }
+        }
+    };
+}
+
+impl_int128_with! { u128, u128_as_string, u128_as_bytes, u128_as_words }
+impl_int128_with! { i128, i128_as_string, i128_as_bytes, i128_as_words }
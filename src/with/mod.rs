@@ -0,0 +1,170 @@
+//! Ready made `with` modules for common field mappings e.g. `#[proto_map(with = "proto_mapper::with::system_time")]`.
+//!
+//! Modules of scalar mappings (e.g. [`u128_as_string`]) require the `scalar` attribute as well.
+//! The mappings of third party types are enabled by the feature of the respective crate (`uuid`, `url`, `semver`
+//! and `rust_decimal`).
+//!
+//! The modules of `google.protobuf` well known types use the types of the enabled proto framework feature
+//! (`prost-types` for `prost` and `protobuf::well_known_types` for `protobuf`).
+
+use crate::ProtoMapError;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "prost")]
+use prost_types::{Duration as ProtoDuration, Timestamp as ProtoTimestamp};
+#[cfg(feature = "protobuf")]
+use protobuf::well_known_types::{
+    duration::Duration as ProtoDuration, timestamp::Timestamp as ProtoTimestamp,
+};
+
+#[cfg(feature = "chrono")]
+pub mod chrono_datetime;
+#[cfg(feature = "chrono")]
+pub mod chrono_duration;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+pub mod duration;
+mod int128;
+mod net;
+mod non_zero;
+#[cfg(feature = "time")]
+pub mod offset_datetime;
+mod path;
+#[cfg(feature = "semver")]
+mod semver;
+pub mod system_time;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "semver")]
+pub use self::semver::version_as_string;
+#[cfg(feature = "url")]
+pub use self::url::url_as_string;
+#[cfg(feature = "uuid")]
+pub use self::uuid::{uuid_as_bytes, uuid_as_string};
+#[cfg(feature = "rust_decimal")]
+pub use decimal::{decimal_as_string, decimal_as_units_nanos, UnitsNanos};
+pub use int128::{
+    i128_as_bytes, i128_as_string, i128_as_words, u128_as_bytes, u128_as_string, u128_as_words,
+    Words128,
+};
+pub use net::{ip_addr_as_bytes, ip_addr_as_string, socket_addr_as_bytes, socket_addr_as_string};
+pub use non_zero::{
+    non_zero_i16, non_zero_i32, non_zero_i64, non_zero_i8, non_zero_isize, non_zero_u16,
+    non_zero_u32, non_zero_u64, non_zero_u8, non_zero_usize,
+};
+pub use path::path_buf_as_string;
+
+/// Valid range of `google.protobuf.Timestamp` seconds (from 0001-01-01T00:00:00Z to 9999-12-31T23:59:59Z)
+const TIMESTAMP_SECONDS: RangeInclusive<i64> = -62_135_596_800..=253_402_300_799;
+
+/// Valid range of `google.protobuf.Duration` seconds (approximately +-10,000 years)
+const DURATION_SECONDS: RangeInclusive<i64> = -315_576_000_000..=315_576_000_000;
+
+const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
+#[cfg(feature = "prost")]
+fn new_timestamp(seconds: i64, nanos: i32) -> ProtoTimestamp {
+    ProtoTimestamp { seconds, nanos }
+}
+
+#[cfg(feature = "protobuf")]
+fn new_timestamp(seconds: i64, nanos: i32) -> ProtoTimestamp {
+    ProtoTimestamp {
+        seconds,
+        nanos,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "prost")]
+fn new_duration(seconds: i64, nanos: i32) -> ProtoDuration {
+    ProtoDuration { seconds, nanos }
+}
+
+#[cfg(feature = "protobuf")]
+fn new_duration(seconds: i64, nanos: i32) -> ProtoDuration {
+    ProtoDuration {
+        seconds,
+        nanos,
+        ..Default::default()
+    }
+}
+
+/// Returns a proto timestamp of `seconds` and `nanos` saturated to the valid range of `google.protobuf.Timestamp`
+fn saturated_timestamp(seconds: i64, nanos: i32) -> ProtoTimestamp {
+    if seconds < *TIMESTAMP_SECONDS.start() {
+        new_timestamp(*TIMESTAMP_SECONDS.start(), 0)
+    } else if seconds > *TIMESTAMP_SECONDS.end() {
+        new_timestamp(*TIMESTAMP_SECONDS.end(), NANOS_PER_SECOND - 1)
+    } else {
+        new_timestamp(seconds, nanos)
+    }
+}
+
+/// Returns a proto duration of `seconds` and `nanos` saturated to the valid range of `google.protobuf.Duration`
+fn saturated_duration(seconds: i64, nanos: i32) -> ProtoDuration {
+    if seconds < *DURATION_SECONDS.start() {
+        new_duration(*DURATION_SECONDS.start(), -(NANOS_PER_SECOND - 1))
+    } else if seconds > *DURATION_SECONDS.end() {
+        new_duration(*DURATION_SECONDS.end(), NANOS_PER_SECOND - 1)
+    } else {
+        new_duration(seconds, nanos)
+    }
+}
+
+/// Error of a proto value that cannot be parsed to `type_name`
+fn invalid_value(type_name: &str, value: &str, error: impl Display) -> ProtoMapError {
+    ProtoMapError::custom(anyhow::anyhow!(
+        "invalid `{}` value `{}`: {}",
+        type_name,
+        value,
+        error
+    ))
+}
+
+/// Error of proto `bytes` with an invalid length for `type_name`
+fn invalid_length(type_name: &str, expected: &str, found: usize) -> ProtoMapError {
+    ProtoMapError::custom(anyhow::anyhow!(
+        "expected {} bytes for `{}` but found {}",
+        expected,
+        type_name,
+        found
+    ))
+}
+
+fn out_of_range(type_name: &'static str, seconds: i64, nanos: i32) -> ProtoMapError {
+    ProtoMapError::out_of_range(type_name, format!("{}s {}ns", seconds, nanos))
+}
+
+/// Returns the `(seconds, nanos)` of a valid proto timestamp or an out of range error for `type_name`
+fn timestamp_parts(
+    proto: &ProtoTimestamp,
+    type_name: &'static str,
+) -> Result<(i64, i32), ProtoMapError> {
+    let (seconds, nanos) = (proto.seconds, proto.nanos);
+    if TIMESTAMP_SECONDS.contains(&seconds) && (0..NANOS_PER_SECOND).contains(&nanos) {
+        Ok((seconds, nanos))
+    } else {
+        Err(out_of_range(type_name, seconds, nanos))
+    }
+}
+
+/// Returns the `(seconds, nanos)` of a valid proto duration or an out of range error for `type_name`.
+///
+/// Note: the seconds and nanos of valid durations have the same sign.
+fn duration_parts(
+    proto: &ProtoDuration,
+    type_name: &'static str,
+) -> Result<(i64, i32), ProtoMapError> {
+    let (seconds, nanos) = (proto.seconds, proto.nanos);
+    let same_sign = (seconds >= 0 && nanos >= 0) || (seconds <= 0 && nanos <= 0);
+    if DURATION_SECONDS.contains(&seconds) && nanos.abs() < NANOS_PER_SECOND && same_sign {
+        Ok((seconds, nanos))
+    } else {
+        Err(out_of_range(type_name, seconds, nanos))
+    }
+}
@@ -0,0 +1,107 @@
+//! Maps [`IpAddr`](std::net::IpAddr) and [`SocketAddr`](std::net::SocketAddr) fields to `string` or `bytes` fields.
+//!
+//! The `bytes` of an address are the octets of the ip (4 for IPv4 and 16 for IPv6) followed by the big endian port
+//! of socket addresses. Note that the flow info and the scope id of IPv6 socket addresses are not mapped.
+use crate::ProtoMapError;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const IPV4_LEN: usize = 4;
+const IPV6_LEN: usize = 16;
+const PORT_LEN: usize = 2;
+
+fn ip_octets(ip: &IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    }
+}
+
+/// Returns the ip of 4 or 16 octets or `None` for any other length
+fn ip_from_octets(octets: &[u8]) -> Option<IpAddr> {
+    if let Ok(octets) = <[u8; IPV4_LEN]>::try_from(octets) {
+        Some(Ipv4Addr::from(octets).into())
+    } else if let Ok(octets) = <[u8; IPV6_LEN]>::try_from(octets) {
+        Some(Ipv6Addr::from(octets).into())
+    } else {
+        None
+    }
+}
+
+fn invalid_length(type_name: &'static str, extra: usize, found: usize) -> ProtoMapError {
+    let expected = format!("{} or {}", IPV4_LEN + extra, IPV6_LEN + extra);
+    crate::with::invalid_length(type_name, &expected, found)
+}
+
+/// Maps [`IpAddr`] fields to `string` fields.
+pub mod ip_addr_as_string {
+    use crate::ProtoMapError;
+    use std::net::IpAddr;
+
+    pub fn to_scalar(value: &IpAddr) -> String {
+        value.to_string()
+    }
+
+    /// Fails for invalid IPv4 or IPv6 addresses
+    pub fn from_scalar(proto: String) -> Result<IpAddr, ProtoMapError> {
+        proto
+            .parse()
+            .map_err(|e| crate::with::invalid_value("IpAddr", &proto, e))
+    }
+}
+
+/// Maps [`IpAddr`] fields to 4 (IPv4) or 16 (IPv6) byte `bytes` fields.
+pub mod ip_addr_as_bytes {
+    use crate::ProtoMapError;
+    use std::net::IpAddr;
+
+    pub fn to_scalar(value: &IpAddr) -> Vec<u8> {
+        super::ip_octets(value)
+    }
+
+    /// Fails for values that are not 4 or 16 bytes long
+    pub fn from_scalar(proto: Vec<u8>) -> Result<IpAddr, ProtoMapError> {
+        super::ip_from_octets(&proto).ok_or_else(|| super::invalid_length("IpAddr", 0, proto.len()))
+    }
+}
+
+/// Maps [`SocketAddr`] fields to `string` fields (e.g. `127.0.0.1:8080` or `[::1]:8080`).
+pub mod socket_addr_as_string {
+    use crate::ProtoMapError;
+    use std::net::SocketAddr;
+
+    pub fn to_scalar(value: &SocketAddr) -> String {
+        value.to_string()
+    }
+
+    /// Fails for invalid socket addresses
+    pub fn from_scalar(proto: String) -> Result<SocketAddr, ProtoMapError> {
+        proto
+            .parse()
+            .map_err(|e| crate::with::invalid_value("SocketAddr", &proto, e))
+    }
+}
+
+/// Maps [`SocketAddr`] fields to 6 (IPv4) or 18 (IPv6) byte `bytes` fields of the ip octets and the big endian port.
+pub mod socket_addr_as_bytes {
+    use super::PORT_LEN;
+    use crate::ProtoMapError;
+    use std::net::SocketAddr;
+
+    pub fn to_scalar(value: &SocketAddr) -> Vec<u8> {
+        let mut bytes = super::ip_octets(&value.ip());
+        bytes.extend_from_slice(&value.port().to_be_bytes());
+        bytes
+    }
+
+    /// Fails for values that are not 6 or 18 bytes long
+    pub fn from_scalar(proto: Vec<u8>) -> Result<SocketAddr, ProtoMapError> {
+        let invalid_length = || super::invalid_length("SocketAddr", PORT_LEN, proto.len());
+        let split = proto
+            .len()
+            .checked_sub(PORT_LEN)
+            .ok_or_else(invalid_length)?;
+        let (octets, port) = proto.split_at(split);
+        let ip = super::ip_from_octets(octets).ok_or_else(invalid_length)?;
+        Ok(SocketAddr::new(ip, u16::from_be_bytes([port[0], port[1]])))
+    }
+}
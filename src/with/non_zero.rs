@@ -0,0 +1,43 @@
+//! Maps `std::num::NonZero*` fields to the proto scalar of their integer type.
+//!
+//! Note: `NonZeroU32`, `NonZeroU64`, `NonZeroI32` and `NonZeroI64` are mapped by default as scalar values
+//! as well, the modules are useful for the rest of the non zero types.
+
+macro_rules! impl_non_zero_with {
+    ( $( $module:ident: $name:ident => $proto:ty ),* ) => {
+        $(
+            #[doc = concat!("Maps `", stringify!($name), "` fields to `", stringify!($proto), "` fields.")]
+            pub mod $module {
+                use crate::ProtoMapError;
+                use std::num::$name;
+
+                #[allow(clippy::unnecessary_cast)]
+                pub fn to_scalar(value: &$name) -> $proto {
+                    value.get() as $proto
+                }
+
+                /// Fails for zero and out of range values
+                pub fn from_scalar(proto: $proto) -> Result<$name, ProtoMapError> {
+                    proto
+                        .try_into()
+                        .ok()
+                        .and_then($name::new)
+                        .ok_or_else(|| ProtoMapError::out_of_range(stringify!($name), proto))
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero_with! {
+    non_zero_u8: NonZeroU8 => u32,
+    non_zero_u16: NonZeroU16 => u32,
+    non_zero_u32: NonZeroU32 => u32,
+    non_zero_u64: NonZeroU64 => u64,
+    non_zero_usize: NonZeroUsize => u64,
+    non_zero_i8: NonZeroI8 => i32,
+    non_zero_i16: NonZeroI16 => i32,
+    non_zero_i32: NonZeroI32 => i32,
+    non_zero_i64: NonZeroI64 => i64,
+    non_zero_isize: NonZeroIsize => i64
+}
@@ -0,0 +1,19 @@
+//! Maps `google.protobuf.Timestamp` fields to [`time::OffsetDateTime`] (requires the `time` feature).
+//!
+//! Converted values are always in UTC.
+use super::{out_of_range, saturated_timestamp, timestamp_parts, ProtoTimestamp};
+use crate::ProtoMapError;
+use time::OffsetDateTime;
+
+const TYPE_NAME: &str = "time::OffsetDateTime";
+
+/// Saturates times outside the valid range of `google.protobuf.Timestamp`
+pub fn to_proto(value: &OffsetDateTime) -> ProtoTimestamp {
+    saturated_timestamp(value.unix_timestamp(), value.nanosecond() as i32)
+}
+
+pub fn from_proto(proto: ProtoTimestamp) -> Result<OffsetDateTime, ProtoMapError> {
+    let (seconds, nanos) = timestamp_parts(&proto, TYPE_NAME)?;
+    let unix_nanos = seconds as i128 * 1_000_000_000 + nanos as i128;
+    OffsetDateTime::from_unix_timestamp_nanos(unix_nanos).map_err(|_| out_of_range(TYPE_NAME, seconds, nanos))
+}
@@ -0,0 +1,19 @@
+//! Maps [`PathBuf`](std::path::PathBuf) fields to `string` fields.
+
+/// Maps [`PathBuf`] fields to `string` fields.
+///
+/// Note: paths that are not valid unicode are converted lossy (see [`Path::to_string_lossy`](std::path::Path::to_string_lossy)).
+pub mod path_buf_as_string {
+    use crate::ProtoMapError;
+    use std::path::PathBuf;
+
+    // Note: the generated code calls the module with a reference of the field type
+    #[allow(clippy::ptr_arg)]
+    pub fn to_scalar(value: &PathBuf) -> String {
+        value.to_string_lossy().into_owned()
+    }
+
+    pub fn from_scalar(proto: String) -> Result<PathBuf, ProtoMapError> {
+        Ok(PathBuf::from(proto))
+    }
+}
@@ -0,0 +1,16 @@
+//! Maps [`Version`](::semver::Version) fields to `string` fields.
+
+/// Maps [`Version`] fields to `string` fields.
+pub mod version_as_string {
+    use crate::ProtoMapError;
+    use semver::Version;
+
+    pub fn to_scalar(value: &Version) -> String {
+        value.to_string()
+    }
+
+    /// Fails for invalid semantic versions
+    pub fn from_scalar(proto: String) -> Result<Version, ProtoMapError> {
+        Version::parse(&proto).map_err(|e| crate::with::invalid_value("semver::Version", &proto, e))
+    }
+}
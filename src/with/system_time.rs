@@ -0,0 +1,38 @@
+//! Maps `google.protobuf.Timestamp` fields to [`SystemTime`].
+use super::{out_of_range, saturated_timestamp, timestamp_parts, ProtoTimestamp, NANOS_PER_SECOND};
+use crate::ProtoMapError;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const TYPE_NAME: &str = "std::time::SystemTime";
+
+/// Saturates times outside the valid range of `google.protobuf.Timestamp`
+pub fn to_proto(value: &SystemTime) -> ProtoTimestamp {
+    match value.duration_since(UNIX_EPOCH) {
+        Ok(after) => {
+            let seconds = i64::try_from(after.as_secs()).unwrap_or(i64::MAX);
+            saturated_timestamp(seconds, after.subsec_nanos() as i32)
+        }
+        Err(before) => {
+            // Timestamps before the epoch still count nanos forward in time
+            let before = before.duration();
+            let seconds = -i64::try_from(before.as_secs()).unwrap_or(i64::MAX);
+            let nanos = before.subsec_nanos() as i32;
+            if nanos > 0 {
+                saturated_timestamp(seconds - 1, NANOS_PER_SECOND - nanos)
+            } else {
+                saturated_timestamp(seconds, 0)
+            }
+        }
+    }
+}
+
+pub fn from_proto(proto: ProtoTimestamp) -> Result<SystemTime, ProtoMapError> {
+    let (seconds, nanos) = timestamp_parts(&proto, TYPE_NAME)?;
+    let time = if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds as u64))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(seconds.unsigned_abs()))
+    };
+    time.and_then(|time| time.checked_add(Duration::from_nanos(nanos as u64)))
+        .ok_or_else(|| out_of_range(TYPE_NAME, seconds, nanos))
+}
@@ -0,0 +1,16 @@
+//! Maps [`Url`](::url::Url) fields to `string` fields.
+
+/// Maps [`Url`] fields to `string` fields.
+pub mod url_as_string {
+    use crate::ProtoMapError;
+    use url::Url;
+
+    pub fn to_scalar(value: &Url) -> String {
+        value.to_string()
+    }
+
+    /// Fails for invalid absolute urls
+    pub fn from_scalar(proto: String) -> Result<Url, ProtoMapError> {
+        Url::parse(&proto).map_err(|e| crate::with::invalid_value("url::Url", &proto, e))
+    }
+}
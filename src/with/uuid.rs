@@ -0,0 +1,36 @@
+//! Maps [`Uuid`](::uuid::Uuid) fields to `string` (hyphenated) or 16 byte `bytes` fields.
+
+const TYPE_NAME: &str = "uuid::Uuid";
+
+/// Maps [`Uuid`] fields to hyphenated `string` fields.
+pub mod uuid_as_string {
+    use super::TYPE_NAME;
+    use crate::ProtoMapError;
+    use uuid::Uuid;
+
+    pub fn to_scalar(value: &Uuid) -> String {
+        value.to_string()
+    }
+
+    /// Fails for invalid uuid strings (any of the formats of [`Uuid::parse_str`])
+    pub fn from_scalar(proto: String) -> Result<Uuid, ProtoMapError> {
+        Uuid::parse_str(&proto).map_err(|e| crate::with::invalid_value(TYPE_NAME, &proto, e))
+    }
+}
+
+/// Maps [`Uuid`] fields to 16 byte `bytes` fields.
+pub mod uuid_as_bytes {
+    use super::TYPE_NAME;
+    use crate::ProtoMapError;
+    use uuid::Uuid;
+
+    pub fn to_scalar(value: &Uuid) -> Vec<u8> {
+        value.as_bytes().to_vec()
+    }
+
+    /// Fails for values that are not exactly 16 bytes long
+    pub fn from_scalar(proto: Vec<u8>) -> Result<Uuid, ProtoMapError> {
+        Uuid::from_slice(&proto)
+            .map_err(|_| crate::with::invalid_length(TYPE_NAME, "16", proto.len()))
+    }
+}
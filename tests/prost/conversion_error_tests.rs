@@ -0,0 +1,171 @@
+use super::custom_mappings::uuid_as_string;
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar, ProtoScalar};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityStatus", enumeration)]
+enum EntityStatus {
+    #[default]
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    #[proto_map(enumeration)]
+    pub status: EntityStatus,
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::NestedEntity")]
+struct NestedEntity {
+    #[proto_map(required)]
+    pub first: ScalarEntity,
+    pub second: Option<ScalarEntity>,
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(
+    source = "proto::prost::HierarchyEntity",
+    one_of(field = "data"),
+    rename_variants = "snake_case"
+)]
+enum HierarchyEntity {
+    FirstEntity(ScalarEntity),
+    SecondEntity(NestedEntity),
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::EntityUuids")]
+struct EntityUuids {
+    #[proto_map(scalar, with = "uuid_as_string")]
+    uuid_str: Uuid,
+    #[proto_map(scalar, with = "uuid_as_string")]
+    opt_uuid_str: Option<Uuid>,
+}
+
+#[test]
+fn unknown_enumeration_value_error() {
+    let error = EntityStatus::from_scalar(42).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::UnknownEnumValue {
+            type_name: "proto::prost::EntityStatus",
+            value: 42,
+            ..
+        }
+    ));
+    assert!(error.path().is_empty());
+}
+
+#[test]
+fn nested_error_field_path() {
+    let proto = proto::prost::HierarchyEntity {
+        data: Some(proto::prost::hierarchy_entity::Data::SecondEntity(
+            proto::prost::NestedEntity {
+                first: Some(proto::prost::ScalarEntity::default()),
+                second: Some(proto::prost::ScalarEntity {
+                    status: 42,
+                    ..Default::default()
+                }),
+            },
+        )),
+    };
+
+    let error = HierarchyEntity::from_proto(proto).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownEnumValue { value: 42, .. }));
+    assert_eq!(error.path().to_string(), "second_entity.second.status");
+    assert_eq!(
+        error.to_string(),
+        "unknown value 42 for enumeration `proto::prost::EntityStatus` at `second_entity.second.status`"
+    );
+}
+
+#[test]
+fn missing_required_message_error() {
+    let proto = proto::prost::NestedEntity {
+        first: None,
+        second: None,
+    };
+
+    let error = NestedEntity::from_proto(proto).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::MissingMessage {
+            type_name: "proto::prost::NestedEntity",
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "first");
+}
+
+#[test]
+fn one_of_not_set_error() {
+    let error = HierarchyEntity::from_proto(proto::prost::HierarchyEntity { data: None }).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::OneOfNotSet {
+            type_name: "proto::prost::HierarchyEntity",
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "data");
+}
+
+#[test]
+fn custom_mapping_error() {
+    let proto = proto::prost::EntityUuids {
+        uuid_str: "not a uuid".into(),
+        ..Default::default()
+    };
+
+    let error = EntityUuids::from_proto(proto).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+    assert_eq!(error.path().to_string(), "uuid_str");
+}
+
+#[test]
+fn collect_all_errors() {
+    let proto = proto::prost::HierarchyEntity {
+        data: Some(proto::prost::hierarchy_entity::Data::SecondEntity(
+            proto::prost::NestedEntity {
+                first: None,
+                second: Some(proto::prost::ScalarEntity {
+                    status: 42,
+                    ..Default::default()
+                }),
+            },
+        )),
+    };
+
+    let errors = HierarchyEntity::try_from_proto_all(proto).unwrap_err();
+    let paths: Vec<_> = errors.errors().iter().map(|e| e.path().to_string()).collect();
+    assert_eq!(paths, vec!["second_entity.first", "second_entity.second.status"]);
+
+    let proto = proto::prost::EntityUuids {
+        uuid_str: "not a uuid".into(),
+        opt_uuid_str: "not a uuid either".into(),
+        ..Default::default()
+    };
+
+    let errors = EntityUuids::try_from_proto_all(proto).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .into_iter()
+        .all(|e| matches!(e, ProtoMapError::Custom { .. })));
+}
+
+#[test]
+fn collect_all_errors_success() {
+    let original = EntityUuids {
+        uuid_str: Uuid::new_v4(),
+        opt_uuid_str: None,
+    };
+
+    let tested = EntityUuids::try_from_proto_all(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
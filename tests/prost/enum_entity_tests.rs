@@ -95,4 +95,32 @@ fn hierarchy_entity_round_trips() {
     let tested = HierarchyEntity::from_proto(p).unwrap();
 
     assert_eq!(tested, original);
-}
\ No newline at end of file
+}
+
+#[test]
+fn hierarchy_entity_into_proto() {
+    let first = ScalarEntity {
+        uint32_f: 1,
+        int32_f: -10,
+        bool_f: true,
+        string_f: "Foo1".into(),
+        bytes_f: "Foo1".as_bytes().to_vec(),
+        status: EntityStatus::StatusC,
+    };
+
+    let second = ScalarEntity {
+        uint32_f: 2,
+        int32_f: -20,
+        bool_f: false,
+        string_f: "Foo2".into(),
+        bytes_f: "Foo2".as_bytes().to_vec(),
+        status: EntityStatus::StatusA,
+    };
+
+    let original = HierarchyEntity::SecondEntity(NestedEntity { first, second });
+
+    let expected = original.to_proto();
+    let tested: proto::prost::HierarchyEntity = original.into_proto();
+
+    assert_eq!(tested, expected);
+}
@@ -0,0 +1,66 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMapScalar};
+
+// The same enums map to the `rust-protobuf` enumerations (see `protobuf/enum_naming_tests.rs`)
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::prost::EntityState",
+    enumeration,
+    rename_variants = "auto",
+    add_prefix = "ENTITY_STATE_"
+)]
+enum EntityState {
+    Unspecified,
+    #[proto_map(alias = "ENTITY_STATE_LEGACY_ACTIVE")]
+    Active,
+    Disabled,
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::prost::EntityType",
+    enumeration,
+    rename_variants = "auto",
+    strip_prefix = "Kind",
+    add_prefix = "TYPE_"
+)]
+enum EntityKind {
+    KindA,
+    KindB,
+    KindC,
+}
+
+#[test]
+fn auto_naming_strips_enum_prefix() {
+    let states = [
+        (
+            EntityState::Unspecified,
+            proto::prost::EntityState::Unspecified,
+        ),
+        (EntityState::Active, proto::prost::EntityState::Active),
+        (EntityState::Disabled, proto::prost::EntityState::Disabled),
+        (EntityState::Archived, proto::prost::EntityState::Archived),
+    ];
+    for (state, proto_state) in states {
+        assert_eq!(state.to_scalar(), proto_state as i32);
+        assert_eq!(EntityState::from_scalar(proto_state as i32).unwrap(), state);
+    }
+
+    let tested = EntityState::from_scalar(proto::prost::EntityState::LegacyActive as i32).unwrap();
+    assert_eq!(tested, EntityState::Active);
+}
+
+#[test]
+fn auto_naming_with_prefixes() {
+    let kinds = [
+        (EntityKind::KindA, proto::prost::EntityType::TypeA),
+        (EntityKind::KindB, proto::prost::EntityType::TypeB),
+        (EntityKind::KindC, proto::prost::EntityType::TypeC),
+    ];
+    for (kind, proto_kind) in kinds {
+        assert_eq!(kind.to_scalar(), proto_kind as i32);
+        assert_eq!(EntityKind::from_scalar(proto_kind as i32).unwrap(), kind);
+    }
+}
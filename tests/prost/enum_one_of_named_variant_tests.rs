@@ -0,0 +1,154 @@
+use crate::prost::custom_mappings::uuid_as_string;
+use crate::proto;
+use proto::prost::shape_entity;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapOneOf, ProtoMapScalar};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::ShapeEntity", one_of(field = "shape"))]
+enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Rectangle {
+        width: f64,
+        #[proto_map(rename = "height")]
+        length: f64,
+        #[proto_map(presence = "explicit")]
+        label: Option<String>,
+    },
+    Tagged {
+        #[proto_map(scalar, with = "uuid_as_string")]
+        id: Uuid,
+        #[proto_map(scalar, with = "uuid_as_string")]
+        owner: Uuid,
+        tags: Vec<String>,
+        #[proto_map(skip)]
+        cached: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::prost::ShapeEntity",
+    one_of(field = "shape", not_set = "default")
+)]
+enum DefaultShape {
+    #[proto_map(default)]
+    Circle { radius: f64 },
+    Rectangle {
+        width: f64,
+        height: f64,
+        #[proto_map(skip)]
+        label: Option<String>,
+    },
+    Tagged {
+        #[proto_map(skip)]
+        tags: Vec<String>,
+    },
+}
+
+fn shapes() -> Vec<Shape> {
+    vec![
+        Shape::Circle { radius: 1.5 },
+        Shape::Rectangle {
+            width: 2.0,
+            length: 3.0,
+            label: Some(String::new()),
+        },
+        Shape::Rectangle {
+            width: 2.0,
+            length: 3.0,
+            label: None,
+        },
+        Shape::Tagged {
+            id: Uuid::new_v4(),
+            owner: Uuid::new_v4(),
+            tags: vec!["a".into(), "b".into()],
+            cached: false,
+        },
+    ]
+}
+
+#[test]
+fn one_of_named_variants_round_trip() {
+    for original in shapes() {
+        let tested = Shape::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = Shape::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = Shape::try_from_proto_all(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+}
+
+#[test]
+fn one_of_named_variants_to_member_messages() {
+    assert_eq!(
+        Shape::Circle { radius: 1.5 }.to_one_of(),
+        Some(shape_entity::Shape::Circle(shape_entity::Circle {
+            radius: 1.5
+        }))
+    );
+
+    let original = Shape::Rectangle {
+        width: 2.0,
+        length: 3.0,
+        label: Some("Foo".into()),
+    };
+    let expected = Some(shape_entity::Shape::Rectangle(shape_entity::Rectangle {
+        width: 2.0,
+        height: 3.0,
+        label: Some("Foo".into()),
+    }));
+    assert_eq!(original.to_one_of(), expected);
+    assert_eq!(original.into_one_of(), expected);
+}
+
+#[test]
+fn one_of_named_variant_skips_fields() {
+    let original = Shape::Tagged {
+        id: Uuid::nil(),
+        owner: Uuid::nil(),
+        tags: vec![],
+        cached: true,
+    };
+
+    let tested = Shape::from_proto(original.into_proto()).unwrap();
+    assert!(matches!(tested, Shape::Tagged { cached: false, .. }));
+}
+
+#[test]
+fn one_of_named_variant_errors() {
+    let member = shape_entity::Shape::Tagged(shape_entity::Tagged {
+        id: "foo".into(),
+        owner: "bar".into(),
+        tags: vec![],
+    });
+
+    let error = Shape::from_one_of(Some(member.clone())).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+    assert_eq!(error.path().to_string(), "tagged.id");
+
+    let errors = Shape::try_from_one_of_all(Some(member)).unwrap_err();
+    let paths: Vec<_> = errors
+        .errors()
+        .iter()
+        .map(|error| error.path().to_string())
+        .collect();
+    assert_eq!(paths, vec!["tagged.id", "tagged.owner"]);
+}
+
+#[test]
+fn one_of_named_variant_not_set_default() {
+    let tested = DefaultShape::from_proto(proto::prost::ShapeEntity::default()).unwrap();
+    assert_eq!(tested, DefaultShape::Circle { radius: 0.0 });
+
+    let original = DefaultShape::Tagged {
+        tags: vec!["a".into()],
+    };
+    let tested = DefaultShape::try_from_proto_all(original.to_proto()).unwrap();
+    assert_eq!(tested, DefaultShape::Tagged { tags: vec![] });
+}
@@ -0,0 +1,97 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapScalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityStatus", enumeration)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+    #[proto_map(unknown)]
+    Unrecognized(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityType", enumeration)]
+enum EntityType {
+    TypeA,
+    TypeB,
+    #[proto_map(other)]
+    TypeC,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    #[proto_map(enumeration)]
+    pub status: EntityStatus,
+    #[proto_map(enumeration)]
+    pub r#type: EntityType,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::RepeatedEntity")]
+struct RepeatedEntity {
+    #[proto_map(enumeration)]
+    pub statuses: Vec<EntityStatus>,
+}
+
+#[test]
+fn unknown_variant_round_trip() {
+    let original = EntityStatus::Unrecognized(42);
+
+    assert_eq!(original.to_scalar(), 42);
+    assert_eq!(EntityStatus::from_scalar(42).unwrap(), original);
+    assert_eq!(EntityStatus::from_scalar(1).unwrap(), EntityStatus::StatusB);
+}
+
+#[test]
+fn other_variant_from_unknown_value() {
+    assert_eq!(EntityType::from_scalar(42).unwrap(), EntityType::TypeC);
+    assert_eq!(EntityType::from_scalar(1).unwrap(), EntityType::TypeB);
+    assert_eq!(EntityType::TypeC.to_scalar(), 2);
+}
+
+#[test]
+fn entity_unknown_values_round_trip() {
+    let original = proto::prost::ScalarEntity {
+        uint32_f: 1,
+        status: 42,
+        r#type: 42,
+        ..Default::default()
+    };
+
+    let e = ScalarEntity::from_proto(original.clone()).unwrap();
+    assert_eq!(
+        e,
+        ScalarEntity {
+            uint32_f: 1,
+            status: EntityStatus::Unrecognized(42),
+            r#type: EntityType::TypeC,
+        }
+    );
+
+    let p = e.into_proto();
+    assert_eq!(p.status, 42);
+    assert_eq!(p.r#type, proto::prost::EntityType::TypeC as i32);
+}
+
+#[test]
+fn repeated_unknown_values_round_trip() {
+    let original = proto::prost::RepeatedEntity {
+        statuses: vec![1, 42, -1],
+        ..Default::default()
+    };
+
+    let e = RepeatedEntity::from_proto(original.clone()).unwrap();
+    assert_eq!(
+        e.statuses,
+        vec![
+            EntityStatus::StatusB,
+            EntityStatus::Unrecognized(42),
+            EntityStatus::Unrecognized(-1)
+        ]
+    );
+    assert_eq!(e.to_proto(), original);
+}
@@ -0,0 +1,81 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityState", enumeration)]
+enum EntityState {
+    #[proto_map(rename = "Unspecified")]
+    Unknown,
+    #[proto_map(alias = "LegacyActive")]
+    Active,
+    Disabled,
+    #[proto_map(value = 3)]
+    Archived,
+    #[proto_map(value = 7)]
+    Removed,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::StateEntity")]
+struct StateEntity {
+    #[proto_map(enumeration)]
+    pub state: EntityState,
+    #[proto_map(enumeration)]
+    pub states: Vec<EntityState>,
+}
+
+#[test]
+fn renamed_variant_round_trip() {
+    assert_eq!(
+        EntityState::Unknown.to_scalar(),
+        proto::prost::EntityState::Unspecified as i32
+    );
+    assert_eq!(EntityState::from_scalar(0).unwrap(), EntityState::Unknown);
+}
+
+#[test]
+fn alias_decodes_to_variant() {
+    let tested = EntityState::from_scalar(proto::prost::EntityState::LegacyActive as i32).unwrap();
+    assert_eq!(tested, EntityState::Active);
+
+    // Aliases are only decoded
+    assert_eq!(
+        EntityState::Active.to_scalar(),
+        proto::prost::EntityState::Active as i32
+    );
+}
+
+#[test]
+fn raw_value_round_trip() {
+    assert_eq!(EntityState::Archived.to_scalar(), 3);
+    assert_eq!(EntityState::from_scalar(3).unwrap(), EntityState::Archived);
+
+    assert_eq!(EntityState::Removed.to_scalar(), 7);
+    assert_eq!(EntityState::from_scalar(7).unwrap(), EntityState::Removed);
+
+    let error = EntityState::from_scalar(8).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::UnknownEnumValue { value: 8, .. }
+    ));
+}
+
+#[test]
+fn entity_round_trip() {
+    let original = StateEntity {
+        state: EntityState::Removed,
+        states: vec![
+            EntityState::Unknown,
+            EntityState::Active,
+            EntityState::Disabled,
+            EntityState::Archived,
+        ],
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.state, 7);
+    assert_eq!(p.states, vec![0, 1, 2, 3]);
+
+    let tested = StateEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
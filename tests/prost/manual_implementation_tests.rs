@@ -1,5 +1,8 @@
+// Manual implementations intentionally mirror the code generated by the macro
+#![allow(clippy::field_reassign_with_default, clippy::useless_conversion)]
+
 use crate::proto;
-use proto_mapper::{ProtoMap, ProtoMapScalar, ProtoScalar};
+use proto_mapper::{ProtoMap, ProtoMapError, ProtoMapScalar, ProtoScalar};
 
 /// Fully expanded and manual experiments (these used to build the macros and the library traits synergy)
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
@@ -20,15 +23,12 @@ impl ProtoMapScalar<i32> for EntityStatus {
         }
     }
 
-    fn from_scalar(proto: i32) -> Result<Self, anyhow::Error> {
+    fn from_scalar(proto: i32) -> Result<Self, ProtoMapError> {
         match proto {
             _ if proto == proto::prost::EntityStatus::StatusA as i32 => Ok(Self::StatusA),
             _ if proto == proto::prost::EntityStatus::StatusB as i32 => Ok(Self::StatusB),
             _ if proto == proto::prost::EntityStatus::StatusC as i32 => Ok(Self::StatusC),
-            _ => Err(anyhow::anyhow!(format!(
-                "Unable to match enumeration value {} to EntityStatus",
-                proto
-            ))),
+            _ => Err(ProtoMapError::unknown_enum_value("proto::prost::EntityStatus", proto)),
         }
     }
 }
@@ -58,7 +58,7 @@ impl ProtoMap for ScalarEntity {
         proto.status = ProtoMapScalar::to_scalar(&self.status);
         proto
     }
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         let inner = Self {
             uint32_f: ProtoMapScalar::from_scalar(proto.uint32_f)?,
             int32_f: ProtoMapScalar::from_scalar(proto.int32_f)?,
@@ -115,7 +115,7 @@ impl ProtoMap for ScalarEntityOptions {
 
         proto
     }
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         let inner = Self {
             // Special case for options
             uint32_f: {
@@ -192,7 +192,7 @@ impl ProtoMap for NestedEntity {
 
         proto
     }
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         let inner = Self {
             first: {
                 if let Some(value) = proto.first {
@@ -230,13 +230,13 @@ impl ProtoMap for HierarchyEntity {
         }
         inner
     }
-    fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> std::result::Result<Self, ProtoMapError> {
         match proto.data {
             Some(proto::prost::hierarchy_entity::Data::FirstEntity(value)) => { ScalarEntity::from_proto(value)
                 .map(Self::FirstEntity) }
             Some(proto::prost::hierarchy_entity::Data::SecondEntity(value)) => { NestedEntity::from_proto(value)
                 .map(Self::SecondEntity) }
-            _ => Err(anyhow::anyhow!(stringify!(Failed to decode enum HierarchyEntity from proto entity)))
+            _ => Err(ProtoMapError::one_of_not_set("proto::prost::HierarchyEntity").with_field("data"))
         }
     }
 }
@@ -0,0 +1,169 @@
+use crate::proto;
+use proto_mapper::with::Words128;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError};
+
+impl Words128 for proto::prost::U128Words {
+    fn from_words(hi: u64, lo: u64) -> Self {
+        Self { hi, lo }
+    }
+
+    fn words(&self) -> (u64, u64) {
+        (self.hi, self.lo)
+    }
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::AmountEntity")]
+struct AmountEntity {
+    #[proto_map(scalar, with = "proto_mapper::with::u128_as_string")]
+    pub u128_string: u128,
+    #[proto_map(scalar, with = "proto_mapper::with::u128_as_bytes")]
+    pub u128_bytes: u128,
+    #[proto_map(with = "proto_mapper::with::u128_as_words")]
+    pub u128_words: u128,
+    #[proto_map(scalar, with = "proto_mapper::with::i128_as_string")]
+    pub i128_string: i128,
+    #[proto_map(scalar, with = "proto_mapper::with::i128_as_bytes")]
+    pub i128_bytes: i128,
+    #[proto_map(with = "proto_mapper::with::i128_as_words")]
+    pub i128_words: i128,
+    #[proto_map(with = "proto_mapper::with::u128_as_words")]
+    pub opt_u128_words: Option<u128>,
+}
+
+fn amount_entity(u: u128, i: i128) -> AmountEntity {
+    AmountEntity {
+        u128_string: u,
+        u128_bytes: u,
+        u128_words: u,
+        i128_string: i,
+        i128_bytes: i,
+        i128_words: i,
+        opt_u128_words: Some(u),
+    }
+}
+
+#[test]
+fn amount_entity_round_trip() {
+    for (u, i) in [(0, 0), (u128::MAX, i128::MIN), (u128::MAX / 3, i128::MAX), (1, -1)] {
+        let original = amount_entity(u, i);
+
+        let tested = AmountEntity::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = AmountEntity::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+}
+
+#[test]
+fn amount_entity_missing_words() {
+    let mut original = amount_entity(0, 0);
+    original.opt_u128_words = None;
+
+    let tested = AmountEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn amount_entity_errors() {
+    let mut p = amount_entity(1, 1).to_proto();
+    p.u128_string = "340282366920938463463374607431768211456".into();
+    p.i128_string = "not a number".into();
+    p.u128_bytes = vec![1, 2, 3];
+
+    let errors = AmountEntity::try_from_proto_all(p).unwrap_err();
+    let errors = errors.errors();
+    assert!(matches!(errors[0], ProtoMapError::OutOfRange { type_name: "u128", .. }));
+    assert_eq!(errors[0].path().to_string(), "u128_string");
+    assert!(matches!(errors[1], ProtoMapError::Custom { .. }));
+    assert_eq!(
+        errors[1].to_string(),
+        "expected 16 bytes for `u128` but found 3 at `u128_bytes`"
+    );
+    assert!(matches!(errors[2], ProtoMapError::Custom { .. }));
+    assert_eq!(errors[2].path().to_string(), "i128_string");
+}
+
+#[cfg(feature = "rust_decimal")]
+mod decimal {
+    use crate::proto;
+    use proto_mapper::with::UnitsNanos;
+    use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    impl UnitsNanos for proto::prost::Money {
+        fn from_units_nanos(units: i64, nanos: i32) -> Self {
+            Self {
+            units,
+            nanos,
+            ..Default::default()
+        }
+        }
+
+        fn units_nanos(&self) -> (i64, i32) {
+            (self.units, self.nanos)
+        }
+    }
+
+    #[derive(Debug, Clone, ProtoMap, PartialEq)]
+    #[proto_map(source = "proto::prost::AmountEntity")]
+    struct DecimalEntity {
+        #[proto_map(scalar, with = "proto_mapper::with::decimal_as_string")]
+        pub decimal_string: Decimal,
+        #[proto_map(with = "proto_mapper::with::decimal_as_units_nanos")]
+        pub decimal_money: Decimal,
+    }
+
+    #[test]
+    fn decimal_entity_round_trip() {
+        for value in ["0", "-1.5", "12345678901.123456789", "-0.000000001"] {
+            let value = Decimal::from_str(value).unwrap();
+            let original = DecimalEntity {
+                decimal_string: value,
+                decimal_money: value,
+            };
+
+            let tested = DecimalEntity::from_proto(original.to_proto()).unwrap();
+            assert_eq!(tested, original);
+        }
+    }
+
+    #[test]
+    fn decimal_entity_rounds_to_nanos() {
+        let original = DecimalEntity {
+            decimal_string: Decimal::from_str("1.0000000004").unwrap(),
+            decimal_money: Decimal::from_str("1.0000000004").unwrap(),
+        };
+
+        let tested = DecimalEntity::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested.decimal_string, original.decimal_string);
+        assert_eq!(tested.decimal_money, Decimal::ONE);
+    }
+
+    #[test]
+    fn decimal_entity_errors() {
+        let mut p = DecimalEntity {
+            decimal_string: Decimal::ONE,
+            decimal_money: Decimal::ONE,
+        }
+        .to_proto();
+        p.decimal_string = "0.00000000000000000000000000001".into();
+        p.decimal_money = Some(proto::prost::Money {
+        units: 1,
+        nanos: -1,
+        ..Default::default()
+    });
+
+        let errors = DecimalEntity::try_from_proto_all(p).unwrap_err();
+        let errors = errors.errors();
+        assert!(matches!(errors[0], ProtoMapError::Custom { .. }));
+        assert_eq!(
+            errors[0].to_string(),
+            "precision loss of `rust_decimal::Decimal` value `0.00000000000000000000000000001` at `decimal_string`"
+        );
+        assert!(matches!(errors[1], ProtoMapError::OutOfRange { .. }));
+        assert_eq!(errors[1].path().to_string(), "decimal_money");
+    }
+}
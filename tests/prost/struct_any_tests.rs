@@ -0,0 +1,158 @@
+use crate::proto;
+use proto_mapper::any::Any;
+use proto_mapper::{derive::ProtoMap, AnyRegistry, ProtoMap, ProtoMapAny, ProtoMapError, ProtoMapScalar};
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::EventCreated", any)]
+struct EventCreated {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::EventDeleted", type_name = "entities.schema.EventDeleted")]
+struct EventDeleted {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto_mapper::any::Any", any)]
+enum Event {
+    Created(EventCreated),
+    Deleted(EventDeleted),
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::EventEnvelope")]
+struct EventEnvelope {
+    pub sequence: u64,
+    #[proto_map(required)]
+    pub payload: Event,
+    pub history: Vec<Event>,
+    pub opt_payload: Option<Event>,
+}
+
+fn created() -> EventCreated {
+    EventCreated {
+        id: "1".into(),
+        name: "first".into(),
+    }
+}
+
+fn deleted() -> EventDeleted {
+    EventDeleted { id: "2".into() }
+}
+
+fn unknown_any(type_url: &str) -> Any {
+    Any {
+        type_url: type_url.into(),
+        value: vec![],
+    }
+}
+
+#[test]
+fn any_type_url() {
+    assert_eq!(EventCreated::type_name(), "entities.schema.EventCreated");
+    assert_eq!(
+        EventCreated::type_url(),
+        "type.googleapis.com/entities.schema.EventCreated"
+    );
+    assert_eq!(
+        EventDeleted::type_url(),
+        "type.googleapis.com/entities.schema.EventDeleted"
+    );
+}
+
+#[test]
+fn any_round_trip() {
+    let original = created();
+
+    let any = original.to_any();
+    assert_eq!(any.type_url, EventCreated::type_url());
+    let tested = EventCreated::from_any(any).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = EventCreated::from_any(original.clone().into_any()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn any_errors() {
+    let error = EventCreated::from_any(deleted().to_any()).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownTypeUrl { .. }));
+    assert_eq!(
+        error.to_string(),
+        "unknown type url `type.googleapis.com/entities.schema.EventDeleted` of `google.protobuf.Any`"
+    );
+
+    let mut any = created().to_any();
+    any.value = vec![0xff];
+    let error = EventCreated::from_any(any).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+}
+
+#[test]
+fn any_enum_envelope_round_trip() {
+    let original = EventEnvelope {
+        sequence: 7,
+        payload: Event::Created(created()),
+        history: vec![Event::Deleted(deleted()), Event::Created(created())],
+        opt_payload: None,
+    };
+
+    let tested = EventEnvelope::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = EventEnvelope::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn any_enum_unknown_type_url() {
+    let mut p = EventEnvelope {
+        sequence: 7,
+        payload: Event::Created(created()),
+        history: vec![],
+        opt_payload: Some(Event::Deleted(deleted())),
+    }
+    .to_proto();
+    p.history.push(unknown_any("example.com/entities.schema.Unknown"));
+
+    let error = EventEnvelope::from_proto(p).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownTypeUrl { .. }));
+    assert_eq!(error.path().to_string(), "history[0]");
+}
+
+#[test]
+fn any_registry_enum() {
+    let mut registry = AnyRegistry::<Event>::new();
+    registry
+        .register_with(Event::Created)
+        .register_with(Event::Deleted);
+
+    assert!(registry.contains("type.googleapis.com/entities.schema.EventCreated"));
+    assert!(registry.contains("entities.schema.EventDeleted"));
+
+    // Type urls of any domain refer to the same message
+    let mut any = deleted().to_any();
+    any.type_url = "example.com/entities.schema.EventDeleted".into();
+    let tested = registry.unpack(any).unwrap();
+    assert_eq!(tested, Event::Deleted(deleted()));
+
+    let error = registry.unpack(unknown_any("entities.schema.Unknown")).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownTypeUrl { .. }));
+}
+
+#[test]
+fn any_registry_boxed() {
+    let mut registry = AnyRegistry::new();
+    registry
+        .register_boxed::<EventCreated>()
+        .register_boxed::<EventDeleted>();
+
+    let tested = registry.unpack(created().to_any()).unwrap();
+    assert_eq!(tested.downcast::<EventCreated>().ok().map(|e| *e), Some(created()));
+
+    let tested = registry.unpack(deleted().to_any()).unwrap();
+    assert_eq!(tested.downcast::<EventDeleted>().ok().map(|e| *e), Some(deleted()));
+}
@@ -0,0 +1,108 @@
+use super::custom_mappings::uuid_as_string;
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityStatus", enumeration)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    pub string_f: String,
+    #[proto_map(enumeration)]
+    pub status: EntityStatus,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::MapEntity")]
+struct MapEntity {
+    pub entities: HashMap<String, ScalarEntity>,
+    pub names: BTreeMap<u32, String>,
+    #[proto_map(enumeration)]
+    pub statuses: HashMap<String, EntityStatus>,
+    #[proto_map(key_with = "uuid_as_string")]
+    pub uuid_counts: HashMap<Uuid, u64>,
+}
+
+fn entity(uint32_f: u32, status: EntityStatus) -> ScalarEntity {
+    ScalarEntity {
+        uint32_f,
+        string_f: format!("Foo{}", uint32_f),
+        status,
+    }
+}
+
+#[test]
+fn map_entity_round_trip() {
+    let original = MapEntity {
+        entities: HashMap::from([
+            ("foo".into(), entity(1, EntityStatus::StatusA)),
+            ("bar".into(), entity(2, EntityStatus::StatusC)),
+        ]),
+        names: BTreeMap::from([(1, "Foo".into()), (2, "Bar".into())]),
+        statuses: HashMap::from([("foo".into(), EntityStatus::StatusB)]),
+        uuid_counts: HashMap::from([(Uuid::new_v4(), 1), (Uuid::new_v4(), 2)]),
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.statuses, HashMap::from([("foo".into(), 1)]));
+    let tested = MapEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = MapEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = MapEntity::try_from_proto_all(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn map_entity_empty_round_trip() {
+    let original = MapEntity {
+        entities: HashMap::new(),
+        names: BTreeMap::new(),
+        statuses: HashMap::new(),
+        uuid_counts: HashMap::new(),
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p, proto::prost::MapEntity::default());
+    let tested = MapEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn map_entry_error_key() {
+    let proto = proto::prost::MapEntity {
+        entities: HashMap::from([(
+            "foo".into(),
+            proto::prost::ScalarEntity {
+                status: 42,
+                ..Default::default()
+            },
+        )]),
+        statuses: HashMap::from([("bar".into(), 7)]),
+        uuid_counts: HashMap::from([("not a uuid".into(), 1)]),
+        ..Default::default()
+    };
+
+    let error = MapEntity::from_proto(proto.clone()).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownEnumValue { value: 42, .. }));
+    assert_eq!(error.path().to_string(), "entities[foo].status");
+
+    let errors = MapEntity::try_from_proto_all(proto).unwrap_err();
+    let paths: Vec<_> = errors.errors().iter().map(|e| e.path().to_string()).collect();
+    assert_eq!(
+        paths,
+        vec!["entities[foo].status", "statuses[bar]", "uuid_counts"]
+    );
+    assert!(matches!(errors.errors()[2], ProtoMapError::Custom { .. }));
+}
@@ -0,0 +1,79 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar, ProtoScalar};
+use std::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::NumericEntity")]
+struct NumericEntity {
+    pub u8_f: u8,
+    pub u16_f: u16,
+    pub i8_f: i8,
+    pub i16_f: i16,
+    pub usize_f: usize,
+    pub isize_f: isize,
+    pub non_zero_u32_f: NonZeroU32,
+    pub non_zero_u64_f: NonZeroU64,
+    pub non_zero_i32_f: NonZeroI32,
+    pub non_zero_i64_f: std::num::NonZeroI64,
+    pub char_f: char,
+    pub opt_u8_f: Option<u8>,
+    pub u16_values: Vec<u16>,
+}
+
+fn numeric_entity() -> NumericEntity {
+    NumericEntity {
+        u8_f: u8::MAX,
+        u16_f: u16::MAX,
+        i8_f: i8::MIN,
+        i16_f: i16::MIN,
+        usize_f: usize::MAX,
+        isize_f: isize::MIN,
+        non_zero_u32_f: NonZeroU32::new(1).unwrap(),
+        non_zero_u64_f: NonZeroU64::new(u64::MAX).unwrap(),
+        non_zero_i32_f: NonZeroI32::new(-1).unwrap(),
+        non_zero_i64_f: NonZeroI64::new(i64::MIN).unwrap(),
+        char_f: '🦀',
+        opt_u8_f: Some(7),
+        u16_values: vec![0, 1, u16::MAX],
+    }
+}
+
+#[test]
+fn numeric_entity_round_trip() {
+    let original = numeric_entity();
+
+    let tested = NumericEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = NumericEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn numeric_entity_out_of_range() {
+    let mut p = numeric_entity().to_proto();
+    p.u8_f = 256;
+
+    let error = NumericEntity::from_proto(p).unwrap_err();
+    assert!(matches!(error, ProtoMapError::OutOfRange { type_name: "u8", .. }));
+    assert_eq!(error.to_string(), "value 256 is out of range for `u8` at `u8_f`");
+}
+
+#[test]
+fn numeric_entity_all_out_of_range() {
+    let mut p = numeric_entity().to_proto();
+    p.char_f = 0xD800;
+    p.non_zero_u32_f = 0;
+    p.u16_values = vec![1, 70_000];
+
+    let errors = NumericEntity::try_from_proto_all(p).unwrap_err();
+    let messages: Vec<_> = errors.errors().iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "value 0 is out of range for `NonZeroU32` at `non_zero_u32_f`",
+            "value 55296 is out of range for `char` at `char_f`",
+            "value 70000 is out of range for `u16` at `u16_values[1]`",
+        ]
+    );
+}
@@ -0,0 +1,154 @@
+use crate::proto;
+use proto_mapper::{
+    derive::ProtoMap, recursion_limit, with_recursion_limit, ProtoMap, ProtoMapError,
+    ProtoMapScalar, DEFAULT_RECURSION_LIMIT,
+};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityStatus", enumeration)]
+enum EntityStatus {
+    #[default]
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    #[proto_map(enumeration)]
+    pub status: EntityStatus,
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::NestedEntity")]
+struct SharedEntity {
+    pub first: Arc<ScalarEntity>,
+    pub second: Option<Rc<ScalarEntity>>,
+}
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::TreeNode")]
+struct TreeNode {
+    pub value: u32,
+    #[proto_map(boxed)]
+    pub left: Option<Box<TreeNode>>,
+    #[proto_map(boxed)]
+    pub right: Option<Box<TreeNode>>,
+    pub children: Vec<TreeNode>,
+}
+
+fn leaf(value: u32) -> TreeNode {
+    TreeNode {
+        value,
+        ..Default::default()
+    }
+}
+
+/// Returns a tree of `depth` nested nodes linked through the `left` field
+fn left_chain(depth: u32) -> proto::prost::TreeNode {
+    (1..depth).fold(
+        proto::prost::TreeNode {
+            value: 0,
+            ..Default::default()
+        },
+        |node, value| proto::prost::TreeNode {
+            value,
+            left: Some(Box::new(node)),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn recursive_entity_round_trip() {
+    let original = TreeNode {
+        value: 1,
+        left: Some(Box::new(TreeNode {
+            value: 2,
+            left: Some(Box::new(leaf(3))),
+            right: None,
+            children: vec![leaf(4), leaf(5)],
+        })),
+        right: Some(Box::new(leaf(6))),
+        children: vec![],
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.left.as_ref().unwrap().left.as_ref().unwrap().value, 3);
+    let tested = TreeNode::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = TreeNode::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = TreeNode::try_from_proto_all(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn shared_entity_round_trip() {
+    let original = SharedEntity {
+        first: Arc::new(ScalarEntity {
+            uint32_f: 1,
+            status: EntityStatus::StatusB,
+        }),
+        second: Some(Rc::new(ScalarEntity {
+            uint32_f: 2,
+            status: EntityStatus::StatusC,
+        })),
+    };
+
+    let p = original.to_proto();
+    let tested = SharedEntity::from_proto(p.clone()).unwrap();
+    assert_eq!(tested, original);
+
+    let shared = original.first.clone();
+    assert_eq!(original.into_proto(), p);
+    assert_eq!(shared.uint32_f, 1);
+}
+
+#[test]
+fn recursion_limit_exceeded() {
+    assert_eq!(recursion_limit(), DEFAULT_RECURSION_LIMIT);
+
+    let tested = TreeNode::from_proto(left_chain(DEFAULT_RECURSION_LIMIT as u32)).unwrap();
+    assert_eq!(tested.value, DEFAULT_RECURSION_LIMIT as u32 - 1);
+
+    let error = TreeNode::from_proto(left_chain(DEFAULT_RECURSION_LIMIT as u32 + 1)).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::RecursionLimitExceeded {
+            limit: DEFAULT_RECURSION_LIMIT,
+            ..
+        }
+    ));
+    assert_eq!(error.path().segments().len(), DEFAULT_RECURSION_LIMIT);
+
+    let errors = TreeNode::try_from_proto_all(left_chain(DEFAULT_RECURSION_LIMIT as u32 + 1)).unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    let error = with_recursion_limit(5, || TreeNode::from_proto(left_chain(6))).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::RecursionLimitExceeded { limit: 5, .. }
+    ));
+    assert_eq!(error.path().to_string(), "left.left.left.left.left");
+    assert!(with_recursion_limit(5, || TreeNode::from_proto(left_chain(5))).is_ok());
+
+    // The limit applies only to the conversions of the closure
+    assert_eq!(recursion_limit(), DEFAULT_RECURSION_LIMIT);
+    assert!(TreeNode::from_proto(left_chain(6)).is_ok());
+}
+
+#[test]
+fn recursion_limit_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        with_recursion_limit(5, || panic!("conversion panicked"));
+    });
+    assert!(result.is_err());
+    assert_eq!(recursion_limit(), DEFAULT_RECURSION_LIMIT);
+}
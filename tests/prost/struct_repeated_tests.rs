@@ -0,0 +1,114 @@
+use super::custom_mappings::uuid_as_string;
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::prost::EntityStatus", enumeration)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    pub string_f: String,
+    #[proto_map(enumeration)]
+    pub status: EntityStatus,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::RepeatedEntity")]
+struct RepeatedEntity {
+    pub entities: Vec<ScalarEntity>,
+    pub numbers: Vec<u32>,
+    pub names: Vec<String>,
+    #[proto_map(enumeration)]
+    pub statuses: Vec<EntityStatus>,
+    pub blobs: Vec<Vec<u8>>,
+    #[proto_map(scalar, with = "uuid_as_string")]
+    pub uuids: Vec<Uuid>,
+}
+
+fn entity(uint32_f: u32, status: EntityStatus) -> ScalarEntity {
+    ScalarEntity {
+        uint32_f,
+        string_f: format!("Foo{}", uint32_f),
+        status,
+    }
+}
+
+#[test]
+fn repeated_entity_round_trip() {
+    let original = RepeatedEntity {
+        entities: vec![entity(1, EntityStatus::StatusA), entity(2, EntityStatus::StatusC)],
+        numbers: vec![1, 2, 3],
+        names: vec!["Foo".into(), "Bar".into()],
+        statuses: vec![EntityStatus::StatusB, EntityStatus::StatusA],
+        blobs: vec!["Foo".as_bytes().to_vec(), vec![]],
+        uuids: vec![Uuid::new_v4(), Uuid::new_v4()],
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.statuses, vec![1, 0]);
+    let tested = RepeatedEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = RepeatedEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn repeated_entity_empty_round_trip() {
+    let original = RepeatedEntity {
+        entities: vec![],
+        numbers: vec![],
+        names: vec![],
+        statuses: vec![],
+        blobs: vec![],
+        uuids: vec![],
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p, proto::prost::RepeatedEntity::default());
+    let tested = RepeatedEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn repeated_element_error_index() {
+    let proto = proto::prost::RepeatedEntity {
+        entities: vec![
+            proto::prost::ScalarEntity::default(),
+            proto::prost::ScalarEntity {
+                status: 42,
+                ..Default::default()
+            },
+        ],
+        statuses: vec![0, 1, 7],
+        ..Default::default()
+    };
+
+    let error = RepeatedEntity::from_proto(proto.clone()).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownEnumValue { value: 42, .. }));
+    assert_eq!(error.path().to_string(), "entities[1].status");
+
+    let errors = RepeatedEntity::try_from_proto_all(proto).unwrap_err();
+    let paths: Vec<_> = errors.errors().iter().map(|e| e.path().to_string()).collect();
+    assert_eq!(paths, vec!["entities[1].status", "statuses[2]"]);
+}
+
+#[test]
+fn repeated_custom_element_error_index() {
+    let proto = proto::prost::RepeatedEntity {
+        uuids: vec![Uuid::new_v4().to_string(), "not a uuid".into()],
+        ..Default::default()
+    };
+
+    let error = RepeatedEntity::from_proto(proto).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+    assert_eq!(error.path().to_string(), "uuids[1]");
+}
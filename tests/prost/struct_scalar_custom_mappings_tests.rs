@@ -1,6 +1,6 @@
 use crate::proto;
 use proto_mapper::derive::ProtoMap;
-use proto_mapper::{ProtoMap, ProtoMapScalar, ProtoScalar};
+use proto_mapper::{ProtoMap, ProtoScalar};
 use super::custom_mappings::{uuid_as_bytes, uuid_as_string};
 use uuid::Uuid;
 
@@ -39,7 +39,6 @@ fn proto_entity_round_trip() {
         opt_uuid_str: Uuid::new_v4().to_string(),
         uuid_bytes: Uuid::new_v4().as_bytes().to_vec(),
         opt_uuid_bytes: Uuid::new_v4().as_bytes().to_vec(),
-        ..Default::default()
     };
 
     let e = EntityUuids::from_proto(original.clone()).unwrap();
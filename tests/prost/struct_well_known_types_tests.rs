@@ -0,0 +1,254 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::TimeEntity")]
+struct TimeEntity {
+    #[proto_map(with = "proto_mapper::with::system_time")]
+    pub system_time: SystemTime,
+    #[proto_map(with = "proto_mapper::with::system_time")]
+    pub opt_system_time: Option<SystemTime>,
+    #[proto_map(with = "proto_mapper::with::duration")]
+    pub duration: Duration,
+}
+
+#[test]
+fn time_entity_round_trip() {
+    let original = TimeEntity {
+        system_time: UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+        opt_system_time: Some(UNIX_EPOCH - Duration::new(10, 250_000_000)),
+        duration: Duration::new(90, 5),
+    };
+
+    let p = original.to_proto();
+    assert_eq!(
+        p.system_time,
+        Some(prost_types::Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 123_456_789
+        })
+    );
+    assert_eq!(
+        p.opt_system_time,
+        Some(prost_types::Timestamp {
+            seconds: -11,
+            nanos: 750_000_000
+        })
+    );
+    assert_eq!(
+        p.duration,
+        Some(prost_types::Duration {
+            seconds: 90,
+            nanos: 5
+        })
+    );
+
+    let tested = TimeEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = TimeEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn time_entity_missing_messages_map_the_default_proto() {
+    let tested = TimeEntity::from_proto(proto::prost::TimeEntity::default()).unwrap();
+    assert_eq!(
+        tested,
+        TimeEntity {
+            system_time: UNIX_EPOCH,
+            opt_system_time: None,
+            duration: Duration::ZERO,
+        }
+    );
+}
+
+#[test]
+fn time_entity_out_of_range() {
+    let proto = proto::prost::TimeEntity {
+        system_time: Some(prost_types::Timestamp {
+            seconds: 0,
+            nanos: -1,
+        }),
+        opt_system_time: Some(prost_types::Timestamp {
+            seconds: 253_402_300_800,
+            nanos: 0,
+        }),
+        duration: Some(prost_types::Duration {
+            seconds: -1,
+            nanos: 0,
+        }),
+        ..Default::default()
+    };
+
+    let error = TimeEntity::from_proto(proto.clone()).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::OutOfRange {
+            type_name: "std::time::SystemTime",
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "value 0s -1ns is out of range for `std::time::SystemTime` at `system_time`"
+    );
+
+    let errors = TimeEntity::try_from_proto_all(proto).unwrap_err();
+    let paths: Vec<_> = errors.errors().iter().map(|e| e.path().to_string()).collect();
+    assert_eq!(paths, vec!["system_time", "opt_system_time", "duration"]);
+}
+
+#[test]
+fn time_entity_saturates_to_the_valid_range() {
+    let original = TimeEntity {
+        system_time: UNIX_EPOCH + Duration::from_secs(300_000_000_000),
+        opt_system_time: Some(UNIX_EPOCH - Duration::new(100_000_000_000, 1)),
+        duration: Duration::MAX,
+    };
+
+    let p = original.to_proto();
+    assert_eq!(
+        p.system_time,
+        Some(prost_types::Timestamp {
+            seconds: 253_402_300_799,
+            nanos: 999_999_999
+        })
+    );
+    assert_eq!(
+        p.opt_system_time,
+        Some(prost_types::Timestamp {
+            seconds: -62_135_596_800,
+            nanos: 0
+        })
+    );
+    assert_eq!(
+        p.duration,
+        Some(prost_types::Duration {
+            seconds: 315_576_000_000,
+            nanos: 999_999_999
+        })
+    );
+    assert!(TimeEntity::from_proto(p).is_ok());
+
+    // The bounds of the valid range are not saturated
+    let original = TimeEntity {
+        system_time: UNIX_EPOCH + Duration::new(253_402_300_799, 999_999_999),
+        opt_system_time: Some(UNIX_EPOCH - Duration::from_secs(62_135_596_800)),
+        duration: Duration::from_secs(315_576_000_000),
+    };
+    let tested = TimeEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_tests {
+    use crate::proto;
+    use chrono::{DateTime, Duration, Utc};
+    use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError};
+
+    #[derive(Debug, Clone, ProtoMap, PartialEq)]
+    #[proto_map(source = "proto::prost::TimeEntity")]
+    struct ChronoTimeEntity {
+        #[proto_map(with = "proto_mapper::with::chrono_datetime")]
+        pub chrono_time: DateTime<Utc>,
+        #[proto_map(with = "proto_mapper::with::chrono_duration")]
+        pub chrono_duration: Duration,
+    }
+
+    #[test]
+    fn chrono_time_entity_round_trip() {
+        let original = ChronoTimeEntity {
+            chrono_time: DateTime::from_timestamp(-1_700_000_000, 42).unwrap(),
+            chrono_duration: Duration::milliseconds(-1_500),
+        };
+
+        let p = original.to_proto();
+        assert_eq!(
+            p.chrono_duration,
+            Some(prost_types::Duration {
+                seconds: -1,
+                nanos: -500_000_000
+            })
+        );
+        let tested = ChronoTimeEntity::from_proto(p).unwrap();
+        assert_eq!(tested, original);
+    }
+
+    #[test]
+    fn chrono_time_entity_saturates_to_the_valid_range() {
+        let original = ChronoTimeEntity {
+            chrono_time: DateTime::<Utc>::MIN_UTC,
+            chrono_duration: Duration::try_seconds(-400_000_000_000).unwrap(),
+        };
+
+        let p = original.to_proto();
+        assert_eq!(
+            p.chrono_time,
+            Some(prost_types::Timestamp {
+                seconds: -62_135_596_800,
+                nanos: 0
+            })
+        );
+        assert_eq!(
+            p.chrono_duration,
+            Some(prost_types::Duration {
+                seconds: -315_576_000_000,
+                nanos: -999_999_999
+            })
+        );
+        assert!(ChronoTimeEntity::from_proto(p).is_ok());
+    }
+
+    #[test]
+    fn chrono_time_entity_out_of_range() {
+        let proto = proto::prost::TimeEntity {
+            chrono_duration: Some(prost_types::Duration {
+                seconds: 1,
+                nanos: -1,
+            }),
+            ..Default::default()
+        };
+
+        let error = ChronoTimeEntity::from_proto(proto).unwrap_err();
+        assert!(matches!(error, ProtoMapError::OutOfRange { .. }));
+        assert_eq!(error.path().to_string(), "chrono_duration");
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_tests {
+    use crate::proto;
+    use proto_mapper::{derive::ProtoMap, ProtoMap};
+    use time::OffsetDateTime;
+
+    #[derive(Debug, Clone, ProtoMap, PartialEq)]
+    #[proto_map(source = "proto::prost::TimeEntity")]
+    struct OffsetTimeEntity {
+        #[proto_map(with = "proto_mapper::with::offset_datetime")]
+        pub offset_time: OffsetDateTime,
+    }
+
+    #[test]
+    fn offset_time_entity_round_trip() {
+        let original = OffsetTimeEntity {
+            offset_time: OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_123_456_789).unwrap(),
+        };
+
+        let p = original.to_proto();
+        assert_eq!(
+            p.offset_time,
+            Some(prost_types::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 123_456_789
+            })
+        );
+        let tested = OffsetTimeEntity::from_proto(p).unwrap();
+        assert_eq!(tested, original);
+        assert_eq!(
+            OffsetTimeEntity::from_proto(proto::prost::TimeEntity::default()).unwrap().offset_time,
+            OffsetDateTime::UNIX_EPOCH
+        );
+    }
+}
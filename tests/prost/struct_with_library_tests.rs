@@ -0,0 +1,161 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoScalar};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::{NonZeroIsize, NonZeroU8};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::prost::LibraryEntity")]
+struct StdEntity {
+    #[proto_map(scalar, with = "proto_mapper::with::ip_addr_as_string")]
+    pub ip_str: IpAddr,
+    #[proto_map(scalar, with = "proto_mapper::with::ip_addr_as_bytes")]
+    pub ip_bytes: IpAddr,
+    #[proto_map(scalar, with = "proto_mapper::with::socket_addr_as_string")]
+    pub socket_str: SocketAddr,
+    #[proto_map(scalar, with = "proto_mapper::with::socket_addr_as_bytes")]
+    pub socket_bytes: SocketAddr,
+    #[proto_map(scalar, with = "proto_mapper::with::path_buf_as_string")]
+    pub path: PathBuf,
+    #[proto_map(scalar, with = "proto_mapper::with::non_zero_u8")]
+    pub non_zero_u8: NonZeroU8,
+    #[proto_map(scalar, with = "proto_mapper::with::non_zero_isize")]
+    pub non_zero_isize: NonZeroIsize,
+    #[proto_map(scalar, with = "proto_mapper::with::ip_addr_as_string")]
+    pub opt_ip_str: Option<IpAddr>,
+}
+
+fn std_entity(ip: IpAddr) -> StdEntity {
+    StdEntity {
+        ip_str: ip,
+        ip_bytes: ip,
+        socket_str: SocketAddr::new(ip, 8080),
+        socket_bytes: SocketAddr::new(ip, 65535),
+        path: PathBuf::from("/var/lib/entities"),
+        non_zero_u8: NonZeroU8::new(255).unwrap(),
+        non_zero_isize: NonZeroIsize::new(-1).unwrap(),
+        opt_ip_str: Some(ip),
+    }
+}
+
+#[test]
+fn std_entity_round_trip() {
+    for ip in [
+        IpAddr::from(Ipv4Addr::LOCALHOST),
+        IpAddr::from(Ipv6Addr::LOCALHOST),
+    ] {
+        let original = std_entity(ip);
+
+        let tested = StdEntity::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = StdEntity::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+}
+
+#[test]
+fn std_entity_missing_optional() {
+    let mut original = std_entity(Ipv4Addr::LOCALHOST.into());
+    original.opt_ip_str = None;
+
+    let tested = StdEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn std_entity_errors() {
+    let mut p = std_entity(Ipv4Addr::LOCALHOST.into()).to_proto();
+    p.ip_str = "256.0.0.1".into();
+    p.ip_bytes = vec![127, 0, 0];
+    p.socket_bytes = vec![1];
+    p.non_zero_u8 = 256;
+    p.non_zero_isize = 0;
+
+    let errors = StdEntity::try_from_proto_all(p).unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 5);
+    assert!(matches!(errors[0], ProtoMapError::Custom { .. }));
+    assert_eq!(
+        errors[0].to_string(),
+        "invalid `IpAddr` value `256.0.0.1`: invalid IP address syntax at `ip_str`"
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "expected 4 or 16 bytes for `IpAddr` but found 3 at `ip_bytes`"
+    );
+    assert_eq!(
+        errors[2].to_string(),
+        "expected 6 or 18 bytes for `SocketAddr` but found 1 at `socket_bytes`"
+    );
+    assert!(matches!(
+        errors[3],
+        ProtoMapError::OutOfRange { type_name: "NonZeroU8", .. }
+    ));
+    assert!(matches!(
+        errors[4],
+        ProtoMapError::OutOfRange { type_name: "NonZeroIsize", .. }
+    ));
+}
+
+#[cfg(all(feature = "uuid", feature = "url", feature = "semver"))]
+mod third_party {
+    use crate::proto;
+    use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoScalar};
+    use semver::Version;
+    use url::Url;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, ProtoMap, PartialEq)]
+    #[proto_map(source = "proto::prost::LibraryEntity")]
+    struct ThirdPartyEntity {
+        #[proto_map(scalar, with = "proto_mapper::with::uuid_as_string")]
+        pub uuid_str: Uuid,
+        #[proto_map(scalar, with = "proto_mapper::with::uuid_as_bytes")]
+        pub uuid_bytes: Option<Uuid>,
+        #[proto_map(scalar, with = "proto_mapper::with::url_as_string")]
+        pub url: Url,
+        #[proto_map(scalar, with = "proto_mapper::with::version_as_string")]
+        pub version: Version,
+    }
+
+    fn third_party_entity() -> ThirdPartyEntity {
+        ThirdPartyEntity {
+            uuid_str: Uuid::new_v4(),
+            uuid_bytes: Some(Uuid::new_v4()),
+            url: Url::parse("https://example.com/entities?id=1").unwrap(),
+            version: Version::parse("1.2.3-beta.1+build.5").unwrap(),
+        }
+    }
+
+    #[test]
+    fn third_party_entity_round_trip() {
+        let original = third_party_entity();
+
+        let tested = ThirdPartyEntity::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = ThirdPartyEntity::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+
+    #[test]
+    fn third_party_entity_errors() {
+        let mut p = third_party_entity().to_proto();
+        p.uuid_bytes = vec![0; 15];
+        p.url = "no scheme".into();
+        p.version = "1.0".into();
+
+        let errors = ThirdPartyEntity::try_from_proto_all(p).unwrap_err();
+        let errors = errors.errors();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[0].to_string(),
+            "expected 16 bytes for `uuid::Uuid` but found 15 at `uuid_bytes`"
+        );
+        assert!(matches!(errors[1], ProtoMapError::Custom { .. }));
+        assert_eq!(errors[1].path().to_string(), "url");
+        assert!(matches!(errors[2], ProtoMapError::Custom { .. }));
+        assert_eq!(errors[2].path().to_string(), "version");
+    }
+}
@@ -1,10 +1,13 @@
 #[cfg(feature="protobuf")]
+#[allow(dead_code)]
 pub mod protobuf {
     include!(concat!(env!("OUT_DIR"), "/mod.rs"));
     pub use entities::*;
+    pub use legacy::*;
 }
 
 #[cfg(feature="prost")]
+#[allow(dead_code)]
 pub mod prost {
     include!(concat!(env!("OUT_DIR"), "/entities.schema.rs"));
 }
@@ -0,0 +1,152 @@
+use super::custom_mappings::uuid_as_string;
+use crate::proto;
+use proto_mapper::{
+    derive::ProtoMap, ProtoMap, ProtoMapEnum, ProtoMapError, ProtoMapScalar, ProtoScalar,
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityStatus",
+    enumeration,
+    rename_variants = "STREAMING_SNAKE_CASE"
+)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    pub string_f: String,
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::NestedEntity")]
+struct NestedEntity {
+    #[proto_map(required)]
+    pub first: ScalarEntity,
+    pub second: Option<ScalarEntity>,
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(
+    source = "proto::protobuf::HierarchyEntity",
+    one_of(field = "data"),
+    rename_variants = "snake_case"
+)]
+enum HierarchyEntity {
+    FirstEntity(ScalarEntity),
+    SecondEntity(NestedEntity),
+}
+
+#[derive(Debug, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::EntityUuids")]
+struct EntityUuids {
+    #[proto_map(scalar, with = "uuid_as_string")]
+    uuid_str: Uuid,
+    #[proto_map(scalar, with = "uuid_as_string")]
+    opt_uuid_str: Option<Uuid>,
+}
+
+#[test]
+fn unknown_enumeration_value_error() {
+    let error =
+        EntityStatus::from_enum_or_unknown(protobuf::EnumOrUnknown::from_i32(42)).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::UnknownEnumValue {
+            type_name: "EntityStatus",
+            value: 42,
+            ..
+        }
+    ));
+    assert!(error.path().is_empty());
+}
+
+#[test]
+fn missing_required_message_error() {
+    let error = NestedEntity::from_proto(proto::protobuf::NestedEntity::default()).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::MissingMessage {
+            type_name: "proto::protobuf::NestedEntity",
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "first");
+}
+
+#[test]
+fn nested_error_field_path() {
+    let mut nested = proto::protobuf::NestedEntity::default();
+    nested.set_second(proto::protobuf::ScalarEntity::default());
+    let mut proto = proto::protobuf::HierarchyEntity::default();
+    proto.set_second_entity(nested);
+
+    let error = HierarchyEntity::from_proto(proto).unwrap_err();
+    assert!(matches!(error, ProtoMapError::MissingMessage { .. }));
+    assert_eq!(error.path().to_string(), "second_entity.first");
+    assert_eq!(
+        error.to_string(),
+        "missing required message field of `proto::protobuf::NestedEntity` at `second_entity.first`"
+    );
+}
+
+#[test]
+fn one_of_not_set_error() {
+    let error = HierarchyEntity::from_proto(proto::protobuf::HierarchyEntity::default()).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::OneOfNotSet {
+            type_name: "proto::protobuf::HierarchyEntity",
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "data");
+}
+
+#[test]
+fn custom_mapping_error() {
+    let mut proto = proto::protobuf::EntityUuids::default();
+    proto.set_uuid_str("not a uuid".into());
+
+    let error = EntityUuids::from_proto(proto).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+    assert_eq!(error.path().to_string(), "uuid_str");
+    assert!(std::error::Error::source(&error).is_some());
+}
+
+#[test]
+fn collect_all_errors() {
+    let mut proto = proto::protobuf::EntityUuids::default();
+    proto.set_uuid_str("not a uuid".into());
+    proto.set_opt_uuid_str("not a uuid either".into());
+
+    let errors = EntityUuids::try_from_proto_all(proto).unwrap_err();
+    let paths: Vec<_> = errors.errors().iter().map(|e| e.path().to_string()).collect();
+    assert_eq!(paths, vec!["uuid_str", "opt_uuid_str"]);
+
+    let mut nested = proto::protobuf::NestedEntity::default();
+    nested.set_second(proto::protobuf::ScalarEntity::default());
+    let mut proto = proto::protobuf::HierarchyEntity::default();
+    proto.set_second_entity(nested);
+
+    let errors = HierarchyEntity::try_from_proto_all(proto).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.errors()[0].path().to_string(), "second_entity.first");
+}
+
+#[test]
+fn collect_all_errors_success() {
+    let original = EntityUuids {
+        uuid_str: Uuid::new_v4(),
+        opt_uuid_str: None,
+    };
+
+    let tested = EntityUuids::try_from_proto_all(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
@@ -0,0 +1,76 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap};
+
+// The same enums map to the `prost` enumerations (see `prost/enum_naming_tests.rs`)
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityState",
+    enumeration,
+    rename_variants = "auto",
+    add_prefix = "ENTITY_STATE_"
+)]
+enum EntityState {
+    Unspecified,
+    #[proto_map(alias = "ENTITY_STATE_LEGACY_ACTIVE")]
+    Active,
+    Disabled,
+    Archived,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityType",
+    enumeration,
+    rename_variants = "auto",
+    strip_prefix = "Kind",
+    add_prefix = "TYPE_"
+)]
+enum EntityKind {
+    KindA,
+    KindB,
+    KindC,
+}
+
+#[test]
+fn auto_naming_keeps_proto_names() {
+    let states = [
+        (
+            EntityState::Unspecified,
+            proto::protobuf::EntityState::ENTITY_STATE_UNSPECIFIED,
+        ),
+        (
+            EntityState::Active,
+            proto::protobuf::EntityState::ENTITY_STATE_ACTIVE,
+        ),
+        (
+            EntityState::Disabled,
+            proto::protobuf::EntityState::ENTITY_STATE_DISABLED,
+        ),
+        (
+            EntityState::Archived,
+            proto::protobuf::EntityState::ENTITY_STATE_ARCHIVED,
+        ),
+    ];
+    for (state, proto_state) in states {
+        assert_eq!(state.to_proto(), proto_state);
+        assert_eq!(EntityState::from_proto(proto_state).unwrap(), state);
+    }
+
+    let tested =
+        EntityState::from_proto(proto::protobuf::EntityState::ENTITY_STATE_LEGACY_ACTIVE).unwrap();
+    assert_eq!(tested, EntityState::Active);
+}
+
+#[test]
+fn auto_naming_with_prefixes() {
+    let kinds = [
+        (EntityKind::KindA, proto::protobuf::EntityType::TYPE_A),
+        (EntityKind::KindB, proto::protobuf::EntityType::TYPE_B),
+        (EntityKind::KindC, proto::protobuf::EntityType::TYPE_C),
+    ];
+    for (kind, proto_kind) in kinds {
+        assert_eq!(kind.to_proto(), proto_kind);
+        assert_eq!(EntityKind::from_proto(proto_kind).unwrap(), kind);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::proto;
+use crate::protobuf::custom_mappings::uuid_as_string;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapOneOf, ProtoMapScalar};
+use protobuf::well_known_types::empty::Empty;
+use protobuf::EnumOrUnknown;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityStatus",
+    enumeration,
+    rename_variants = "STREAMING_SNAKE_CASE"
+)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::protobuf::IdentityEntity", one_of(field = "id"))]
+enum Identity {
+    Email(String),
+    Account(i64),
+    Anonymous,
+    #[proto_map(enumeration)]
+    Status(EntityStatus),
+    #[proto_map(scalar, with = "uuid_as_string")]
+    Uuid(Uuid),
+    Guest,
+}
+
+fn identities() -> Vec<Identity> {
+    vec![
+        Identity::Email("foo@bar.com".into()),
+        Identity::Account(42),
+        Identity::Anonymous,
+        Identity::Status(EntityStatus::StatusB),
+        Identity::Uuid(Uuid::new_v4()),
+        Identity::Guest,
+    ]
+}
+
+#[test]
+fn one_of_payloads_round_trip() {
+    for original in identities() {
+        let tested = Identity::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = Identity::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = Identity::try_from_proto_all(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+}
+
+#[test]
+fn one_of_payloads_to_members() {
+    use proto::protobuf::identity_entity::Id;
+
+    assert_eq!(
+        Identity::Email("foo@bar.com".into()).to_one_of(),
+        Some(Id::Email("foo@bar.com".into()))
+    );
+    assert_eq!(Identity::Account(42).to_one_of(), Some(Id::Account(42)));
+    assert_eq!(
+        Identity::Anonymous.to_one_of(),
+        Some(Id::Anonymous(Empty::new()))
+    );
+    assert_eq!(
+        Identity::Status(EntityStatus::StatusC).to_one_of(),
+        Some(Id::Status(EnumOrUnknown::new(
+            proto::protobuf::EntityStatus::STATUS_C
+        )))
+    );
+    assert_eq!(
+        Identity::Uuid(Uuid::nil()).into_one_of(),
+        Some(Id::Uuid(Uuid::nil().to_string()))
+    );
+    assert_eq!(
+        Identity::Guest.to_one_of(),
+        Some(Id::Guest(proto::protobuf::EmptyEntity::new()))
+    );
+}
+
+#[test]
+fn one_of_payload_errors() {
+    use proto::protobuf::identity_entity::Id;
+
+    let error = Identity::from_one_of(Some(Id::Status(EnumOrUnknown::from_i32(42)))).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownEnumValue { value: 42, .. }));
+    assert_eq!(error.path().to_string(), "status");
+
+    let error = Identity::from_one_of(Some(Id::Uuid("foo".into()))).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+    assert_eq!(error.path().to_string(), "uuid");
+
+    let errors = Identity::try_from_one_of_all(Some(Id::Uuid("foo".into()))).unwrap_err();
+    assert_eq!(errors.len(), 1);
+}
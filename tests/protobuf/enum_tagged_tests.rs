@@ -0,0 +1,235 @@
+use crate::proto;
+use crate::protobuf::custom_mappings::uuid_as_string;
+use proto::protobuf::{EventKind, TaggedEventEntity};
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::protobuf::EventCreated")]
+struct EventCreated {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::protobuf::TaggedEventEntity", tagged(tag = "kind"))]
+enum Event {
+    #[proto_map(tag = "proto::protobuf::EventKind::EVENT_KIND_CREATED")]
+    Created(EventCreated),
+    #[proto_map(
+        tag = "proto::protobuf::EventKind::EVENT_KIND_RENAMED",
+        payload = "name"
+    )]
+    Renamed(String),
+    #[proto_map(tag = "proto::protobuf::EventKind::EVENT_KIND_DELETED")]
+    Deleted {
+        #[proto_map(scalar, with = "uuid_as_string")]
+        id: Uuid,
+    },
+    #[proto_map(tag = "proto::protobuf::EventKind::EVENT_KIND_MOVED")]
+    Moved {
+        x: f64,
+        #[proto_map(rename = "y")]
+        y_pos: f64,
+        #[proto_map(skip)]
+        cached: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::protobuf::TaggedEventEntity", tagged(tag = "kind"))]
+enum FallbackEvent {
+    #[proto_map(tag = "proto::protobuf::EventKind::EVENT_KIND_CREATED")]
+    Created(EventCreated),
+    #[proto_map(tag = "proto::protobuf::EventKind::EVENT_KIND_RENAMED")]
+    Renamed {
+        #[proto_map(required)]
+        created: EventCreated,
+        name: String,
+    },
+    #[proto_map(tag = "proto::protobuf::EventKind::EVENT_KIND_MOVED")]
+    Moved { x: f64 },
+    #[proto_map(other)]
+    Other,
+}
+
+fn created() -> EventCreated {
+    EventCreated {
+        id: "1".into(),
+        name: "Foo".into(),
+    }
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        Event::Created(created()),
+        Event::Renamed("Bar".into()),
+        Event::Deleted { id: Uuid::new_v4() },
+        Event::Moved {
+            x: 1.0,
+            y_pos: -2.0,
+            cached: false,
+        },
+    ]
+}
+
+#[test]
+fn tagged_variants_round_trip() {
+    for original in events() {
+        let tested = Event::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = Event::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+
+        let tested = Event::try_from_proto_all(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+}
+
+#[test]
+fn tagged_variants_set_the_tag_and_the_payload() {
+    let p = Event::Created(created()).to_proto();
+    assert_eq!(p.kind, EventKind::EVENT_KIND_CREATED.into());
+    assert_eq!(p.created.as_ref(), Some(&created().to_proto()));
+
+    let p = Event::Renamed("Bar".into()).into_proto();
+    assert_eq!(p.kind, EventKind::EVENT_KIND_RENAMED.into());
+    assert_eq!(p.name, "Bar");
+    assert!(p.created.is_none());
+
+    let p = Event::Moved {
+        x: 1.0,
+        y_pos: -2.0,
+        cached: true,
+    }
+    .to_proto();
+    assert_eq!(p.kind, EventKind::EVENT_KIND_MOVED.into());
+    assert_eq!((p.x, p.y), (1.0, -2.0));
+
+    let tested = Event::from_proto(p).unwrap();
+    assert!(matches!(tested, Event::Moved { cached: false, .. }));
+}
+
+#[test]
+fn tagged_unknown_tag_error() {
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_UNSPECIFIED.into(),
+        ..Default::default()
+    };
+
+    let error = Event::from_proto(p.clone()).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::UnknownEnumValue {
+            type_name: "proto::protobuf::EventKind",
+            value: 0,
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "kind");
+
+    let errors = Event::try_from_proto_all(p).unwrap_err();
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn tagged_payload_mismatch_error() {
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_CREATED.into(),
+        name: "Bar".into(),
+        ..Default::default()
+    };
+
+    let error = Event::from_proto(p).unwrap_err();
+    assert!(matches!(error, ProtoMapError::MissingMessage { .. }));
+    assert_eq!(error.path().to_string(), "created");
+
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_DELETED.into(),
+        id: "foo".into(),
+        ..Default::default()
+    };
+    let errors = Event::try_from_proto_all(p).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.errors()[0].path().to_string(), "id");
+}
+
+#[test]
+fn tagged_other_tag_payload_error() {
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_MOVED.into(),
+        created: protobuf::MessageField::some(created().to_proto()),
+        ..Default::default()
+    };
+
+    let error = Event::from_proto(p.clone()).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::TaggedFieldMismatch {
+            type_name: "proto::protobuf::TaggedEventEntity",
+            tag: 4,
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "created");
+    assert_eq!(
+        error.to_string(),
+        "payload field of another tag than 4 is set in `proto::protobuf::TaggedEventEntity` at `created`"
+    );
+
+    let errors = Event::try_from_proto_all(p).unwrap_err();
+    assert_eq!(errors.len(), 1);
+
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_RENAMED.into(),
+        created: protobuf::MessageField::some(created().to_proto()),
+        name: "Bar".into(),
+        ..Default::default()
+    };
+    let error = Event::from_proto(p).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::TaggedFieldMismatch { tag: 2, .. }
+    ));
+    assert_eq!(error.path().to_string(), "created");
+}
+
+#[test]
+fn tagged_fallback_variant() {
+    let original = FallbackEvent::Renamed {
+        created: created(),
+        name: "Bar".into(),
+    };
+    let tested = FallbackEvent::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    // Unknown tags
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_DELETED.into(),
+        ..Default::default()
+    };
+    assert_eq!(FallbackEvent::from_proto(p).unwrap(), FallbackEvent::Other);
+
+    // Payloads that disagree with the tag
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_RENAMED.into(),
+        name: "Bar".into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        FallbackEvent::try_from_proto_all(p).unwrap(),
+        FallbackEvent::Other
+    );
+
+    // Payloads of another tag
+    let p = TaggedEventEntity {
+        kind: EventKind::EVENT_KIND_MOVED.into(),
+        created: protobuf::MessageField::some(created().to_proto()),
+        ..Default::default()
+    };
+    assert_eq!(FallbackEvent::from_proto(p).unwrap(), FallbackEvent::Other);
+
+    let p = FallbackEvent::Other.to_proto();
+    assert_eq!(p.kind, EventKind::EVENT_KIND_UNSPECIFIED.into());
+}
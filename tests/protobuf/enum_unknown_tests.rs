@@ -0,0 +1,141 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapEnum, ProtoMapScalar};
+use protobuf::EnumOrUnknown;
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityStatus",
+    enumeration,
+    rename_variants = "STREAMING_SNAKE_CASE"
+)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+    #[proto_map(unknown)]
+    Unrecognized(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityType",
+    enumeration,
+    rename_variants = "STREAMING_SNAKE_CASE"
+)]
+enum EntityType {
+    TypeA,
+    TypeB,
+    #[proto_map(other)]
+    TypeC,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    #[proto_map(enumeration)]
+    pub status: EntityStatus,
+    #[proto_map(enumeration, rename = "type_")]
+    pub r#type: EntityType,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::RepeatedEntity")]
+struct RepeatedEntity {
+    #[proto_map(enumeration)]
+    pub statuses: Vec<EntityStatus>,
+}
+
+#[test]
+fn unknown_variant_round_trip() {
+    let original = EntityStatus::Unrecognized(42);
+
+    let p = original.to_enum_or_unknown();
+    assert_eq!(p.value(), 42);
+    assert_eq!(EntityStatus::from_enum_or_unknown(p).unwrap(), original);
+
+    let p = EnumOrUnknown::new(proto::protobuf::EntityStatus::STATUS_B);
+    assert_eq!(
+        EntityStatus::from_enum_or_unknown(p).unwrap(),
+        EntityStatus::StatusB
+    );
+
+    // The proto enumeration has no unknown values
+    assert_eq!(original.to_proto(), proto::protobuf::EntityStatus::STATUS_A);
+}
+
+#[test]
+fn other_variant_from_unknown_value() {
+    let p = EnumOrUnknown::from_i32(42);
+    assert_eq!(
+        EntityType::from_enum_or_unknown(p).unwrap(),
+        EntityType::TypeC
+    );
+    assert_eq!(
+        EntityType::TypeC.to_enum_or_unknown(),
+        EnumOrUnknown::new(proto::protobuf::EntityType::TYPE_C)
+    );
+}
+
+#[test]
+fn entity_unknown_values_round_trip() {
+    let original = proto::protobuf::ScalarEntity {
+        uint32_f: 1,
+        status: EnumOrUnknown::from_i32(42),
+        type_: EnumOrUnknown::from_i32(42),
+        ..Default::default()
+    };
+
+    let e = ScalarEntity::from_proto(original.clone()).unwrap();
+    assert_eq!(
+        e,
+        ScalarEntity {
+            uint32_f: 1,
+            status: EntityStatus::Unrecognized(42),
+            r#type: EntityType::TypeC,
+        }
+    );
+
+    let p = e.into_proto();
+    assert_eq!(p.status.value(), 42);
+    assert_eq!(
+        p.type_,
+        EnumOrUnknown::new(proto::protobuf::EntityType::TYPE_C)
+    );
+}
+
+#[test]
+fn entity_unknown_variant_round_trip() {
+    let original = ScalarEntity {
+        uint32_f: 1,
+        status: EntityStatus::Unrecognized(42),
+        r#type: EntityType::TypeA,
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.status.value(), 42);
+    assert_eq!(ScalarEntity::from_proto(p).unwrap(), original);
+}
+
+#[test]
+fn repeated_unknown_values_round_trip() {
+    let original = proto::protobuf::RepeatedEntity {
+        statuses: vec![
+            EnumOrUnknown::from_i32(1),
+            EnumOrUnknown::from_i32(42),
+            EnumOrUnknown::from_i32(-1),
+        ],
+        ..Default::default()
+    };
+
+    let e = RepeatedEntity::from_proto(original.clone()).unwrap();
+    assert_eq!(
+        e.statuses,
+        vec![
+            EntityStatus::StatusB,
+            EntityStatus::Unrecognized(42),
+            EntityStatus::Unrecognized(-1)
+        ]
+    );
+    assert_eq!(e.to_proto(), original);
+}
@@ -0,0 +1,120 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapEnum, ProtoMapError};
+use protobuf::EnumOrUnknown;
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::protobuf::EntityState", enumeration)]
+enum EntityState {
+    #[proto_map(rename = "ENTITY_STATE_UNSPECIFIED")]
+    Unknown,
+    #[proto_map(
+        rename = "ENTITY_STATE_ACTIVE",
+        alias = "ENTITY_STATE_ENABLED",
+        alias = "ENTITY_STATE_LEGACY_ACTIVE"
+    )]
+    Active,
+    #[proto_map(rename = "ENTITY_STATE_DISABLED")]
+    Disabled,
+    #[proto_map(value = 3)]
+    Archived,
+    #[proto_map(value = 7)]
+    Removed,
+}
+
+/// Maps only the active values of the proto enumeration
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(source = "proto::protobuf::EntityState", enumeration)]
+enum ActiveState {
+    #[proto_map(rename = "ENTITY_STATE_ACTIVE", alias = "ENTITY_STATE_ENABLED")]
+    Active,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::StateEntity")]
+struct StateEntity {
+    #[proto_map(enumeration)]
+    pub state: EntityState,
+    #[proto_map(enumeration)]
+    pub states: Vec<EntityState>,
+}
+
+#[test]
+fn renamed_variant_round_trip() {
+    assert_eq!(
+        EntityState::Unknown.to_proto(),
+        proto::protobuf::EntityState::ENTITY_STATE_UNSPECIFIED
+    );
+    let tested =
+        EntityState::from_proto(proto::protobuf::EntityState::ENTITY_STATE_UNSPECIFIED).unwrap();
+    assert_eq!(tested, EntityState::Unknown);
+}
+
+#[test]
+fn alias_decodes_to_variant() {
+    for alias in [
+        proto::protobuf::EntityState::ENTITY_STATE_ENABLED,
+        proto::protobuf::EntityState::ENTITY_STATE_LEGACY_ACTIVE,
+    ] {
+        assert_eq!(EntityState::from_proto(alias).unwrap(), EntityState::Active);
+    }
+
+    // Aliases are only decoded
+    assert_eq!(
+        protobuf::Enum::value(&EntityState::Active.to_proto()),
+        protobuf::Enum::value(&proto::protobuf::EntityState::ENTITY_STATE_ACTIVE)
+    );
+}
+
+#[test]
+fn raw_value_round_trip() {
+    assert_eq!(protobuf::Enum::value(&EntityState::Archived.to_proto()), 3);
+    let tested =
+        EntityState::from_proto(proto::protobuf::EntityState::ENTITY_STATE_ARCHIVED).unwrap();
+    assert_eq!(tested, EntityState::Archived);
+
+    // Values unknown to the proto enumeration are kept by the `EnumOrUnknown` fields
+    let p = EntityState::Removed.to_enum_or_unknown();
+    assert_eq!(p.value(), 7);
+    assert_eq!(
+        EntityState::from_enum_or_unknown(p).unwrap(),
+        EntityState::Removed
+    );
+    let error = EntityState::from_enum_or_unknown(EnumOrUnknown::from_i32(8)).unwrap_err();
+    assert!(matches!(error, ProtoMapError::UnknownEnumValue { value: 8, .. }));
+}
+
+#[test]
+fn unmapped_value_fails() {
+    let error =
+        ActiveState::from_proto(proto::protobuf::EntityState::ENTITY_STATE_DISABLED).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::UnknownEnumValue { value: 2, .. }
+    ));
+}
+
+#[test]
+fn entity_round_trip() {
+    let original = StateEntity {
+        state: EntityState::Removed,
+        states: vec![
+            EntityState::Unknown,
+            EntityState::Active,
+            EntityState::Disabled,
+            EntityState::Archived,
+        ],
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.state.value(), 7);
+    assert_eq!(
+        p.states
+            .iter()
+            .map(|state| state.value())
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2, 3]
+    );
+
+    let tested = StateEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
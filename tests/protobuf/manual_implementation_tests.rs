@@ -1,5 +1,8 @@
+// Manual implementations intentionally mirror the code generated by the macro
+#![allow(clippy::field_reassign_with_default, clippy::useless_conversion)]
+
 use crate::proto;
-use proto_mapper::{ProtoMap, ProtoMapScalar, ProtoScalar};
+use proto_mapper::{ProtoMap, ProtoMapError, ProtoMapScalar, ProtoScalar};
 use protobuf::Enum;
 
 /// Fully expanded and manual experiments (these used to build the macros and the library traits synergy)
@@ -22,7 +25,7 @@ impl ProtoMap for EntityStatus {
         }
     }
 
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         match proto {
             proto::protobuf::EntityStatus::STATUS_A => Ok(Self::StatusA),
             proto::protobuf::EntityStatus::STATUS_B => Ok(Self::StatusB),
@@ -54,7 +57,7 @@ impl ProtoMap for ScalarEntity {
         proto.set_status(ProtoMap::to_proto(&self.status));
         proto
     }
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         let inner = Self {
             uint32_f: ProtoMapScalar::from_scalar(proto.uint32_f().to_owned())?,
             int32_f: ProtoMapScalar::from_scalar(proto.int32_f().to_owned())?,
@@ -111,7 +114,7 @@ impl ProtoMap for ScalarEntityOptions {
 
         proto
     }
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         let inner = Self {
             // Special case for options
             uint32_f: {
@@ -185,7 +188,7 @@ impl ProtoMap for NestedEntity {
         }
         proto
     }
-    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: Self::ProtoStruct) -> Result<Self, ProtoMapError> {
         let inner = Self {
             first: ProtoMap::from_proto(proto.first().to_owned())?,
             second: {
@@ -217,7 +220,7 @@ impl ProtoMap for HierarchyEntityManual {
         inner
     }
 
-    fn from_proto(proto: proto::protobuf::HierarchyEntity) -> Result<Self, anyhow::Error> {
+    fn from_proto(proto: proto::protobuf::HierarchyEntity) -> Result<Self, ProtoMapError> {
         match proto.data {
             Some(proto::protobuf::hierarchy_entity::Data::FirstEntity(v)) => {
                 ScalarEntity::from_proto(v).map(HierarchyEntityManual::FirstEntity)
@@ -226,9 +229,7 @@ impl ProtoMap for HierarchyEntityManual {
                 NestedEntity::from_proto(v).map(HierarchyEntityManual::SecondEntity)
             }
 
-            None => Err(anyhow::anyhow!(
-                "Failed to convert HierarchyEntityManual from protobuf"
-            )),
+            None => Err(ProtoMapError::one_of_not_set("proto::protobuf::HierarchyEntity").with_field("data")),
         }
     }
 }
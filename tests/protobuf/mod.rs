@@ -1,7 +1,30 @@
 mod enum_entity_tests;
+mod enum_unknown_tests;
+mod enum_variant_attribute_tests;
+mod enum_naming_tests;
 mod manual_implementation_tests;
 mod struct_entity_attribute_tests;
 mod struct_entity_optional_tests;
 mod struct_entity_tests;
 mod struct_scalar_custom_mappings_tests;
 mod custom_mappings;
+mod conversion_error_tests;
+mod struct_repeated_tests;
+mod struct_map_tests;
+mod struct_recursive_tests;
+mod struct_well_known_types_tests;
+mod struct_wrapper_tests;
+mod struct_presence_tests;
+mod struct_numeric_tests;
+mod struct_amount_tests;
+mod struct_with_library_tests;
+mod struct_any_tests;
+mod struct_field_mask_tests;
+mod struct_merge_tests;
+mod struct_one_of_tests;
+mod enum_one_of_payload_tests;
+mod struct_optional_one_of_tests;
+mod enum_one_of_named_variant_tests;
+mod enum_tagged_tests;
+#[cfg(feature = "serde_json")]
+mod struct_json_tests;
@@ -1,6 +1,5 @@
 use crate::proto;
 use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapScalar, ProtoScalar};
-use protobuf::Enum;
 
 #[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
 #[proto_map(
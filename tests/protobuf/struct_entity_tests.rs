@@ -59,3 +59,20 @@ fn proto_entity_round_trip() {
 
     assert_eq!(tested, original);
 }
+
+#[test]
+fn entity_into_proto() {
+    let original = ScalarEntity {
+        uint32_f: 1,
+        int32_f: -10,
+        bool_f: true,
+        string_f: "Foo".into(),
+        bytes_f: "Foo".as_bytes().to_vec(),
+        status: EntityStatus::StatusC,
+    };
+
+    let expected = original.to_proto();
+    let tested: proto::protobuf::ScalarEntity = original.into_proto();
+
+    assert_eq!(tested, expected);
+}
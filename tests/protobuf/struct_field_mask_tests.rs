@@ -0,0 +1,257 @@
+use crate::proto;
+use proto_mapper::field_mask::FieldMask;
+use proto_mapper::{
+    derive::ProtoMap, field_mask_between, ProtoMap, ProtoMapError, ProtoMapFieldMask,
+    ProtoMapScalar,
+};
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ProfileAddress", field_mask)]
+struct Address {
+    pub city: String,
+    pub street: String,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(
+    source = "proto::protobuf::ProfileContact",
+    one_of(field = "value"),
+    rename_variants = "snake_case",
+    field_mask
+)]
+enum Contact {
+    Postal(Address),
+    Office(Address),
+}
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::Profile", field_mask)]
+struct Profile {
+    pub name: String,
+    pub address: Address,
+    pub billing_address: Option<Address>,
+    pub tags: Vec<String>,
+    #[proto_map(rename = "display_age")]
+    pub age: u32,
+    #[proto_map(skip)]
+    pub cached: bool,
+    pub contact: Option<Contact>,
+}
+
+fn mask(paths: &[&str]) -> FieldMask {
+    FieldMask {
+        paths: paths.iter().map(|path| path.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+fn address(city: &str, street: &str) -> Address {
+    Address {
+        city: city.into(),
+        street: street.into(),
+    }
+}
+
+fn profile() -> Profile {
+    Profile {
+        name: "first".into(),
+        address: address("Athens", "Ermou"),
+        billing_address: None,
+        tags: vec!["a".into()],
+        age: 30,
+        cached: true,
+        contact: Some(Contact::Postal(address("Athens", "Ermou"))),
+    }
+}
+
+fn update() -> Profile {
+    Profile {
+        name: "second".into(),
+        address: address("Berlin", "Unter den Linden"),
+        billing_address: Some(address("Paris", "Rivoli")),
+        tags: vec!["b".into(), "c".into()],
+        age: 40,
+        cached: false,
+        contact: Some(Contact::Office(address("Paris", "Rivoli"))),
+    }
+}
+
+#[test]
+fn merge_with_mask() {
+    let mut tested = profile();
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["name", "tags"]))
+        .unwrap();
+
+    let expected = Profile {
+        name: "second".into(),
+        tags: vec!["b".into(), "c".into()],
+        ..profile()
+    };
+    assert_eq!(tested, expected);
+}
+
+#[test]
+fn merge_with_mask_nested_paths() {
+    let mut tested = profile();
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["address.city"]))
+        .unwrap();
+    assert_eq!(tested.address, address("Berlin", "Ermou"));
+
+    // Whole paths select every nested field
+    let mut tested = profile();
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["address.city", "address"]))
+        .unwrap();
+    assert_eq!(tested.address, address("Berlin", "Unter den Linden"));
+}
+
+#[test]
+fn merge_with_mask_optional_nested_paths() {
+    // Missing optional messages are set as a whole
+    let mut tested = profile();
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["billing_address.street"]))
+        .unwrap();
+    assert_eq!(tested.billing_address, Some(address("Paris", "Rivoli")));
+
+    let mut tested = Profile {
+        billing_address: Some(address("Athens", "Ermou")),
+        ..profile()
+    };
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["billing_address.street"]))
+        .unwrap();
+    assert_eq!(tested.billing_address, Some(address("Athens", "Rivoli")));
+
+    // Unset proto messages clear the optional field
+    tested
+        .merge_from_proto_with_mask(profile().to_proto(), &mask(&["billing_address"]))
+        .unwrap();
+    assert_eq!(tested.billing_address, None);
+}
+
+#[test]
+fn merge_with_mask_one_of() {
+    // Oneofs are replaced as a whole
+    let mut tested = profile();
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["contact.office.city"]))
+        .unwrap();
+    assert_eq!(tested.contact, update().contact);
+
+    let tested = field_mask_between(&profile(), &update());
+    assert!(tested.paths.contains(&"contact".to_string()));
+}
+
+#[test]
+fn merge_with_mask_renamed_and_skipped_fields() {
+    let mut tested = profile();
+    tested
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["display_age"]))
+        .unwrap();
+    assert_eq!(tested.age, 40);
+    assert!(tested.cached);
+
+    let e = profile()
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["age"]))
+        .unwrap_err();
+    assert!(matches!(
+        e,
+        ProtoMapError::UnknownFieldMaskPath {
+            type_name: "proto::protobuf::Profile",
+            ref field,
+            ..
+        } if field == "age"
+    ));
+    assert_eq!(
+        e.to_string(),
+        "unknown field `age` in field mask of `proto::protobuf::Profile`"
+    );
+
+    let e = profile()
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["cached"]))
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "unknown field `cached` in field mask of `proto::protobuf::Profile`"
+    );
+}
+
+#[test]
+fn merge_with_mask_errors() {
+    let e = profile()
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["address.country"]))
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "unknown field `country` in field mask of `proto::protobuf::ProfileAddress` at `address`"
+    );
+
+    let e = profile()
+        .merge_from_proto_with_mask(update().to_proto(), &mask(&["tags.value"]))
+        .unwrap_err();
+    assert!(matches!(
+        &e,
+        ProtoMapError::InvalidFieldMaskPath { field, .. } if field == "value"
+    ));
+    assert_eq!(
+        e.to_string(),
+        "field mask selects nested field `value` of a field that is overwritten only as a whole at `tags`"
+    );
+}
+
+#[test]
+fn field_mask_between_values() {
+    let tested = field_mask_between(&profile(), &profile());
+    assert!(tested.paths.is_empty());
+
+    let mut target = Profile {
+        address: address("Athens", "Unter den Linden"),
+        ..update()
+    };
+    target.billing_address = Some(address("Paris", "Rivoli"));
+    let tested = Profile::field_mask_between(&profile(), &target);
+    assert_eq!(
+        tested.paths,
+        vec![
+            "name",
+            "address.street",
+            "billing_address",
+            "tags",
+            "display_age",
+            "contact"
+        ]
+    );
+
+    // Merging the field mask of two values results in the second value (except the skipped fields)
+    let mut merged = profile();
+    merged
+        .merge_from_proto_with_mask(target.to_proto(), &tested)
+        .unwrap();
+    assert_eq!(
+        merged,
+        Profile {
+            cached: true,
+            ..target
+        }
+    );
+}
+
+#[test]
+fn field_mask_between_optional_nested_values() {
+    let a = Profile {
+        billing_address: Some(address("Athens", "Ermou")),
+        ..profile()
+    };
+    let b = Profile {
+        billing_address: Some(address("Athens", "Rivoli")),
+        ..profile()
+    };
+    let tested = field_mask_between(&a, &b);
+    assert_eq!(tested.paths, vec!["billing_address.street"]);
+
+    let tested = field_mask_between(&a, &profile());
+    assert_eq!(tested.paths, vec!["billing_address"]);
+}
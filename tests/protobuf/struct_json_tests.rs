@@ -0,0 +1,171 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError};
+use protobuf::well_known_types::struct_::{value::Kind, ListValue, Struct, Value as ProtoValue};
+use protobuf::MessageField;
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::JsonEntity")]
+struct JsonEntity {
+    pub metadata: Map<String, Value>,
+    pub value: Value,
+    pub opt_metadata: Option<Map<String, Value>>,
+    pub opt_value: Option<Value>,
+    pub values: Vec<Value>,
+}
+
+fn value(kind: Kind) -> ProtoValue {
+    ProtoValue {
+        kind: Some(kind),
+        ..Default::default()
+    }
+}
+
+fn invalid_struct() -> Struct {
+    Struct {
+        fields: [(
+            "list".to_string(),
+            value(Kind::ListValue(ListValue {
+                values: vec![
+                    value(Kind::BoolValue(true)),
+                    value(Kind::NumberValue(f64::INFINITY)),
+                ],
+                ..Default::default()
+            })),
+        )]
+        .into_iter()
+        .collect(),
+        ..Default::default()
+    }
+}
+
+fn json_entity() -> JsonEntity {
+    let Value::Object(metadata) = json!({
+        "name": "entity",
+        "count": 42,
+        "negative": -7,
+        "ratio": 0.25,
+        "enabled": true,
+        "missing": null,
+        "tags": ["a", 1, false, null, {"nested": [1.5, []]}],
+        "object": {"empty": {}},
+    }) else {
+        unreachable!()
+    };
+
+    JsonEntity {
+        metadata: metadata.clone(),
+        value: json!([1, "two", {"three": 3}]),
+        opt_metadata: Some(metadata),
+        opt_value: Some(Value::Null),
+        values: vec![json!("first"), json!(2), json!(null)],
+    }
+}
+
+#[test]
+fn json_entity_round_trip() {
+    let original = json_entity();
+
+    let tested = JsonEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = JsonEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn json_entity_missing_optional() {
+    let original = JsonEntity {
+        opt_metadata: None,
+        opt_value: None,
+        ..json_entity()
+    };
+
+    let tested = JsonEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn json_entity_numbers() {
+    let original = JsonEntity {
+        value: json!([
+            u64::MAX,
+            i64::MIN,
+            9_007_199_254_740_992_u64,
+            1.0,
+            -0.5,
+            1e300
+        ]),
+        ..json_entity()
+    };
+
+    let tested = JsonEntity::from_proto(original.to_proto()).unwrap();
+    // Integers that do not fit exactly to an `f64` are strings and whole numbers are integers
+    assert_eq!(
+        tested.value,
+        json!([
+            u64::MAX.to_string(),
+            i64::MIN.to_string(),
+            9_007_199_254_740_992_u64,
+            1,
+            -0.5,
+            1e300
+        ])
+    );
+}
+
+#[test]
+fn json_entity_large_integers_are_strings() {
+    let original = JsonEntity {
+        value: json!(u64::MAX),
+        ..json_entity()
+    };
+
+    let p = original.to_proto();
+    assert_eq!(
+        p.value.as_ref().and_then(|value| value.kind.clone()),
+        Some(Kind::StringValue(u64::MAX.to_string()))
+    );
+
+    // The number does not round trip
+    let tested = JsonEntity::from_proto(p).unwrap();
+    assert_ne!(tested, original);
+    assert_eq!(tested.value, Value::String(u64::MAX.to_string()));
+}
+
+#[test]
+fn json_entity_value_without_kind() {
+    let mut p = json_entity().to_proto();
+    p.value = MessageField::some(ProtoValue::default());
+
+    let tested = JsonEntity::from_proto(p).unwrap();
+    assert_eq!(tested.value, Value::Null);
+}
+
+#[test]
+fn json_entity_errors() {
+    let mut p = json_entity().to_proto();
+    p.metadata = MessageField::some(invalid_struct());
+    p.value = MessageField::some(value(Kind::NumberValue(f64::NAN)));
+    p.values = vec![
+        value(Kind::StringValue("ok".into())),
+        value(Kind::NumberValue(f64::NEG_INFINITY)),
+    ];
+
+    let errors = JsonEntity::try_from_proto_all(p).unwrap_err();
+    let errors = errors.errors();
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(errors[0], ProtoMapError::OutOfRange { .. }));
+    assert_eq!(
+        errors[0].to_string(),
+        "value inf is out of range for `serde_json::Number` at `metadata[list][1]`"
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "value NaN is out of range for `serde_json::Number` at `value`"
+    );
+    assert_eq!(
+        errors[2].to_string(),
+        "value -inf is out of range for `serde_json::Number` at `values[1]`"
+    );
+}
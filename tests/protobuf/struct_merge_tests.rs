@@ -0,0 +1,208 @@
+use crate::proto;
+use proto_mapper::{
+    derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapMerge, ProtoMapScalar, ProtoScalar,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::MergeSettings", merge)]
+struct Settings {
+    pub theme: String,
+    pub font_size: u8,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ProfileAddress")]
+struct Address {
+    pub city: String,
+    pub street: String,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(
+    source = "proto::protobuf::ProfileContact",
+    one_of(field = "value"),
+    rename_variants = "snake_case",
+    merge
+)]
+enum Contact {
+    Postal(Address),
+    Office(Address),
+}
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::MergeEntity", merge)]
+struct Entity {
+    pub name: String,
+    pub count: u32,
+    #[proto_map(presence = "explicit")]
+    pub nickname: Option<String>,
+    pub settings: Settings,
+    pub overrides: Option<Settings>,
+    pub tags: Vec<String>,
+    pub limits: HashMap<String, u32>,
+    pub contact: Option<Contact>,
+    #[proto_map(skip)]
+    pub cached: bool,
+}
+
+fn address(city: &str) -> Address {
+    Address {
+        city: city.into(),
+        street: "Ermou".into(),
+    }
+}
+
+fn entity() -> Entity {
+    Entity {
+        name: "first".into(),
+        count: 1,
+        nickname: Some("one".into()),
+        settings: Settings {
+            theme: "dark".into(),
+            font_size: 12,
+        },
+        overrides: None,
+        tags: vec!["a".into()],
+        limits: HashMap::from([("a".into(), 1), ("b".into(), 2)]),
+        contact: Some(Contact::Postal(address("Athens"))),
+        cached: true,
+    }
+}
+
+#[test]
+fn merge_empty_proto() {
+    let mut tested = entity();
+    tested.merge_from_proto(Default::default()).unwrap();
+    assert_eq!(tested, entity());
+}
+
+#[test]
+fn merge_scalars() {
+    let mut tested = entity();
+    let delta = proto::protobuf::MergeEntity {
+        count: 2,
+        nickname: Some(String::new()),
+        ..Default::default()
+    };
+    tested.merge_from_proto(delta).unwrap();
+
+    // Explicit presence fields are set even with default values
+    let expected = Entity {
+        count: 2,
+        nickname: Some(String::new()),
+        ..entity()
+    };
+    assert_eq!(tested, expected);
+}
+
+#[test]
+fn merge_nested_messages() {
+    let mut tested = entity();
+    let delta = proto::protobuf::MergeEntity {
+        settings: Some(proto::protobuf::MergeSettings {
+            font_size: 14,
+            ..Default::default()
+        })
+        .into(),
+        overrides: Some(proto::protobuf::MergeSettings {
+            theme: "light".into(),
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    tested.merge_from_proto(delta.clone()).unwrap();
+
+    let mut expected = entity();
+    expected.settings.font_size = 14;
+    expected.overrides = Some(Settings {
+        theme: "light".into(),
+        font_size: 0,
+    });
+    assert_eq!(tested, expected);
+
+    // Set optional messages merge as well
+    tested.overrides.as_mut().unwrap().font_size = 10;
+    tested.merge_from_proto(delta).unwrap();
+    assert_eq!(
+        tested.overrides,
+        Some(Settings {
+            theme: "light".into(),
+            font_size: 10,
+        })
+    );
+}
+
+#[test]
+fn merge_repeated_and_maps() {
+    let mut tested = entity();
+    let delta = proto::protobuf::MergeEntity {
+        tags: vec!["b".into(), "c".into()],
+        limits: HashMap::from([("b".into(), 20), ("c".into(), 30)]),
+        ..Default::default()
+    };
+    tested.merge_from_proto(delta).unwrap();
+
+    assert_eq!(tested.tags, vec!["a", "b", "c"]);
+    assert_eq!(
+        tested.limits,
+        HashMap::from([("a".into(), 1), ("b".into(), 20), ("c".into(), 30)])
+    );
+}
+
+#[test]
+fn merge_one_of() {
+    let mut tested = entity();
+    let delta = Entity {
+        contact: Some(Contact::Office(address("Paris"))),
+        ..Default::default()
+    };
+    tested.merge_from_proto(delta.to_proto()).unwrap();
+    assert_eq!(tested.contact, Some(Contact::Office(address("Paris"))));
+
+    // Unset oneofs leave the current variant
+    let delta = proto::protobuf::MergeEntity {
+        contact: Some(Default::default()).into(),
+        ..Default::default()
+    };
+    tested.merge_from_proto(delta).unwrap();
+    assert_eq!(tested.contact, Some(Contact::Office(address("Paris"))));
+
+    // The same member replaces the current variant as a whole (the messages are not merged)
+    let delta = Entity {
+        contact: Some(Contact::Office(Address {
+            city: "Rome".into(),
+            street: String::new(),
+        })),
+        ..Default::default()
+    };
+    tested.merge_from_proto(delta.to_proto()).unwrap();
+    assert_eq!(
+        tested.contact,
+        Some(Contact::Office(Address {
+            city: "Rome".into(),
+            street: String::new(),
+        }))
+    );
+}
+
+#[test]
+fn merge_errors() {
+    let mut tested = entity();
+    let delta = proto::protobuf::MergeEntity {
+        name: "second".into(),
+        settings: Some(proto::protobuf::MergeSettings {
+            font_size: 300,
+            ..Default::default()
+        })
+        .into(),
+        ..Default::default()
+    };
+    let e = tested.merge_from_proto(delta).unwrap_err();
+    assert!(matches!(e, ProtoMapError::OutOfRange { .. }));
+    assert_eq!(e.path().to_string(), "settings.font_size");
+
+    // Fields merged before the failure remain merged
+    assert_eq!(tested.name, "second");
+}
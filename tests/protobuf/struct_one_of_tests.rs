@@ -0,0 +1,260 @@
+use crate::proto;
+use proto_mapper::field_mask::{field_mask_between, FieldMask};
+use proto_mapper::{
+    derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapFieldMask, ProtoMapMerge, ProtoMapOneOf,
+    ProtoMapScalar, ProtoScalar,
+};
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    pub int32_f: i32,
+    pub bool_f: bool,
+    pub string_f: String,
+    pub bytes_f: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::NestedEntity")]
+struct NestedEntity {
+    #[proto_map(required)]
+    pub first: ScalarEntity,
+    pub second: ScalarEntity,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::DocumentEntity", one_of(field = "content"))]
+enum DocumentContent {
+    Scalar(ScalarEntity),
+    Nested(NestedEntity),
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::DocumentEntity", field_mask, merge)]
+struct Document {
+    pub title: String,
+    #[proto_map(one_of = "content")]
+    pub body: DocumentContent,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(
+    source = "proto::protobuf::catalog_entity::Section",
+    one_of(
+        field = "content",
+        path = "proto::protobuf::catalog_entity::section::Content"
+    )
+)]
+enum SectionContent {
+    Scalar(ScalarEntity),
+    Nested(NestedEntity),
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::catalog_entity::Section")]
+struct Section {
+    pub name: String,
+    #[proto_map(one_of = "content")]
+    pub content: SectionContent,
+}
+
+fn scalar(string_f: &str) -> ScalarEntity {
+    ScalarEntity {
+        uint32_f: 1,
+        int32_f: -10,
+        bool_f: true,
+        string_f: string_f.into(),
+        bytes_f: string_f.as_bytes().to_vec(),
+    }
+}
+
+fn document() -> Document {
+    Document {
+        title: "Foo".into(),
+        body: DocumentContent::Nested(NestedEntity {
+            first: scalar("Foo1"),
+            second: scalar("Foo2"),
+        }),
+        tags: vec!["a".into(), "b".into()],
+    }
+}
+
+fn mask(paths: &[&str]) -> FieldMask {
+    FieldMask {
+        paths: paths.iter().map(|path| path.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn one_of_field_round_trips() {
+    let original = document();
+
+    let p = original.to_proto();
+    assert_eq!(p.title(), "Foo");
+    assert!(p.has_nested());
+    assert_eq!(p.tags, vec!["a".to_string(), "b".to_string()]);
+
+    let tested = Document::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+
+    let original = Document {
+        body: DocumentContent::Scalar(scalar("Bar")),
+        ..document()
+    };
+    let tested = Document::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn one_of_field_into_proto() {
+    let original = document();
+
+    let expected = original.to_proto();
+    let tested: proto::protobuf::DocumentEntity = original.into_proto();
+    assert_eq!(tested, expected);
+}
+
+#[test]
+fn one_of_enum_converts_the_members() {
+    let original = DocumentContent::Scalar(scalar("Foo"));
+
+    let member = original.to_one_of();
+    assert!(matches!(
+        member,
+        Some(proto::protobuf::document_entity::Content::Scalar(_))
+    ));
+    let tested = DocumentContent::from_one_of(member).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn one_of_field_not_set_error() {
+    let mut p = document().to_proto();
+    p.content = None;
+
+    let error = Document::from_proto(p.clone()).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::OneOfNotSet {
+            type_name: "proto::protobuf::DocumentEntity",
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "content");
+
+    let errors = Document::try_from_proto_all(p).unwrap_err();
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn one_of_field_member_error_path() {
+    let mut p = document().to_proto();
+    p.set_nested(proto::protobuf::NestedEntity::default());
+
+    let error = Document::from_proto(p).unwrap_err();
+    assert!(matches!(error, ProtoMapError::MissingMessage { .. }));
+    assert_eq!(error.path().to_string(), "nested.first");
+}
+
+#[test]
+fn nested_message_one_of_field_round_trips() {
+    let original = Section {
+        name: "Foo".into(),
+        content: SectionContent::Scalar(scalar("Foo")),
+    };
+
+    let p = original.to_proto();
+    assert!(p.has_scalar());
+    let tested = Section::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn one_of_field_merges_as_a_whole() {
+    let mut tested = document();
+    let delta = Document {
+        title: "Bar".into(),
+        body: DocumentContent::Scalar(scalar("Bar")),
+        tags: vec![],
+    };
+
+    tested
+        .merge_from_proto_with_mask(delta.to_proto(), &mask(&["scalar"]))
+        .unwrap();
+    assert_eq!(tested.title, "Foo");
+    assert_eq!(tested.body, delta.body);
+
+    let mut tested = document();
+    tested.merge_from_proto(delta.to_proto()).unwrap();
+    assert_eq!(tested.title, "Bar");
+    assert_eq!(tested.body, delta.body);
+    assert_eq!(tested.tags, vec!["a".to_string(), "b".to_string()]);
+
+    // Not set oneof fields keep the current variant
+    let mut tested = document();
+    tested
+        .merge_from_proto(proto::protobuf::DocumentEntity::default())
+        .unwrap();
+    assert_eq!(tested, document());
+}
+
+#[test]
+fn one_of_field_merge_error_path() {
+    let mut p = document().to_proto();
+    p.set_nested(proto::protobuf::NestedEntity::default());
+
+    let mut tested = document();
+    let error = tested.merge_from_proto(p).unwrap_err();
+    assert!(matches!(error, ProtoMapError::MissingMessage { .. }));
+    assert_eq!(error.path().to_string(), "content.nested.first");
+}
+
+#[test]
+fn one_of_field_mask_selects_the_members() {
+    let delta = Document {
+        title: "Bar".into(),
+        body: DocumentContent::Scalar(scalar("Bar")),
+        tags: vec![],
+    };
+
+    // Any member selects the whole `oneof`
+    let mut tested = document();
+    tested
+        .merge_from_proto_with_mask(delta.to_proto(), &mask(&["nested"]))
+        .unwrap();
+    assert_eq!(tested.title, "Foo");
+    assert_eq!(tested.body, delta.body);
+
+    let mut tested = document();
+    tested
+        .merge_from_proto_with_mask(delta.to_proto(), &mask(&["title"]))
+        .unwrap();
+    assert_eq!(tested.body, document().body);
+
+    // The `oneof` name and nested paths of the members are not valid paths
+    let error = document()
+        .merge_from_proto_with_mask(delta.to_proto(), &mask(&["content"]))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::UnknownFieldMaskPath {
+            type_name: "proto::protobuf::DocumentEntity",
+            ..
+        }
+    ));
+    assert!(document()
+        .merge_from_proto_with_mask(delta.to_proto(), &mask(&["scalar.string_f"]))
+        .is_err());
+
+    // The differences of the `oneof` are the paths of the new members
+    let tested = field_mask_between(&document(), &delta);
+    assert_eq!(tested.paths, vec!["title", "scalar", "tags"]);
+    let mut merged = document();
+    merged
+        .merge_from_proto_with_mask(delta.to_proto(), &tested)
+        .unwrap();
+    assert_eq!(merged, delta);
+}
@@ -0,0 +1,162 @@
+use crate::proto;
+use proto_mapper::{
+    derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapMerge, ProtoMapOneOf, ProtoMapScalar,
+    ProtoScalar,
+};
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ScalarEntity")]
+struct ScalarEntity {
+    pub uint32_f: u32,
+    pub string_f: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::NestedEntity")]
+struct NestedEntity {
+    pub first: ScalarEntity,
+    pub second: ScalarEntity,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::DocumentEntity", one_of(field = "content"))]
+enum DocumentContent {
+    Scalar(ScalarEntity),
+    Nested(NestedEntity),
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::DocumentEntity", merge)]
+struct Document {
+    pub title: String,
+    #[proto_map(one_of = "content")]
+    pub body: Option<DocumentContent>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::ContactEntity",
+    one_of(field = "contact", not_set = "default")
+)]
+enum Contact {
+    Email(String),
+    #[proto_map(default)]
+    Phone(i64),
+    Anonymous,
+}
+
+#[derive(Debug, Clone, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::ContactEntity",
+    one_of(field = "contact", not_set = "variant"),
+    merge
+)]
+enum OptionalContact {
+    Email(String),
+    Phone(i64),
+    Anonymous,
+    #[proto_map(not_set)]
+    NotSet,
+}
+
+#[test]
+fn optional_one_of_field_round_trips() {
+    let original = Document {
+        title: "Foo".into(),
+        body: Some(DocumentContent::Scalar(ScalarEntity {
+            uint32_f: 1,
+            string_f: "Foo".into(),
+        })),
+        tags: vec!["a".into()],
+    };
+    let tested = Document::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let original = Document {
+        body: None,
+        ..original
+    };
+    let p = original.clone().into_proto();
+    assert_eq!(p.content, None);
+    let tested = Document::from_proto(p.clone()).unwrap();
+    assert_eq!(tested, original);
+    let tested = Document::try_from_proto_all(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn optional_one_of_field_merges_set_members() {
+    let mut tested = Document {
+        title: "Foo".into(),
+        body: None,
+        tags: vec![],
+    };
+    let delta = Document {
+        title: "Foo".into(),
+        body: Some(DocumentContent::Nested(NestedEntity::default())),
+        tags: vec![],
+    };
+    tested.merge_from_proto(delta.to_proto()).unwrap();
+    assert_eq!(tested.body, delta.body);
+
+    // Not set oneof fields keep the current variant
+    tested
+        .merge_from_proto(proto::protobuf::DocumentEntity::default())
+        .unwrap();
+    assert_eq!(tested.body, delta.body);
+}
+
+#[test]
+fn not_set_default_variant() {
+    let tested = Contact::from_proto(proto::protobuf::ContactEntity::default()).unwrap();
+    assert_eq!(tested, Contact::Phone(0));
+
+    let tested = Contact::try_from_one_of_all(None).unwrap();
+    assert_eq!(tested, Contact::Phone(0));
+
+    // The default variant is a regular member of the oneof
+    let original = Contact::Phone(42);
+    assert_eq!(
+        original.to_one_of(),
+        Some(proto::protobuf::contact_entity::Contact::Phone(42))
+    );
+    let tested = Contact::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn not_set_variant() {
+    let p = OptionalContact::NotSet.to_proto();
+    assert_eq!(p.contact, None);
+    let tested = OptionalContact::from_proto(p).unwrap();
+    assert_eq!(tested, OptionalContact::NotSet);
+
+    for original in [
+        OptionalContact::Email("foo@bar.com".into()),
+        OptionalContact::Anonymous,
+    ] {
+        let tested = OptionalContact::from_proto(original.clone().into_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+
+    // Not set oneof fields keep the current variant
+    let mut tested = OptionalContact::Anonymous;
+    tested
+        .merge_from_proto(OptionalContact::NotSet.to_proto())
+        .unwrap();
+    assert_eq!(tested, OptionalContact::Anonymous);
+}
+
+#[test]
+fn not_set_error() {
+    let error = DocumentContent::from_one_of(None).unwrap_err();
+    assert!(matches!(
+        error,
+        ProtoMapError::OneOfNotSet {
+            type_name: "proto::protobuf::DocumentEntity",
+            ..
+        }
+    ));
+    assert_eq!(error.path().to_string(), "content");
+}
@@ -0,0 +1,211 @@
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapScalar, ProtoScalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::EntityStatus",
+    enumeration,
+    rename_variants = "STREAMING_SNAKE_CASE"
+)]
+enum EntityStatus {
+    StatusA,
+    StatusB,
+    StatusC,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ProtoMap)]
+#[proto_map(
+    source = "proto::protobuf::LegacyStatus",
+    enumeration,
+    rename_variants = "STREAMING_SNAKE_CASE"
+)]
+enum LegacyStatus {
+    LegacyA,
+    LegacyB,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq, Default)]
+#[proto_map(source = "proto::protobuf::OptionalEntity")]
+struct OptionalEntity {
+    #[proto_map(presence = "explicit")]
+    pub uint32_f: Option<u32>,
+    #[proto_map(presence = "explicit")]
+    pub int32_f: Option<i32>,
+    #[proto_map(presence = "explicit")]
+    pub bool_f: Option<bool>,
+    #[proto_map(presence = "explicit")]
+    pub string_f: Option<String>,
+    #[proto_map(presence = "explicit")]
+    pub bytes_f: Option<Vec<u8>>,
+    #[proto_map(presence = "explicit")]
+    pub double_f: Option<f64>,
+    #[proto_map(enumeration, presence = "explicit")]
+    pub status: Option<EntityStatus>,
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::LegacyEntity")]
+struct LegacyEntity {
+    pub id: u32,
+    pub name: String,
+    #[proto_map(presence = "explicit")]
+    pub count: Option<i64>,
+    #[proto_map(presence = "explicit")]
+    pub label: Option<String>,
+    #[proto_map(enumeration, presence = "explicit")]
+    pub status: Option<LegacyStatus>,
+}
+
+#[test]
+fn optional_entity_round_trip() {
+    let original = OptionalEntity {
+        uint32_f: Some(100),
+        int32_f: Some(-1000),
+        bool_f: Some(true),
+        string_f: Some("Foo".into()),
+        bytes_f: Some("Foo".as_bytes().to_vec()),
+        double_f: Some(1.5),
+        status: Some(EntityStatus::StatusB),
+    };
+
+    let tested = OptionalEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = OptionalEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn optional_entity_default_values_round_trip() {
+    // Unlike implicit presence the default values (including the first enumeration variant) are kept
+    let original = OptionalEntity {
+        uint32_f: Some(0),
+        int32_f: Some(0),
+        bool_f: Some(false),
+        string_f: Some("".into()),
+        bytes_f: Some(vec![]),
+        double_f: Some(0.0),
+        status: Some(EntityStatus::StatusA),
+    };
+
+    let p = original.to_proto();
+    assert_ne!(p, proto::protobuf::OptionalEntity::default());
+    let tested = OptionalEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn optional_entity_none_round_trip() {
+    let original = OptionalEntity::default();
+
+    let p = original.to_proto();
+    assert_eq!(p, proto::protobuf::OptionalEntity::default());
+    let tested = OptionalEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn legacy_entity_round_trip() {
+    let original = LegacyEntity {
+        id: 0,
+        name: "Foo".into(),
+        count: Some(0),
+        label: None,
+        status: Some(LegacyStatus::LegacyA),
+    };
+
+    let tested = LegacyEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let original = LegacyEntity {
+        id: 1,
+        name: "".into(),
+        count: None,
+        label: Some("".into()),
+        status: None,
+    };
+
+    let tested = LegacyEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ScalarEntity", presence = "always")]
+struct ScalarEntityPolicies {
+    pub uint32_f: Option<u32>,
+    pub bool_f: Option<bool>,
+    #[proto_map(enumeration)]
+    pub status: Option<EntityStatus>,
+    #[proto_map(presence(sentinel = -1))]
+    pub int32_f: Option<i32>,
+    #[proto_map(presence(sentinel = "none"))]
+    pub string_f: Option<String>,
+    #[proto_map(presence = "implicit")]
+    pub int64_f: Option<i64>,
+}
+
+#[test]
+fn presence_policies_round_trip() {
+    let original = ScalarEntityPolicies {
+        uint32_f: Some(0),
+        bool_f: Some(false),
+        status: Some(EntityStatus::StatusA),
+        int32_f: Some(0),
+        string_f: Some("".into()),
+        int64_f: Some(10),
+    };
+
+    let tested = ScalarEntityPolicies::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = ScalarEntityPolicies::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn presence_policies_none_round_trip() {
+    let original = ScalarEntityPolicies {
+        uint32_f: None,
+        bool_f: None,
+        status: None,
+        int32_f: None,
+        string_f: None,
+        int64_f: None,
+    };
+
+    let p = original.to_proto();
+    assert_eq!(p.int32_f, -1);
+    assert_eq!(p.string_f, "none");
+
+    // Always present fields map back to the default values
+    let tested = ScalarEntityPolicies::from_proto(p).unwrap();
+    assert_eq!(tested.uint32_f, Some(0));
+    assert_eq!(tested.bool_f, Some(false));
+    assert_eq!(tested.status, Some(EntityStatus::StatusA));
+    assert_eq!(tested.int32_f, None);
+    assert_eq!(tested.string_f, None);
+    assert_eq!(tested.int64_f, None);
+}
+
+#[derive(Debug, Clone, ProtoMap, PartialEq)]
+#[proto_map(source = "proto::protobuf::ScalarEntity")]
+struct ScalarEntitySentinel {
+    #[proto_map(enumeration, presence(sentinel = 2))]
+    pub status: Option<EntityStatus>,
+}
+
+#[test]
+fn enumeration_sentinel_round_trip() {
+    for original in [
+        ScalarEntitySentinel { status: None },
+        ScalarEntitySentinel {
+            status: Some(EntityStatus::StatusA),
+        },
+        ScalarEntitySentinel {
+            status: Some(EntityStatus::StatusB),
+        },
+    ] {
+        let tested = ScalarEntitySentinel::from_proto(original.to_proto()).unwrap();
+        assert_eq!(tested, original);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::proto;
 use proto_mapper::derive::ProtoMap;
-use proto_mapper::{ProtoMap, ProtoMapScalar, ProtoScalar};
+use proto_mapper::{ProtoMap, ProtoScalar};
 use super::custom_mappings::{uuid_as_bytes, uuid_as_string};
 use uuid::Uuid;
 
@@ -0,0 +1,93 @@
+use super::custom_mappings::uuid_as_string;
+use crate::proto;
+use proto_mapper::{derive::ProtoMap, ProtoMap, ProtoMapError, ProtoMapScalar};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, ProtoMap, PartialEq, Default)]
+#[proto_map(source = "proto::protobuf::WrapperEntity")]
+struct WrapperEntity {
+    #[proto_map(wrapper)]
+    pub int32_f: Option<i32>,
+    #[proto_map(wrapper)]
+    pub uint32_f: Option<u32>,
+    #[proto_map(wrapper)]
+    pub int64_f: Option<i64>,
+    #[proto_map(wrapper)]
+    pub uint64_f: Option<u64>,
+    #[proto_map(wrapper)]
+    pub bool_f: Option<bool>,
+    #[proto_map(wrapper)]
+    pub string_f: Option<String>,
+    #[proto_map(wrapper)]
+    pub bytes_f: Option<Vec<u8>>,
+    #[proto_map(wrapper)]
+    pub float_f: Option<f32>,
+    #[proto_map(wrapper)]
+    pub double_f: Option<f64>,
+    #[proto_map(wrapper, scalar, with = "uuid_as_string")]
+    pub uuid_f: Option<Uuid>,
+}
+
+#[test]
+fn wrapper_entity_round_trip() {
+    let original = WrapperEntity {
+        int32_f: Some(-1000),
+        uint32_f: Some(100),
+        int64_f: Some(-1_000_000),
+        uint64_f: Some(1_000_000),
+        bool_f: Some(true),
+        string_f: Some("Foo".into()),
+        bytes_f: Some("Foo".as_bytes().to_vec()),
+        float_f: Some(1.5),
+        double_f: Some(-2.5),
+        uuid_f: Some(Uuid::new_v4()),
+    };
+
+    let tested = WrapperEntity::from_proto(original.to_proto()).unwrap();
+    assert_eq!(tested, original);
+
+    let tested = WrapperEntity::from_proto(original.clone().into_proto()).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn wrapper_entity_default_values_round_trip() {
+    // Unlike plain optional scalars the default values are kept
+    let original = WrapperEntity {
+        int32_f: Some(0),
+        uint32_f: Some(0),
+        int64_f: Some(0),
+        uint64_f: Some(0),
+        bool_f: Some(false),
+        string_f: Some("".into()),
+        bytes_f: Some(vec![]),
+        float_f: Some(0.0),
+        double_f: Some(0.0),
+        uuid_f: Some(Uuid::nil()),
+    };
+
+    let p = original.to_proto();
+    assert_ne!(p, proto::protobuf::WrapperEntity::default());
+    let tested = WrapperEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn wrapper_entity_none_round_trip() {
+    let original = WrapperEntity::default();
+
+    let p = original.to_proto();
+    assert_eq!(p, proto::protobuf::WrapperEntity::default());
+    let tested = WrapperEntity::from_proto(p).unwrap();
+    assert_eq!(tested, original);
+}
+
+#[test]
+fn wrapper_custom_error() {
+    let mut p = WrapperEntity::default().to_proto();
+    p.mut_uuid_f().value = "not a uuid".into();
+
+    let error = WrapperEntity::from_proto(p).unwrap_err();
+    assert!(matches!(error, ProtoMapError::Custom { .. }));
+    assert_eq!(error.path().to_string(), "uuid_f");
+}